@@ -76,4 +76,21 @@ void spvc_rs_compiler_get_execution_model_indirect(spvc_compiler compiler, SpvEx
     *out = spvc_compiler_get_execution_model(compiler);
 }
 
+spvc_result spvc_rs_compiler_cpp_set_interface_name(spvc_compiler compiler, const char* name) {
+#if SPIRV_CROSS_C_API_CPP
+    if (compiler->backend != SPVC_BACKEND_CPP) {
+        compiler->context->report_error("C++ function used on a non-C++ backend.");
+        return SPVC_ERROR_INVALID_ARGUMENT;
+    }
+
+    auto &cpp = *static_cast<CompilerCPP *>(compiler->compiler.get());
+    cpp.set_interface_name(name);
+    return SPVC_SUCCESS;
+#else
+    (void)name;
+    compiler->context->report_error("C++ function used on a non-C++ backend.");
+    return SPVC_ERROR_INVALID_ARGUMENT;
+#endif
+}
+
 } // extern "C"
\ No newline at end of file
@@ -15,4 +15,6 @@ spvc_bool spvc_rs_type_is_pointer(spvc_type type);
 
 spvc_bool spvc_rs_type_is_forward_pointer(spvc_type type);
 
-void spvc_rs_compiler_get_execution_model_indirect(spvc_compiler compiler, SpvExecutionModel* out);
\ No newline at end of file
+void spvc_rs_compiler_get_execution_model_indirect(spvc_compiler compiler, SpvExecutionModel* out);
+
+spvc_result spvc_rs_compiler_cpp_set_interface_name(spvc_compiler compiler, const char* name);
\ No newline at end of file
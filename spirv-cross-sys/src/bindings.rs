@@ -4086,3 +4086,10 @@ extern "C" {
         out: *mut SpvExecutionModel,
     );
 }
+extern "C" {
+    #[must_use]
+    pub fn spvc_rs_compiler_cpp_set_interface_name(
+        compiler: spvc_compiler,
+        name: *const crate::ctypes::c_char,
+    ) -> spvc_result;
+}
@@ -0,0 +1,70 @@
+//! Compares the per-module overhead of reflecting with `Compiler<None>`
+//! against a compilable target.
+//!
+//! Run with `cargo bench --bench reflection`.
+
+use spirv_cross2::reflect::ResourceType;
+use spirv_cross2::targets::Target;
+use spirv_cross2::{targets, Compiler, Module, SpirvCrossError};
+use std::time::{Duration, Instant};
+
+static BASIC_SPV: &[u8] = include_bytes!("../basic.spv");
+
+const ITERATIONS: u32 = 10_000;
+
+fn reflect<T: Target>(words: &[u32]) -> Result<usize, SpirvCrossError> {
+    let compiler = Compiler::<T>::new(Module::from_words(words))?;
+    let resources = compiler.shader_resources()?;
+
+    let mut count = 0;
+    for resource in resources.resources_for_type(ResourceType::UniformBuffer)? {
+        count += compiler.active_buffer_ranges(resource.id)?.len();
+    }
+    count += resources
+        .resources_for_type(ResourceType::SampledImage)?
+        .count();
+
+    Ok(count)
+}
+
+fn bench<T: Target>(words: &[u32]) -> Result<Duration, SpirvCrossError> {
+    // Warm up.
+    let mut total = 0;
+    for _ in 0..ITERATIONS / 10 {
+        total += reflect::<T>(words)?;
+    }
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        total += reflect::<T>(words)?;
+    }
+    let elapsed = start.elapsed() / ITERATIONS;
+
+    // Keep the results observable so the work is not optimized out.
+    assert_ne!(0, total);
+    Ok(elapsed)
+}
+
+fn main() -> Result<(), SpirvCrossError> {
+    let vec = Vec::from(BASIC_SPV);
+    let words: &[u32] = bytemuck::cast_slice(&vec);
+
+    println!(
+        "Compiler<None>: {:?} per module",
+        bench::<targets::None>(words)?
+    );
+
+    #[cfg(feature = "glsl")]
+    println!(
+        "Compiler<Glsl>: {:?} per module",
+        bench::<targets::Glsl>(words)?
+    );
+
+    #[cfg(feature = "msl")]
+    println!(
+        "Compiler<Msl>:  {:?} per module",
+        bench::<targets::Msl>(words)?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+//! Measures reflecting every type of a module with deeply nested structs,
+//! on a fresh compiler with an empty cache, and on a compiler that has already
+//! described its types.
+//!
+//! Run with `cargo bench --bench type_description`.
+
+use glslang::SpirvVersion::SPIRV1_6;
+use glslang::{CompilerOptions, ShaderInput, ShaderSource, ShaderStage, Target, VulkanVersion};
+use spirv_cross2::handle::{Handle, TypeId};
+use spirv_cross2::reflect::TypeInner;
+use spirv_cross2::{targets, Compiler, Module, SpirvCrossError};
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+const DEPTH: usize = 12;
+const ITERATIONS: u32 = 100;
+
+/// Generate a compute shader with a storage buffer of `DEPTH` levels of nested structs.
+fn shader() -> String {
+    let mut source = String::from("#version 450\nlayout(local_size_x = 1) in;\n");
+    source.push_str("struct S0 { vec4 a; mat4 b; float c[4]; };\n");
+    for level in 1..DEPTH {
+        let inner = level - 1;
+        writeln!(
+            source,
+            "struct S{level} {{ S{inner} a[2]; S{inner} b; uvec2 c; }};"
+        )
+        .unwrap();
+    }
+
+    let outer = DEPTH - 1;
+    writeln!(
+        source,
+        "layout(set = 0, binding = 0) buffer Data {{ S{outer} value; }} data;"
+    )
+    .unwrap();
+    source.push_str("void main() { data.value.c = uvec2(1u); }\n");
+    source
+}
+
+fn walk(compiler: &Compiler<targets::None>, id: Handle<TypeId>) -> Result<usize, SpirvCrossError> {
+    let ty = compiler.type_description(id)?;
+
+    let mut count = 1;
+    match ty.inner {
+        TypeInner::Struct(ty) => {
+            for member in ty.members {
+                count += walk(compiler, member.id)?;
+            }
+        }
+        TypeInner::Array { base, .. } | TypeInner::Pointer { base, .. } => {
+            count += walk(compiler, base)?;
+        }
+        _ => {}
+    }
+
+    Ok(count)
+}
+
+fn reflect(compiler: &Compiler<targets::None>) -> Result<usize, SpirvCrossError> {
+    let resources = compiler.shader_resources()?.all_resources()?;
+
+    let mut count = 0;
+    for resource in resources.storage_buffers {
+        count += walk(compiler, resource.base_type_id)?;
+    }
+
+    Ok(count)
+}
+
+fn bench(words: &[u32], warm: bool) -> Result<Duration, SpirvCrossError> {
+    let mut total = 0;
+    let mut elapsed = Duration::ZERO;
+    for _ in 0..ITERATIONS {
+        let compiler = Compiler::<targets::None>::new(Module::from_words(words))?;
+        if warm {
+            total += reflect(&compiler)?;
+        }
+
+        let start = Instant::now();
+        total += reflect(&compiler)?;
+        elapsed += start.elapsed();
+    }
+
+    // Keep the results observable so the work is not optimized out.
+    assert_ne!(0, total);
+    Ok(elapsed / ITERATIONS)
+}
+
+fn main() -> Result<(), SpirvCrossError> {
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let source = shader();
+    let src = ShaderSource::from(source.as_str());
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Compute, &opts, None, None).unwrap();
+    let words = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    println!("Fresh compiler:  {:?} per module", bench(&words, false)?);
+    println!("Warmed compiler: {:?} per module", bench(&words, true)?);
+
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use spirv_cross_sys as sys;
-use spirv_cross_sys::spvc_context_s;
+use spirv_cross_sys::{spvc_context_s, spvc_parsed_ir_s};
 use std::ptr::NonNull;
 use std::sync::Arc;
 
@@ -36,6 +36,17 @@ impl<T> Clone for AllocationDropGuard<T> {
     }
 }
 
+/// A parsed SPIR-V module, as it was before being used to create a compiler.
+///
+/// Compilers are created from a copy of the parsed module, so it is never modified
+/// after parsing. The drop guard keeps the context that owns the parsed module alive,
+/// so it can be shared with compilers in other contexts.
+#[derive(Clone)]
+pub(crate) struct ParsedIr {
+    ir: NonNull<spvc_parsed_ir_s>,
+    _ctx: AllocationDropGuard,
+}
+
 impl CrossAllocationCell {
     /// Initialize a new SPIRV-Cross context.
     pub fn new() -> error::Result<Self> {
@@ -63,7 +74,7 @@ impl CrossAllocationCell {
     /// This allows for instances to be stored without keeping a reference to the
     /// context separately.
     pub(crate) fn into_compiler<T: Target>(self, spirv: Module) -> error::Result<Compiler<T>> {
-        unsafe {
+        let ir = unsafe {
             let mut ir = std::ptr::null_mut();
             sys::spvc_context_parse_spirv(
                 self.0 .0.as_ptr(),
@@ -73,12 +84,38 @@ impl CrossAllocationCell {
             )
             .ok(&self)?;
 
+            let Some(ir) = NonNull::new(ir) else {
+                return Err(SpirvCrossError::OutOfMemory(String::from("Out of memory")));
+            };
+
+            ParsedIr {
+                ir,
+                _ctx: self.drop_guard(),
+            }
+        };
+
+        self.into_compiler_from_ir(
+            ir,
+            Arc::from(bytemuck::must_cast_slice::<_, u32>(&*spirv.0)),
+        )
+    }
+
+    /// Create a compiler instance from a copy of an already parsed SPIR-V module,
+    /// which may belong to a different context.
+    pub(crate) fn into_compiler_from_ir<T: Target>(
+        self,
+        ir: ParsedIr,
+        words: Arc<[u32]>,
+    ) -> error::Result<Compiler<T>> {
+        unsafe {
             let mut compiler = std::ptr::null_mut();
+            // The parsed module is copied rather than moved, so it can be used to
+            // create further compilers with `Compiler::try_clone`.
             sys::spvc_context_create_compiler(
                 self.0 .0.as_ptr(),
                 T::BACKEND,
-                ir,
-                spirv_cross_sys::spvc_capture_mode::TakeOwnership,
+                ir.ir.as_ptr(),
+                spirv_cross_sys::spvc_capture_mode::Copy,
                 &mut compiler,
             )
             .ok(&self)?;
@@ -87,7 +124,7 @@ impl CrossAllocationCell {
                 return Err(SpirvCrossError::OutOfMemory(String::from("Out of memory")));
             };
 
-            Ok(Compiler::new_from_raw(compiler, self))
+            Ok(Compiler::new_from_raw(compiler, self, ir, words))
         }
     }
 
@@ -120,6 +157,10 @@ impl<T> ContextRooted for &Compiler<T> {
     fn context(&self) -> NonNull<spvc_context_s> {
         unsafe { self.ctx.as_ptr() }
     }
+
+    fn execution_model(&self) -> Option<spirv::ExecutionModel> {
+        Compiler::execution_model(self).ok()
+    }
 }
 
 impl ContextRooted for &PhantomCompiler {
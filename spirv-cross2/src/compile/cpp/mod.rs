@@ -0,0 +1,60 @@
+#![allow(deprecated)]
+
+use crate::error::ToContextError;
+use crate::reflect::validate_identifier;
+use crate::targets::Cpp;
+use crate::{error, Compiler, CompilerStr};
+use spirv_cross_sys as sys;
+
+/// C++ specific APIs.
+impl Compiler<Cpp> {
+    /// Set the name of the exported function that returns the shader interface.
+    ///
+    /// By default, the interface is exported as `spirv_cross_get_interface`. Setting a
+    /// unique name allows several shaders to be linked statically into the same binary.
+    ///
+    /// The name must be a legal C++ identifier, consisting of ASCII letters, digits and
+    /// underscores and not starting with a digit. Otherwise, returns
+    /// [`SpirvCrossError::InvalidArgument`](crate::SpirvCrossError::InvalidArgument).
+    pub fn set_interface_name<'str>(
+        &mut self,
+        name: impl Into<CompilerStr<'str>>,
+    ) -> error::Result<()> {
+        let name = name.into();
+        validate_identifier(&name)?;
+
+        unsafe {
+            let name = name.into_cstring_ptr()?;
+
+            sys::spvc_rs_compiler_cpp_set_interface_name(self.ptr_mut(), name.as_ptr()).ok(&*self)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::SpirvCrossError;
+    use crate::Compiler;
+    use crate::{targets, Module};
+
+    static BASIC_SPV: &[u8] = include_bytes!("../../../basic.spv");
+
+    #[test]
+    pub fn set_interface_name() -> Result<(), SpirvCrossError> {
+        let vec = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&vec));
+
+        let mut compiler: Compiler<targets::Cpp> = Compiler::new(words)?;
+        assert!(matches!(
+            compiler.set_interface_name("0interface"),
+            Err(SpirvCrossError::InvalidArgument(_))
+        ));
+
+        compiler.set_interface_name("basic_get_interface")?;
+        let artifact = compiler.compile(&Default::default())?;
+
+        assert!(artifact.as_ref().contains("basic_get_interface"));
+        assert!(!artifact.as_ref().contains("spirv_cross_get_interface"));
+        Ok(())
+    }
+}
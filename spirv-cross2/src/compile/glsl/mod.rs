@@ -1,13 +1,14 @@
-use super::CommonOptions;
-use crate::compile::sealed::ApplyCompilerOptions;
-use crate::error::ToContextError;
-use crate::handle::Handle;
+use super::{CommonOptions, CompiledArtifact};
+use crate::compile::sealed::{ApplyCompilerOptions, CompileHooks};
+use crate::error::{SpirvCrossError, ToContextError};
+use crate::handle::{Handle, TypeId};
 use crate::iter::impl_iterator;
+use crate::reflect::{DecorationValue, ResourceType, TypeInner};
 use crate::sealed::Sealed;
 use crate::targets::Glsl;
 use crate::{error, Compiler, CompilerStr, ContextRooted, PhantomCompiler};
 use spirv_cross_sys as sys;
-use spirv_cross_sys::{spvc_compiler_option, spvc_compiler_options, VariableId};
+use spirv_cross_sys::{spvc_compiler_option, spvc_compiler_options, BaseType, VariableId};
 use std::marker::PhantomData;
 use std::ops::Range;
 
@@ -183,6 +184,138 @@ impl ApplyCompilerOptions for GlslVersion {
     }
 }
 
+/// The prefix reserved for GLSL built-in identifiers.
+const RESERVED_PREFIX: &str = "gl_";
+
+/// The prefix that replaces [`RESERVED_PREFIX`] in sanitized names.
+const SANITIZED_PREFIX: &str = "_gl_";
+
+/// SPIR-V capabilities and the GLSL extensions they typically require.
+const CAPABILITY_EXTENSIONS: &[(spirv::Capability, &str)] = &[
+    (
+        spirv::Capability::Int8,
+        "GL_EXT_shader_explicit_arithmetic_types_int8",
+    ),
+    (
+        spirv::Capability::Int16,
+        "GL_EXT_shader_explicit_arithmetic_types_int16",
+    ),
+    (spirv::Capability::Int64, "GL_ARB_gpu_shader_int64"),
+    (
+        spirv::Capability::Int64Atomics,
+        "GL_EXT_shader_atomic_int64",
+    ),
+    (
+        spirv::Capability::Float16,
+        "GL_EXT_shader_explicit_arithmetic_types_float16",
+    ),
+    (spirv::Capability::Float64, "GL_ARB_gpu_shader_fp64"),
+    (spirv::Capability::ImageGatherExtended, "GL_ARB_gpu_shader5"),
+    (spirv::Capability::GeometryStreams, "GL_ARB_gpu_shader5"),
+    (
+        spirv::Capability::SampledCubeArray,
+        "GL_ARB_texture_cube_map_array",
+    ),
+    (
+        spirv::Capability::ImageCubeArray,
+        "GL_ARB_texture_cube_map_array",
+    ),
+    (spirv::Capability::ImageQuery, "GL_ARB_texture_query_levels"),
+    (
+        spirv::Capability::SampleRateShading,
+        "GL_ARB_sample_shading",
+    ),
+    (
+        spirv::Capability::AtomicStorage,
+        "GL_ARB_shader_atomic_counters",
+    ),
+    (
+        spirv::Capability::StorageImageReadWithoutFormat,
+        "GL_EXT_shader_image_load_formatted",
+    ),
+    (
+        spirv::Capability::DrawParameters,
+        "GL_ARB_shader_draw_parameters",
+    ),
+    (spirv::Capability::MultiView, "GL_EXT_multiview"),
+    (
+        spirv::Capability::ShaderViewportIndexLayerEXT,
+        "GL_ARB_shader_viewport_layer_array",
+    ),
+    (
+        spirv::Capability::ShaderNonUniform,
+        "GL_EXT_nonuniform_qualifier",
+    ),
+    (
+        spirv::Capability::RuntimeDescriptorArray,
+        "GL_EXT_nonuniform_qualifier",
+    ),
+    (
+        spirv::Capability::GroupNonUniform,
+        "GL_KHR_shader_subgroup_basic",
+    ),
+    (
+        spirv::Capability::GroupNonUniformVote,
+        "GL_KHR_shader_subgroup_vote",
+    ),
+    (
+        spirv::Capability::GroupNonUniformArithmetic,
+        "GL_KHR_shader_subgroup_arithmetic",
+    ),
+    (
+        spirv::Capability::GroupNonUniformBallot,
+        "GL_KHR_shader_subgroup_ballot",
+    ),
+    (
+        spirv::Capability::GroupNonUniformShuffle,
+        "GL_KHR_shader_subgroup_shuffle",
+    ),
+    (
+        spirv::Capability::GroupNonUniformShuffleRelative,
+        "GL_KHR_shader_subgroup_shuffle_relative",
+    ),
+    (
+        spirv::Capability::GroupNonUniformClustered,
+        "GL_KHR_shader_subgroup_clustered",
+    ),
+    (
+        spirv::Capability::GroupNonUniformQuad,
+        "GL_KHR_shader_subgroup_quad",
+    ),
+    (
+        spirv::Capability::FragmentShaderPixelInterlockEXT,
+        "GL_ARB_fragment_shader_interlock",
+    ),
+    (
+        spirv::Capability::FragmentShaderSampleInterlockEXT,
+        "GL_ARB_fragment_shader_interlock",
+    ),
+    (
+        spirv::Capability::DemoteToHelperInvocation,
+        "GL_EXT_demote_to_helper_invocation",
+    ),
+    (
+        spirv::Capability::FragmentBarycentricKHR,
+        "GL_EXT_fragment_shader_barycentric",
+    ),
+    (spirv::Capability::ShaderClockKHR, "GL_ARB_shader_clock"),
+    (
+        spirv::Capability::StencilExportEXT,
+        "GL_ARB_shader_stencil_export",
+    ),
+];
+
+/// The precision qualifier of a variable in ES targets, set with [`Compiler<Glsl>::set_precision`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum GlslPrecision {
+    /// `lowp`, which is emitted as `mediump` since SPIR-V can not express it.
+    Low,
+    /// `mediump`.
+    Medium,
+    /// `highp`.
+    High,
+}
+
 impl Compiler<Glsl> {
     /// Legacy GLSL compatibility method.
     ///
@@ -191,14 +324,54 @@ impl Compiler<Glsl> {
     /// mixing int and float is not.
     ///
     /// The name of the uniform array will be the same as the interface block name.
+    ///
+    /// The block is flattened when compiling, and can be restored with [`Compiler<Glsl>::unflatten_buffer_block`]
+    /// until then. If the block is already flattened, returns [`SpirvCrossError::InvalidOperation`].
     pub fn flatten_buffer_block(
         &mut self,
         block: impl Into<Handle<VariableId>>,
     ) -> error::Result<()> {
         let block = block.into();
-        let block = self.yield_id(block)?;
+        self.yield_id(block)?;
 
-        unsafe { sys::spvc_compiler_flatten_buffer_block(self.ptr.as_ptr(), block).ok(&*self) }
+        if self.state().flattened_blocks.contains(&block) {
+            return Err(SpirvCrossError::InvalidOperation(String::from(
+                "The buffer block is already flattened",
+            )));
+        }
+
+        self.state_mut().flattened_blocks.push(block);
+        Ok(())
+    }
+
+    /// Undo [`Compiler<Glsl>::flatten_buffer_block`], so that the block is compiled as a block.
+    ///
+    /// If the block is not flattened, returns [`SpirvCrossError::InvalidOperation`].
+    pub fn unflatten_buffer_block(
+        &mut self,
+        block: impl Into<Handle<VariableId>>,
+    ) -> error::Result<()> {
+        let block = block.into();
+        self.yield_id(block)?;
+
+        let Some(index) = self
+            .state()
+            .flattened_blocks
+            .iter()
+            .position(|b| *b == block)
+        else {
+            return Err(SpirvCrossError::InvalidOperation(String::from(
+                "The buffer block is not flattened",
+            )));
+        };
+
+        self.state_mut().flattened_blocks.remove(index);
+        Ok(())
+    }
+
+    /// Returns whether the block was flattened with [`Compiler<Glsl>::flatten_buffer_block`].
+    pub fn is_buffer_block_flattened(&self, block: impl Into<Handle<VariableId>>) -> bool {
+        self.state().flattened_blocks.contains(&block.into())
     }
 
     /// Returns the list of required extensions in a GLSL shader.
@@ -216,6 +389,314 @@ impl Compiler<Glsl> {
             GlslExtensionsIter(range, self.phantom(), PhantomData)
         }
     }
+
+    /// Rename user variables and struct members whose names begin with the reserved `gl_` prefix,
+    /// returning the number of renamed identifiers.
+    ///
+    /// Names from modules compiled from other languages may collide with the `gl_` prefix, which
+    /// is reserved in GLSL. SPIRV-Cross renames these itself when compiling, but this
+    /// renames them up front to `_gl_` instead, so that reflected names stay predictable.
+    ///
+    /// Variables and members decorated as built-ins are never renamed.
+    pub fn sanitize_glsl_names(&mut self) -> error::Result<usize> {
+        let mut variables = Vec::new();
+        let mut members = Vec::new();
+        let mut struct_types: Vec<Handle<TypeId>> = Vec::new();
+
+        let resources = self.shader_resources()?;
+        for resource_type in [
+            ResourceType::UniformBuffer,
+            ResourceType::StorageBuffer,
+            ResourceType::StageInput,
+            ResourceType::StageOutput,
+            ResourceType::SubpassInput,
+            ResourceType::StorageImage,
+            ResourceType::SampledImage,
+            ResourceType::AtomicCounter,
+            ResourceType::PushConstant,
+            ResourceType::SeparateImage,
+            ResourceType::SeparateSamplers,
+            ResourceType::AccelerationStructure,
+            ResourceType::ShaderRecordBuffer,
+            ResourceType::GlPlainUniform,
+        ] {
+            for resource in resources.resources_for_type(resource_type)? {
+                if self
+                    .decoration(resource.id, spirv::Decoration::BuiltIn)?
+                    .is_some()
+                {
+                    continue;
+                }
+
+                if let Some(name) = self.name(resource.id)? {
+                    if let Some(rest) = name.strip_prefix(RESERVED_PREFIX) {
+                        variables.push((resource.id, format!("{SANITIZED_PREFIX}{rest}")));
+                    }
+                }
+
+                if !struct_types.contains(&resource.base_type_id) {
+                    struct_types.push(resource.base_type_id);
+                }
+            }
+        }
+
+        for struct_type in struct_types {
+            let TypeInner::Struct(ty) = self.type_description(struct_type)?.inner else {
+                continue;
+            };
+
+            for member in &ty.members {
+                if self
+                    .member_decoration(member, spirv::Decoration::BuiltIn)?
+                    .is_some()
+                {
+                    continue;
+                }
+
+                if let Some(rest) = member
+                    .name
+                    .as_ref()
+                    .and_then(|name| name.strip_prefix(RESERVED_PREFIX))
+                {
+                    members.push((
+                        struct_type,
+                        member.index as u32,
+                        format!("{SANITIZED_PREFIX}{rest}"),
+                    ));
+                }
+            }
+        }
+
+        let renamed = variables.len() + members.len();
+        for (variable, name) in variables {
+            self.set_name(variable, name)?;
+        }
+
+        for (struct_type, index, name) in members {
+            self.set_member_name(struct_type, index, name)?;
+        }
+
+        Ok(renamed)
+    }
+
+    /// Assign a `Location` decoration to every stage input and output that lacks one,
+    /// returning the number of variables that were assigned a location.
+    ///
+    /// SPIRV-Cross only emits `layout(location = N)` for variables that are decorated
+    /// with a location, so this can be used to guarantee explicit locations on every
+    /// stage interface variable, for example when linking separate shader objects.
+    ///
+    /// Locations are assigned in declaration order, starting after the highest location
+    /// already in use by the inputs or outputs respectively. Arrays and matrices reserve
+    /// one location per element and column. Built-in variables are never assigned a location.
+    pub fn assign_missing_io_locations(&mut self) -> error::Result<usize> {
+        let resources = self.shader_resources()?;
+        let mut assignments = Vec::new();
+
+        for resource_type in [ResourceType::StageInput, ResourceType::StageOutput] {
+            let mut next_location = 0;
+            let mut missing = Vec::new();
+
+            for resource in resources.resources_for_type(resource_type)? {
+                if self
+                    .decoration(resource.id, spirv::Decoration::BuiltIn)?
+                    .is_some()
+                {
+                    continue;
+                }
+
+                let count = self.io_location_count(resource.type_id)?;
+                match self
+                    .decoration(resource.id, spirv::Decoration::Location)?
+                    .and_then(|l| l.as_literal())
+                {
+                    Some(location) => {
+                        next_location = next_location.max(location.saturating_add(count))
+                    }
+                    None => missing.push((resource.id, count)),
+                }
+            }
+
+            for (variable, count) in missing {
+                assignments.push((variable, next_location));
+                next_location = next_location.saturating_add(count);
+            }
+        }
+
+        let assigned = assignments.len();
+        for (variable, location) in assignments {
+            self.set_decoration(variable, spirv::Decoration::Location, Some(location))?;
+        }
+
+        Ok(assigned)
+    }
+
+    /// Suggest GLSL extensions to enable based on the capabilities declared by the module.
+    ///
+    /// This uses a fixed table mapping SPIR-V capabilities to the extensions they typically require
+    /// on older GLSL versions, for example `Int64` to `GL_ARB_gpu_shader_int64`.
+    /// SPIRV-Cross may already add some of these automatically. The suggestions can be enabled with
+    /// [`Compiler::require_extension`].
+    ///
+    /// Each extension is suggested at most once, in the order of the declared capabilities.
+    pub fn suggested_extensions(&self) -> error::Result<Vec<String>> {
+        let mut extensions = Vec::new();
+        for capability in self.declared_capabilities()? {
+            for (_, extension) in CAPABILITY_EXTENSIONS
+                .iter()
+                .filter(|(required, _)| required == capability)
+            {
+                if !extensions.iter().any(|e| e == extension) {
+                    extensions.push(extension.to_string());
+                }
+            }
+        }
+
+        Ok(extensions)
+    }
+
+    /// Predict the list of extensions that compiling with the given options will require,
+    /// without exposing the compiled source.
+    ///
+    /// This can be used to check extension availability against the driver before
+    /// committing to a compilation.
+    ///
+    /// This performs a full compilation internally on a throwaway compiler created with
+    /// [`Compiler::try_clone`], and is as expensive as calling [`Compiler::compile`].
+    /// The selected entry point, entry point renames, and extensions added with
+    /// [`Compiler::require_extension`] are carried over. Other mutations made to this instance,
+    /// such as decorations, names, or flattened buffer blocks, can not be copied and are not.
+    pub fn predict_required_extensions(
+        &self,
+        options: &CompilerOptions,
+    ) -> error::Result<Vec<String>> {
+        let mut compiler = self.try_clone()?;
+        self.copy_entry_point_to(&mut compiler)?;
+        for extension in self.required_extensions() {
+            compiler.require_extension(extension)?;
+        }
+
+        let artifact = compiler.compile(options)?;
+        let extensions = artifact
+            .required_extensions()
+            .map(|extension| extension.to_string())
+            .collect();
+        Ok(extensions)
+    }
+
+    /// Consume the compilation instance and compile to GLSL, failing if the output
+    /// requires any extension not in `allowed_extensions`.
+    ///
+    /// This checks every extension in [`Compiler<Glsl>::required_extensions`] after compilation,
+    /// including those added with [`Compiler::require_extension`] and those added
+    /// automatically by SPIRV-Cross. The first extension that is not allowed is returned as
+    /// [`SpirvCrossError::DisallowedExtension`].
+    pub fn compile_strict(
+        self,
+        options: &CompilerOptions,
+        allowed_extensions: &[&str],
+    ) -> error::Result<CompiledArtifact<Glsl>> {
+        let artifact = self.compile(options)?;
+        if let Some(extension) = artifact
+            .required_extensions()
+            .find(|extension| !allowed_extensions.contains(&extension.as_ref()))
+        {
+            return Err(SpirvCrossError::DisallowedExtension(extension.to_string()));
+        }
+
+        Ok(artifact)
+    }
+
+    /// Set the precision qualifier of a variable in ES targets.
+    ///
+    /// Precision is expressed with the `RelaxedPrecision` decoration, which `Medium` sets and `High` unsets.
+    /// SPIR-V has no equivalent of `lowp`, so `Low` is emitted as `mediump`, which satisfies the
+    /// same minimum requirements. The qualifier is omitted if it matches the default precision, see
+    /// [`CompilerOptions::es_default_float_precision_highp`] and [`CompilerOptions::es_default_int_precision_highp`].
+    ///
+    /// `highp` integers are not supported in GLSL ES 1.00, so compiling to [`GlslVersion::Glsl100Es`]
+    /// after setting `High` precision on an integer variable returns [`SpirvCrossError::InvalidOperation`].
+    pub fn set_precision(
+        &mut self,
+        variable: impl Into<Handle<VariableId>>,
+        precision: GlslPrecision,
+    ) -> error::Result<()> {
+        let variable = variable.into();
+        let type_id = self.variable_type(variable)?;
+        let type_id = self.yield_id(type_id)?;
+
+        let is_int = unsafe {
+            let ty = sys::spvc_compiler_get_type_handle(self.ptr.as_ptr(), type_id);
+            matches!(
+                sys::spvc_type_get_basetype(ty),
+                BaseType::Int8
+                    | BaseType::Uint8
+                    | BaseType::Int16
+                    | BaseType::Uint16
+                    | BaseType::Int32
+                    | BaseType::Uint32
+                    | BaseType::Int64
+                    | BaseType::Uint64
+            )
+        };
+
+        if precision == GlslPrecision::High {
+            self.set_decoration(
+                variable,
+                spirv::Decoration::RelaxedPrecision,
+                DecorationValue::unset(),
+            )?;
+        } else {
+            self.set_decoration(variable, spirv::Decoration::RelaxedPrecision, Some(()))?;
+        }
+
+        self.state_mut().highp_ints.retain(|id| *id != variable);
+        if is_int && precision == GlslPrecision::High {
+            self.state_mut().highp_ints.push(variable);
+        }
+
+        Ok(())
+    }
+}
+
+impl CompileHooks for Glsl {
+    fn pre_compile(compiler: &mut Compiler<Self>, options: &CompilerOptions) -> error::Result<()> {
+        // highp integers are only known to be unsupported once the version is known.
+        if options.version == GlslVersion::Glsl100Es && !compiler.state().highp_ints.is_empty() {
+            return Err(SpirvCrossError::InvalidOperation(String::from(
+                "highp integer precision is not supported in GLSL ES 1.00",
+            )));
+        }
+
+        // SPIRV-Cross can not unflatten a block, so blocks are only flattened once final.
+        for block in compiler.state().flattened_blocks.clone() {
+            let block = compiler.yield_id(block)?;
+            unsafe {
+                sys::spvc_compiler_flatten_buffer_block(compiler.ptr_mut(), block)
+                    .ok(&*compiler)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl CompiledArtifact<Glsl> {
+    /// Returns whether the compiled source requires the `GL_ARB_separate_shader_objects` extension.
+    ///
+    /// SPIRV-Cross requires the extension when [`CompilerOptions::seperate_shader_objects`] is enabled
+    /// for desktop GLSL versions older than 410. On newer versions and in ES, separate shader
+    /// objects are part of the core profile and this returns `false`.
+    pub fn requires_separate_shader_objects(&self) -> bool {
+        self.as_ref().lines().any(|line| {
+            line.trim_start()
+                .strip_prefix("#extension")
+                .and_then(|rest| rest.split(':').next())
+                .map_or(false, |name| {
+                    name.trim() == "GL_ARB_separate_shader_objects"
+                })
+        })
+    }
 }
 
 /// Iterator for required GLSL extensions, created by [`Compiler<Glsl>::required_extensions`].
@@ -246,7 +727,7 @@ impl_iterator!(GlslExtensionsIter<'c>: CompilerStr<'c> as and_then |s, index: us
 
 #[cfg(test)]
 mod test {
-    use crate::compile::glsl::CompilerOptions;
+    use crate::compile::glsl::{CompilerOptions, GlslVersion};
     use spirv_cross_sys::spvc_compiler_create_compiler_options;
 
     use crate::compile::CompilableTarget;
@@ -317,4 +798,91 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    pub fn requires_separate_shader_objects() -> Result<(), SpirvCrossError> {
+        for (version, expected) in [(GlslVersion::Glsl330, true), (GlslVersion::Glsl450, false)] {
+            let words = Vec::from(BASIC_SPV);
+            let words = Module::from_words(bytemuck::cast_slice(&words));
+            let compiler: Compiler<targets::Glsl> = Compiler::new(words)?;
+
+            let mut options = Glsl::options();
+            options.version = version;
+            options.seperate_shader_objects = true;
+
+            let artifact = compiler.compile(&options)?;
+            assert_eq!(expected, artifact.requires_separate_shader_objects());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn compile_strict() -> Result<(), SpirvCrossError> {
+        const EXTENSION: &str = "GL_ARB_separate_shader_objects";
+
+        let mut options = Glsl::options();
+        options.version = GlslVersion::Glsl330;
+        options.seperate_shader_objects = true;
+
+        for (allowed, expected) in [(&[][..], false), (&[EXTENSION][..], true)] {
+            let words = Vec::from(BASIC_SPV);
+            let words = Module::from_words(bytemuck::cast_slice(&words));
+            let compiler: Compiler<targets::Glsl> = Compiler::new(words)?;
+
+            match compiler.compile_strict(&options, allowed) {
+                Ok(_) => assert!(expected),
+                Err(SpirvCrossError::DisallowedExtension(extension)) => {
+                    assert!(!expected);
+                    assert_eq!(EXTENSION, extension);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn flatten_buffer_block() -> Result<(), SpirvCrossError> {
+        let words = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&words));
+
+        let mut compiler: Compiler<targets::Glsl> = Compiler::new(words)?;
+        let ubo = compiler
+            .shader_resources()?
+            .all_resources()?
+            .uniform_buffers[0]
+            .id;
+
+        compiler.flatten_buffer_block(ubo)?;
+        assert!(compiler.is_buffer_block_flattened(ubo));
+        assert!(matches!(
+            compiler.flatten_buffer_block(ubo),
+            Err(SpirvCrossError::InvalidOperation(_))
+        ));
+
+        compiler.unflatten_buffer_block(ubo)?;
+        assert!(!compiler.is_buffer_block_flattened(ubo));
+        assert!(matches!(
+            compiler.unflatten_buffer_block(ubo),
+            Err(SpirvCrossError::InvalidOperation(_))
+        ));
+
+        let mut flattened = compiler.try_clone()?;
+        let ubo = flattened
+            .shader_resources()?
+            .all_resources()?
+            .uniform_buffers[0]
+            .id;
+        flattened.flatten_buffer_block(ubo)?;
+
+        let source = compiler.compile(&Glsl::options())?.to_string();
+        assert!(source.contains("std140"));
+
+        let source = flattened.compile(&Glsl::options())?.to_string();
+        assert!(!source.contains("std140"));
+
+        Ok(())
+    }
 }
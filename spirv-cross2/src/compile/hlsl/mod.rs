@@ -1,3 +1,4 @@
+use crate::compile::sealed::CompileHooks;
 use crate::compile::{CommonOptions, CompiledArtifact};
 use crate::targets::Hlsl;
 use crate::{error, Compiler};
@@ -11,6 +12,7 @@ pub use spirv_cross_sys::HlslRootConstants as RootConstants;
 
 use crate::error::ToContextError;
 use crate::handle::{Handle, VariableId};
+use crate::reflect::{ResourceType, ShaderResources, DESCRIPTOR_RESOURCE_TYPES};
 use crate::sealed::Sealed;
 use crate::string::CompilerStr;
 use crate::ContextRooted;
@@ -178,6 +180,90 @@ impl From<HlslShaderModel> for u32 {
     }
 }
 
+/// SPIR-V capabilities and the minimum shader model needed to express them in HLSL.
+const CAPABILITY_SHADER_MODELS: &[(spirv::Capability, HlslShaderModel)] = &[
+    (spirv::Capability::Geometry, HlslShaderModel::ShaderModel4_0),
+    (
+        spirv::Capability::Tessellation,
+        HlslShaderModel::ShaderModel5_0,
+    ),
+    (
+        spirv::Capability::ShaderNonUniform,
+        HlslShaderModel::ShaderModel5_1,
+    ),
+    (
+        spirv::Capability::RuntimeDescriptorArray,
+        HlslShaderModel::ShaderModel5_1,
+    ),
+    (spirv::Capability::Int64, HlslShaderModel::ShaderModel6_0),
+    (
+        spirv::Capability::GroupNonUniform,
+        HlslShaderModel::ShaderModel6_0,
+    ),
+    (
+        spirv::Capability::GroupNonUniformVote,
+        HlslShaderModel::ShaderModel6_0,
+    ),
+    (
+        spirv::Capability::GroupNonUniformArithmetic,
+        HlslShaderModel::ShaderModel6_0,
+    ),
+    (
+        spirv::Capability::GroupNonUniformBallot,
+        HlslShaderModel::ShaderModel6_0,
+    ),
+    (
+        spirv::Capability::GroupNonUniformShuffle,
+        HlslShaderModel::ShaderModel6_0,
+    ),
+    (
+        spirv::Capability::GroupNonUniformQuad,
+        HlslShaderModel::ShaderModel6_0,
+    ),
+    (
+        spirv::Capability::SubgroupBallotKHR,
+        HlslShaderModel::ShaderModel6_0,
+    ),
+    (
+        spirv::Capability::SubgroupVoteKHR,
+        HlslShaderModel::ShaderModel6_0,
+    ),
+    (
+        spirv::Capability::FragmentBarycentricKHR,
+        HlslShaderModel::ShaderModel6_1,
+    ),
+    (
+        spirv::Capability::MultiView,
+        HlslShaderModel::ShaderModel6_1,
+    ),
+    (spirv::Capability::Int16, HlslShaderModel::ShaderModel6_2),
+    (spirv::Capability::Float16, HlslShaderModel::ShaderModel6_2),
+    (
+        spirv::Capability::RayTracingKHR,
+        HlslShaderModel::ShaderModel6_3,
+    ),
+    (
+        spirv::Capability::FragmentShadingRateKHR,
+        HlslShaderModel::ShaderModel6_4,
+    ),
+    (
+        spirv::Capability::RayQueryKHR,
+        HlslShaderModel::ShaderModel6_5,
+    ),
+    (
+        spirv::Capability::MeshShadingEXT,
+        HlslShaderModel::ShaderModel6_5,
+    ),
+    (
+        spirv::Capability::Int64Atomics,
+        HlslShaderModel::ShaderModel6_6,
+    ),
+    (
+        spirv::Capability::DrawParameters,
+        HlslShaderModel::ShaderModel6_8,
+    ),
+];
+
 /// Pipeline binding information for a resource.
 ///
 /// Used to map a SPIR-V resource to an HLSL buffer.
@@ -298,9 +384,103 @@ impl Compiler<Hlsl> {
         };
 
         unsafe {
-            sys::spvc_compiler_hlsl_add_resource_binding(self.ptr.as_ptr(), &hlsl_resource_binding)
-                .ok(&*self)
+            sys::spvc_compiler_hlsl_add_resource_binding(self.ptr_mut(), &hlsl_resource_binding)
+                .ok(&*self)?;
         }
+
+        let bindings = &mut self.state_mut().resource_bindings;
+        if !bindings.contains(&(stage, binding)) {
+            bindings.push((stage, binding));
+        }
+
+        Ok(())
+    }
+
+    /// Enable or disable `[[vk::binding(binding, set)]]` annotations on resource declarations
+    /// in the compiled HLSL, for consumption by DXC with Vulkan extensions.
+    ///
+    /// SPIRV-Cross does not emit these attributes. Instead, the compiled source is augmented
+    /// textually after compilation: every declaration with a `register(...)` whose name matches
+    /// a reflected resource is prefixed with the `DescriptorSet` and `Binding` decorations
+    /// of that resource. Combined image samplers annotate both the texture and sampler halves.
+    pub fn emit_vk_binding_annotations(&mut self, enabled: bool) {
+        self.state_mut().vk_binding_annotations = enabled;
+    }
+
+    /// Map every descriptor set to the register space of the same index.
+    ///
+    /// For each resource in `resources`, a resource binding is added for the current
+    /// entry point, with the register set to the `Binding` decoration and the space set to the
+    /// `DescriptorSet` decoration of the resource. That is, `layout(set = N, binding = M)`
+    /// will be bound to `register(xM, spaceN)`.
+    ///
+    /// Combined image samplers are bound to both the SRV and sampler registers.
+    ///
+    /// On SM 5.0 and lower, the register space is ignored.
+    pub fn map_sets_to_spaces(&mut self, resources: &ShaderResources) -> error::Result<()> {
+        let stage = self.execution_model()?;
+
+        for resource_type in DESCRIPTOR_RESOURCE_TYPES {
+            for resource in resources.resources_for_type(resource_type)? {
+                let set = self
+                    .decoration(resource.id, spirv::Decoration::DescriptorSet)?
+                    .and_then(|s| s.as_literal())
+                    .unwrap_or(0);
+                let binding = self
+                    .decoration(resource.id, spirv::Decoration::Binding)?
+                    .and_then(|b| b.as_literal())
+                    .unwrap_or(0);
+
+                let register = Some(RegisterBinding {
+                    register: binding,
+                    space: set,
+                });
+
+                let bind_target = match resource_type {
+                    ResourceType::UniformBuffer => BindTarget {
+                        cbv: register,
+                        uav: None,
+                        srv: None,
+                        sampler: None,
+                    },
+                    // Readonly storage buffers and images may be declared as SRVs.
+                    ResourceType::StorageBuffer
+                    | ResourceType::StorageImage
+                    | ResourceType::AtomicCounter => BindTarget {
+                        cbv: None,
+                        uav: register,
+                        srv: register,
+                        sampler: None,
+                    },
+                    ResourceType::SampledImage => BindTarget {
+                        cbv: None,
+                        uav: None,
+                        srv: register,
+                        sampler: register,
+                    },
+                    ResourceType::SeparateSamplers => BindTarget {
+                        cbv: None,
+                        uav: None,
+                        srv: None,
+                        sampler: register,
+                    },
+                    _ => BindTarget {
+                        cbv: None,
+                        uav: None,
+                        srv: register,
+                        sampler: None,
+                    },
+                };
+
+                self.add_resource_binding(
+                    stage,
+                    ResourceBinding::from_qualified(set, binding),
+                    &bind_target,
+                )?;
+            }
+        }
+
+        Ok(())
     }
 
     /// Compiles and remap vertex attribute at specific locations to a fixed semantic.
@@ -322,8 +502,39 @@ impl Compiler<Hlsl> {
         };
 
         unsafe {
-            sys::spvc_compiler_hlsl_add_vertex_attribute_remap(self.ptr.as_ptr(), &remap, 1)
-                .ok(&*self)
+            sys::spvc_compiler_hlsl_add_vertex_attribute_remap(self.ptr_mut(), &remap, 1).ok(&*self)
+        }
+    }
+
+    /// Remap vertex attributes at multiple locations to fixed semantics at once.
+    ///
+    /// This is equivalent to calling [`Compiler::remap_vertex_attribute`] for each
+    /// `(location, semantic)` pair, but adds all remaps with a single call into SPIRV-Cross.
+    pub fn remap_vertex_attributes<'str>(
+        &mut self,
+        remaps: impl IntoIterator<Item = (u32, impl Into<CompilerStr<'str>>)>,
+    ) -> error::Result<()> {
+        // The semantics must outlive the remaps that point to them.
+        let semantics = remaps
+            .into_iter()
+            .map(|(location, semantic)| Ok((location, semantic.into().into_cstring_ptr()?)))
+            .collect::<error::Result<Vec<_>>>()?;
+
+        let remaps: Vec<HlslVertexAttributeRemap> = semantics
+            .iter()
+            .map(|(location, semantic)| HlslVertexAttributeRemap {
+                location: *location,
+                semantic: semantic.as_ptr(),
+            })
+            .collect();
+
+        unsafe {
+            sys::spvc_compiler_hlsl_add_vertex_attribute_remap(
+                self.ptr_mut(),
+                remaps.as_ptr(),
+                remaps.len(),
+            )
+            .ok(&*self)
         }
     }
 
@@ -337,7 +548,7 @@ impl Compiler<Hlsl> {
     ) -> error::Result<()> {
         unsafe {
             sys::spvc_compiler_hlsl_set_root_constants_layout(
-                self.ptr.as_ptr(),
+                self.ptr_mut(),
                 constant_info.as_ptr(),
                 constant_info.len(),
             )
@@ -349,7 +560,7 @@ impl Compiler<Hlsl> {
     pub fn set_resource_binding_flags(&mut self, flags: BindingFlags) -> error::Result<()> {
         unsafe {
             sys::spvc_compiler_hlsl_set_resource_binding_flags(
-                self.ptr.as_ptr(),
+                self.ptr_mut(),
                 HlslBindingFlags(flags.bits()),
             )
             .ok(&*self)
@@ -382,11 +593,50 @@ impl Compiler<Hlsl> {
     /// [`Compiler::compile`].
     pub fn remap_num_workgroups_builtin(&mut self) -> Option<Handle<VariableId>> {
         unsafe {
-            let id = sys::spvc_compiler_hlsl_remap_num_workgroups_builtin(self.ptr.as_ptr());
+            let id = sys::spvc_compiler_hlsl_remap_num_workgroups_builtin(self.ptr_mut());
             self.create_handle_if_not_zero(id)
         }
     }
 
+    /// Returns whether the `NumWorkgroups` builtin is statically used by the current entry point,
+    /// and so a cbuffer must be provisioned with [`Compiler<Hlsl>::remap_num_workgroups_builtin`]
+    /// before compiling.
+    ///
+    /// This does not declare the cbuffer, but updates the set of active builtins with
+    /// [`Compiler::update_active_builtins`]. SPIRV-Cross only knows which builtins are active
+    /// after that call, which mutates the compiler, and the C API has no read-only query.
+    /// This is why it requires a mutable reference, like [`Compiler::uses_draw_parameters`].
+    pub fn needs_num_workgroups_cbuffer(&mut self) -> error::Result<bool> {
+        let proof = self.update_active_builtins();
+        self.has_active_builtin(
+            spirv::BuiltIn::NumWorkgroups,
+            spirv::StorageClass::Input,
+            proof,
+        )
+    }
+
+    /// Get the lowest shader model that can express the capabilities declared by the module.
+    ///
+    /// This is a conservative estimate derived from [`Compiler::declared_capabilities`],
+    /// for example wave operations and 64-bit integers require Shader Model 6.0, and
+    /// barycentrics require Shader Model 6.1. Capabilities that do not raise the
+    /// requirement are ignored, so the result is at least [`HlslShaderModel::ShaderModel3_0`].
+    pub fn minimum_shader_model(&self) -> error::Result<HlslShaderModel> {
+        let mut model = HlslShaderModel::ShaderModel3_0;
+        for capability in self.declared_capabilities()? {
+            for (_, required) in CAPABILITY_SHADER_MODELS
+                .iter()
+                .filter(|(declared, _)| declared == capability)
+            {
+                if u32::from(*required) > u32::from(model) {
+                    model = *required;
+                }
+            }
+        }
+
+        Ok(model)
+    }
+
     /// Mask a stage output by location.
     ///
     /// If a shader output is active in this stage, but inactive in a subsequent stage,
@@ -404,7 +654,7 @@ impl Compiler<Hlsl> {
         component: u32,
     ) -> crate::error::Result<()> {
         unsafe {
-            sys::spvc_compiler_mask_stage_output_by_location(self.ptr.as_ptr(), location, component)
+            sys::spvc_compiler_mask_stage_output_by_location(self.ptr_mut(), location, component)
                 .ok(&*self)
         }
     }
@@ -426,7 +676,7 @@ impl Compiler<Hlsl> {
     ) -> crate::error::Result<()> {
         unsafe {
             sys::spvc_compiler_mask_stage_output_by_builtin(
-                self.ptr.as_ptr(),
+                self.ptr_mut(),
                 SpvBuiltIn(builtin as u32 as i32),
             )
             .ok(&*self)
@@ -434,6 +684,84 @@ impl Compiler<Hlsl> {
     }
 }
 
+impl CompileHooks for Hlsl {
+    fn post_compile(
+        compiler: &Compiler<Self>,
+        source: CompilerStr<'static>,
+    ) -> error::Result<CompilerStr<'static>> {
+        if !compiler.state().vk_binding_annotations {
+            return Ok(source);
+        }
+
+        Ok(CompilerStr::from(compiler.annotate_vk_bindings(&source)?))
+    }
+}
+
+impl Compiler<Hlsl> {
+    /// Prefix resource declarations in the compiled HLSL `source` with `[[vk::binding]]` attributes.
+    fn annotate_vk_bindings(&self, source: &str) -> error::Result<String> {
+        let resources = self.shader_resources()?;
+        let mut bindings = Vec::new();
+        for resource_type in DESCRIPTOR_RESOURCE_TYPES {
+            for resource in resources.resources_for_type(resource_type)? {
+                let set = self
+                    .decoration(resource.id, spirv::Decoration::DescriptorSet)?
+                    .and_then(|s| s.as_literal())
+                    .unwrap_or(0);
+                let Some(binding) = self
+                    .decoration(resource.id, spirv::Decoration::Binding)?
+                    .and_then(|b| b.as_literal())
+                else {
+                    continue;
+                };
+
+                let mut names = vec![
+                    resource.name.to_string(),
+                    format!("_{}_sampler", resource.name),
+                ];
+                // Constant buffers may be declared by their block name.
+                if let Some(name) = self.type_description(resource.base_type_id)?.name {
+                    names.push(name.to_string());
+                }
+
+                bindings.push((names, binding, set));
+            }
+        }
+
+        let mut output = String::with_capacity(source.len());
+        for line in source.split_inclusive('\n') {
+            let Some(register) = line.find(" : register(") else {
+                output.push_str(line);
+                continue;
+            };
+
+            let mut declaration = line[..register].trim_end();
+            while let Some(array) = declaration.strip_suffix(']').and_then(|d| d.rfind('[')) {
+                declaration = declaration[..array].trim_end();
+            }
+            let name = declaration
+                .rsplit(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .next()
+                .unwrap_or_default();
+
+            let Some((_, binding, set)) = bindings
+                .iter()
+                .find(|(names, _, _)| names.iter().any(|n| n == name))
+            else {
+                output.push_str(line);
+                continue;
+            };
+
+            let indent = line.len() - line.trim_start().len();
+            output.push_str(&line[..indent]);
+            output.push_str(&format!("[[vk::binding({binding}, {set})]] "));
+            output.push_str(&line[indent..]);
+        }
+
+        Ok(output)
+    }
+}
+
 impl CompiledArtifact<Hlsl> {
     /// Returns whether the set/binding combination provided in [`Compiler<Hlsl>::add_resource_binding`]
     /// was used.
@@ -447,14 +775,28 @@ impl CompiledArtifact<Hlsl> {
             )
         }
     }
+
+    /// Get every binding provided in [`Compiler<Hlsl>::add_resource_binding`], in the order
+    /// they were added, along with whether the binding was used.
+    pub fn used_bindings(&self) -> Vec<(spirv::ExecutionModel, ResourceBinding, bool)> {
+        self.compiler
+            .state()
+            .resource_bindings
+            .iter()
+            .map(|&(model, binding)| (model, binding, self.is_resource_used(model, binding)))
+            .collect()
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::compile::hlsl::CompilerOptions;
+    use crate::compile::hlsl::{
+        BindTarget, CompilerOptions, HlslShaderModel, RegisterBinding, ResourceBinding,
+    };
     use spirv_cross_sys::spvc_compiler_create_compiler_options;
 
     use crate::compile::sealed::ApplyCompilerOptions;
+    use crate::compile::CompilableTarget;
     use crate::error::{SpirvCrossError, ToContextError};
     use crate::Compiler;
     use crate::{targets, Module};
@@ -491,4 +833,62 @@ mod test {
         // }
         Ok(())
     }
+
+    #[test]
+    pub fn used_bindings() -> Result<(), SpirvCrossError> {
+        let words = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&words));
+
+        let mut compiler: Compiler<targets::Hlsl> = Compiler::new(words)?;
+        let target = BindTarget {
+            cbv: None,
+            uav: None,
+            srv: Some(RegisterBinding {
+                register: 0,
+                space: 0,
+            }),
+            sampler: Some(RegisterBinding {
+                register: 0,
+                space: 0,
+            }),
+        };
+
+        let tex = ResourceBinding::from_qualified(0, 1);
+        let unused = ResourceBinding::from_qualified(3, 3);
+        compiler.add_resource_binding(spirv::ExecutionModel::Fragment, tex, &target)?;
+        compiler.add_resource_binding(spirv::ExecutionModel::Fragment, unused, &target)?;
+
+        let artifact = compiler.compile(&targets::Hlsl::options())?;
+        assert_eq!(
+            vec![
+                (spirv::ExecutionModel::Fragment, tex, true),
+                (spirv::ExecutionModel::Fragment, unused, false),
+            ],
+            artifact.used_bindings()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn emit_vk_binding_annotations() -> Result<(), SpirvCrossError> {
+        let words = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&words));
+
+        let mut compiler: Compiler<targets::Hlsl> = Compiler::new(words)?;
+        compiler.emit_vk_binding_annotations(true);
+
+        let mut options = targets::Hlsl::options();
+        options.shader_model = HlslShaderModel::ShaderModel5_0;
+
+        let artifact = compiler.compile(&options)?;
+        let source: &str = artifact.as_ref();
+
+        // tex is a combined image sampler at set 0, binding 1.
+        assert!(source.contains("[[vk::binding(1, 0)]] Texture2D<float4> tex"));
+        assert!(source.contains("[[vk::binding(1, 0)]] SamplerState _tex_sampler"));
+        assert!(source.contains("[[vk::binding(0, 0)]] "));
+
+        Ok(())
+    }
 }
@@ -0,0 +1,40 @@
+use crate::compile::CompiledArtifact;
+use crate::error;
+use crate::error::SpirvCrossError;
+use crate::targets::Json;
+
+/// JSON specific APIs.
+impl CompiledArtifact<Json> {
+    /// Parse the compiled reflection output into a [`serde_json::Value`].
+    ///
+    /// SPIRV-Cross writes identifiers into the output verbatim. If the module contains a
+    /// name with characters that must be escaped in JSON, such as `"` or `\`, the output is
+    /// not valid JSON and [`SpirvCrossError::InvalidOperation`] is returned.
+    pub fn parse(&self) -> error::Result<serde_json::Value> {
+        serde_json::from_str(self.as_ref()).map_err(|e| {
+            SpirvCrossError::InvalidOperation(format!("The compiled output is not valid JSON: {e}"))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::SpirvCrossError;
+    use crate::Compiler;
+    use crate::{targets, Module};
+
+    static BASIC_SPV: &[u8] = include_bytes!("../../../basic.spv");
+
+    #[test]
+    pub fn parse() -> Result<(), SpirvCrossError> {
+        let vec = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&vec));
+
+        let compiler: Compiler<targets::Json> = Compiler::new(words)?;
+        let artifact = compiler.compile(&Default::default())?;
+        let value = artifact.parse()?;
+
+        assert!(value["entryPoints"].is_array());
+        Ok(())
+    }
+}
@@ -1,8 +1,10 @@
+use crate::compile::sealed::ApplyCompilerOptions;
 use crate::error::{Result, ToContextError};
 use crate::sealed::Sealed;
 use crate::targets::Target;
 use crate::{error, Compiler, CompilerStr, ContextRooted};
 use spirv_cross_sys as sys;
+use spirv_cross_sys::{spvc_compiler_option, spvc_compiler_options};
 use std::fmt::{Display, Formatter};
 use std::ops::Deref;
 
@@ -21,7 +23,67 @@ pub mod hlsl;
 #[cfg_attr(docsrs, doc(cfg(feature = "msl")))]
 pub mod msl;
 
+/// JSON specific APIs.
+#[cfg(all(feature = "json", feature = "serde-json"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "json", feature = "serde-json"))))]
+pub mod json;
+
+/// C++ specific APIs.
+#[cfg(feature = "cpp")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cpp")))]
+pub mod cpp;
+
 impl Sealed for CommonOptions {}
+impl Sealed for FlipY {}
+
+/// When to invert gl_Position.y or equivalent.
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum FlipY {
+    /// Never invert gl_Position.y.
+    #[default]
+    Never,
+    /// Always invert gl_Position.y.
+    ///
+    /// SPIRV-Cross only emits the flip in vertex, tessellation evaluation
+    /// and geometry shaders.
+    Always,
+    /// Invert gl_Position.y only if the execution model of the current
+    /// entry point is vertex-like, that is vertex, tessellation evaluation
+    /// or geometry.
+    ///
+    /// This allows the same options to be used to compile every stage of a pipeline.
+    VertexLike,
+}
+
+impl ApplyCompilerOptions for FlipY {
+    unsafe fn apply(
+        &self,
+        options: spvc_compiler_options,
+        root: impl ContextRooted + Copy,
+    ) -> error::Result<()> {
+        let flip = match self {
+            FlipY::Never => false,
+            FlipY::Always => true,
+            FlipY::VertexLike => matches!(
+                root.execution_model(),
+                Some(
+                    spirv::ExecutionModel::Vertex
+                        | spirv::ExecutionModel::TessellationEvaluation
+                        | spirv::ExecutionModel::Geometry
+                )
+            ),
+        };
+
+        unsafe {
+            sys::spvc_compiler_options_set_bool(
+                options,
+                spvc_compiler_option::SPVC_COMPILER_OPTION_FLIP_VERTEX_Y,
+                flip,
+            )
+            .ok(root)
+        }
+    }
+}
 
 /// Compile options common to all backends.
 #[derive(Debug, spirv_cross2_derive::CompilerOptions)]
@@ -39,9 +101,11 @@ pub struct CommonOptions {
     #[option(SPVC_COMPILER_OPTION_FLATTEN_MULTIDIMENSIONAL_ARRAYS, false)]
     pub flatten_multidimensional_arrays: bool,
 
-    /// In vertex-like shaders, inverts gl_Position.y or equivalent.
-    #[option(SPVC_COMPILER_OPTION_FLIP_VERTEX_Y, false)]
-    pub flip_vertex_y: bool,
+    /// Whether to invert gl_Position.y or equivalent.
+    ///
+    /// See [`FlipY`] for which shader stages are affected.
+    #[expand]
+    pub flip_vertex_y: FlipY,
 
     /// GLSL: In vertex-like shaders, rewrite [0, w] depth (Vulkan/D3D style) to [-w, w] depth (GL style).
     /// MSL: In vertex-like shaders, rewrite [-w, w] depth (GL style) to [0, w] depth.
@@ -109,6 +173,93 @@ impl<'a, T> Deref for CompiledArtifact<T> {
     }
 }
 
+impl<T> CompiledArtifact<T> {
+    /// Get a map from lines in the compiled source to lines in the original source.
+    ///
+    /// Each entry is a pair of the 1-based output line, and the source line declared
+    /// by the `#line` directive immediately preceding it. This requires
+    /// [`CommonOptions::emit_line_directives`] to be enabled, and the module to contain
+    /// `OpLine` instructions.
+    ///
+    /// The map is to the lines recorded by `OpLine`, which are lines in the original
+    /// high-level source the module was compiled from. It is not a map to SPIR-V
+    /// word offsets, which are not preserved in the output.
+    ///
+    /// If the compiled source contains no `#line` directives, returns `None`.
+    pub fn source_map(&self) -> Option<Vec<(u32, u32)>> {
+        let map = parse_line_directives(self.source.as_ref());
+        if map.is_empty() {
+            None
+        } else {
+            Some(map)
+        }
+    }
+
+    /// Get the compiled source with `preamble` inserted before the `#version` directive,
+    /// such as a license or SPDX header.
+    ///
+    /// [`Compiler::add_header_line`] inserts lines after `#version`, which must otherwise
+    /// be the first line of GLSL source. Only comments and whitespace may precede `#version`,
+    /// so the preamble should consist of comments.
+    ///
+    /// If the source has no `#version` directive, such as for targets other than GLSL,
+    /// the preamble is inserted at the top. A new-line is added after the preamble if
+    /// it does not already end with one.
+    pub fn with_preamble(&self, preamble: &str) -> String {
+        let source: &str = self.source.as_ref();
+        let mut version = 0;
+        for line in source.split_inclusive('\n') {
+            if line.trim_start().starts_with("#version") {
+                break;
+            }
+            version += line.len();
+        }
+
+        if version == source.len() {
+            version = 0;
+        }
+
+        let mut output = String::with_capacity(source.len() + preamble.len() + 1);
+        output.push_str(&source[..version]);
+        output.push_str(preamble);
+        if !preamble.is_empty() && !preamble.ends_with('\n') {
+            output.push('\n');
+        }
+        output.push_str(&source[version..]);
+        output
+    }
+
+    /// Consume the artifact and return the compiled source as an owned `String`.
+    ///
+    /// Source owned by the SPIRV-Cross context is copied once, while source that is
+    /// already an owned Rust string, for example after post-processing, is moved out
+    /// without allocating.
+    pub fn into_string(self) -> String {
+        self.source.into_string()
+    }
+}
+
+/// Parse `#line N` and `#line N "file"` directives, returning the line
+/// following each directive paired with `N`.
+fn parse_line_directives(source: &str) -> Vec<(u32, u32)> {
+    let mut map = Vec::new();
+    for (index, line) in source.lines().enumerate() {
+        let Some(directive) = line.trim_start().strip_prefix("#line") else {
+            continue;
+        };
+
+        let Some(Ok(source_line)) = directive.split_whitespace().next().map(str::parse::<u32>)
+        else {
+            continue;
+        };
+
+        // index is 0-based, so the line following the directive is index + 2.
+        map.push((index as u32 + 2, source_line));
+    }
+
+    map
+}
+
 /// Cross-compilation related methods.
 impl<T: CompilableTarget> Compiler<T> {
     /// Adds a line in valid header position.
@@ -126,9 +277,7 @@ impl<T: CompilableTarget> Compiler<T> {
     pub fn add_header_line<'str>(&mut self, line: impl Into<CompilerStr<'str>>) -> Result<()> {
         let line = line.into();
         let cstring = line.into_cstring_ptr()?;
-        unsafe {
-            sys::spvc_compiler_add_header_line(self.ptr.as_ptr(), cstring.as_ptr()).ok(&*self)
-        }
+        unsafe { sys::spvc_compiler_add_header_line(self.ptr_mut(), cstring.as_ptr()).ok(&*self) }
     }
 
     /// Adds an extension which is required to run this shader, e.g.
@@ -138,23 +287,20 @@ impl<T: CompilableTarget> Compiler<T> {
         let cstring = ext.into_cstring_ptr()?;
 
         unsafe {
-            sys::spvc_compiler_require_extension(self.ptr.as_ptr(), cstring.as_ptr().cast())
-                .ok(&*self)
+            sys::spvc_compiler_require_extension(self.ptr_mut(), cstring.as_ptr().cast()).ok(&*self)
         }
     }
 
     /// Apply the set of compiler options to the compiler instance.
     fn set_compiler_options(&mut self, options: &T::Options) -> error::Result<()> {
-        use crate::compile::sealed::ApplyCompilerOptions;
         unsafe {
             let mut handle = std::ptr::null_mut();
 
-            sys::spvc_compiler_create_compiler_options(self.ptr.as_ptr(), &mut handle)
-                .ok(&*self)?;
+            sys::spvc_compiler_create_compiler_options(self.ptr_mut(), &mut handle).ok(&*self)?;
 
             options.apply(handle, &*self)?;
 
-            sys::spvc_compiler_install_compiler_options(self.ptr.as_ptr(), handle).ok(&*self)?;
+            sys::spvc_compiler_install_compiler_options(self.ptr_mut(), handle).ok(&*self)?;
 
             Ok(())
         }
@@ -162,31 +308,60 @@ impl<T: CompilableTarget> Compiler<T> {
 
     /// Consume the compilation instance, and compile source code to the
     /// output target.
+    ///
+    /// Resource bindings added with `Compiler<Msl>::add_resource_binding`, and buffer blocks
+    /// flattened with `Compiler<Glsl>::flatten_buffer_block` are applied to SPIRV-Cross here,
+    /// so errors for them are returned by this method.
     pub fn compile(mut self, options: &T::Options) -> error::Result<CompiledArtifact<T>> {
+        T::pre_compile(&mut self, options)?;
+
         self.set_compiler_options(options)?;
 
         unsafe {
             let mut src = std::ptr::null();
-            sys::spvc_compiler_compile(self.ptr.as_ptr(), &mut src).ok(&self)?;
+            // SPIRV-Cross renames reserved identifiers while compiling, so this must
+            // invalidate the names in cached type descriptions.
+            sys::spvc_compiler_compile(self.ptr_mut(), &mut src).ok(&self)?;
 
             // SAFETY: 'static is OK to return here
             // https://github.com/KhronosGroup/SPIRV-Cross/blob/6a1fb66eef1bdca14acf7d0a51a3f883499d79f0/spirv_cross_c.cpp#L1782
             let src = CompilerStr::from_ptr(src, self.ctx.drop_guard());
+            let src = T::post_compile(&self, src)?;
+
             Ok(CompiledArtifact {
                 compiler: self,
                 source: src,
             })
         }
     }
+
+    /// Set the current entry point, then consume the compilation instance
+    /// and compile source code to the output target.
+    ///
+    /// This is equivalent to calling [`Compiler::set_entry_point`] followed by [`Compiler::compile`].
+    /// If no entry point with the given name and execution model exists,
+    /// [`SpirvCrossError::InvalidArgument`](crate::SpirvCrossError::InvalidArgument) is returned.
+    pub fn compile_entry_point<'str>(
+        mut self,
+        name: impl Into<CompilerStr<'str>>,
+        model: spirv::ExecutionModel,
+        options: &T::Options,
+    ) -> error::Result<CompiledArtifact<T>> {
+        self.set_entry_point(name, model)?;
+        self.compile(options)
+    }
 }
 
 /// Marker trait for compiler options.
 pub trait CompilerOptions: Default + sealed::ApplyCompilerOptions {}
 
 pub(crate) mod sealed {
+    use crate::compile::CompilableTarget;
     use crate::error;
     use crate::error::ContextRooted;
     use crate::sealed::Sealed;
+    use crate::targets::Target;
+    use crate::{Compiler, CompilerStr};
     use spirv_cross_sys::spvc_compiler_options;
 
     pub trait ApplyCompilerOptions: Sealed {
@@ -197,6 +372,28 @@ pub(crate) mod sealed {
             root: impl ContextRooted + Copy,
         ) -> error::Result<()>;
     }
+
+    /// Target specific steps of [`Compiler::compile`].
+    pub trait CompileHooks: Target + Sized {
+        /// Validate and apply state kept on the compiler before compiling.
+        fn pre_compile(
+            _compiler: &mut Compiler<Self>,
+            _options: &<Self as CompilableTarget>::Options,
+        ) -> error::Result<()>
+        where
+            Self: CompilableTarget,
+        {
+            Ok(())
+        }
+
+        /// Transform the source compiled by SPIRV-Cross.
+        fn post_compile(
+            _compiler: &Compiler<Self>,
+            source: CompilerStr<'static>,
+        ) -> error::Result<CompilerStr<'static>> {
+            Ok(source)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -216,6 +413,148 @@ mod test {
         let compiler: Compiler<targets::None> = Compiler::new(words)?;
         Ok(())
     }
+
+    #[test]
+    pub fn parse_line_directives() {
+        let source = "#version 450\n#line 12 \"shader.frag\"\nvoid main()\n#line 13\n{\n#line\n}\n";
+        assert_eq!(vec![(3, 12), (5, 13)], super::parse_line_directives(source));
+        assert!(super::parse_line_directives("void main() {}").is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "glsl")]
+    pub fn compile_entry_point() -> Result<(), SpirvCrossError> {
+        use crate::compile::CompilableTarget;
+
+        let vec = Vec::from(BASIC_SPV);
+
+        let compiler: Compiler<targets::Glsl> =
+            Compiler::new(Module::from_words(bytemuck::cast_slice(&vec)))?;
+        let artifact = compiler.compile_entry_point(
+            "main",
+            spirv::ExecutionModel::Fragment,
+            &targets::Glsl::options(),
+        )?;
+        assert_eq!(spirv::ExecutionModel::Fragment, artifact.execution_model()?);
+        assert!(artifact.as_ref().contains("void main()"));
+
+        let compiler: Compiler<targets::Glsl> =
+            Compiler::new(Module::from_words(bytemuck::cast_slice(&vec)))?;
+        assert!(compiler
+            .compile_entry_point(
+                "main",
+                spirv::ExecutionModel::Vertex,
+                &targets::Glsl::options()
+            )
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "glsl")]
+    pub fn with_preamble() -> Result<(), SpirvCrossError> {
+        use crate::compile::glsl::GlslVersion;
+        use crate::compile::CompilableTarget;
+
+        let vec = Vec::from(BASIC_SPV);
+
+        let compiler: Compiler<targets::Glsl> =
+            Compiler::new(Module::from_words(bytemuck::cast_slice(&vec)))?;
+        let mut options = targets::Glsl::options();
+        options.version = GlslVersion::Glsl450;
+
+        let artifact = compiler.compile(&options)?;
+        let source = artifact.with_preamble("// SPDX-License-Identifier: MIT");
+
+        assert!(source.starts_with("// SPDX-License-Identifier: MIT\n#version 450\n"));
+        assert_eq!(artifact.as_ref().len() + 32, source.len());
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "glsl")]
+    pub fn source_map() -> Result<(), SpirvCrossError> {
+        use crate::compile::glsl::GlslVersion;
+        use crate::compile::CompilableTarget;
+
+        // A fragment shader whose only instruction is `OpLine %file 42 0`.
+        #[rustfmt::skip]
+        let words: [u32; 41] = [
+            0x07230203, 0x00010000, 0, 6, 0,
+            // OpCapability Shader
+            0x00020011, 1,
+            // OpMemoryModel Logical GLSL450
+            0x0003000E, 0, 1,
+            // OpEntryPoint Fragment %4 "main"
+            0x0005000F, 4, 4, 0x6E69616D, 0,
+            // OpExecutionMode %4 OriginUpperLeft
+            0x00030010, 4, 7,
+            // %1 = OpString "test.frag"
+            0x00050007, 1, 0x74736574, 0x6172662E, 0x00000067,
+            // %2 = OpTypeVoid
+            0x00020013, 2,
+            // %3 = OpTypeFunction %2
+            0x00030021, 3, 2,
+            // %4 = OpFunction %2 None %3
+            0x00050036, 2, 4, 0, 3,
+            // %5 = OpLabel
+            0x000200F8, 5,
+            // OpLine %1 42 0
+            0x00040008, 1, 42, 0,
+            // OpReturn
+            0x000100FD,
+            // OpFunctionEnd
+            0x00010038,
+        ];
+
+        let compiler: Compiler<targets::Glsl> = Compiler::new(Module::from_words(&words))?;
+        let mut options = targets::Glsl::options();
+        options.version = GlslVersion::Glsl450;
+
+        let artifact = compiler.compile(&options)?;
+        assert!(artifact.source_map().is_none());
+
+        let compiler: Compiler<targets::Glsl> = Compiler::new(Module::from_words(&words))?;
+        options.common.emit_line_directives = true;
+
+        let artifact = compiler.compile(&options)?;
+        let source: &str = artifact.as_ref();
+        let map = artifact
+            .source_map()
+            .expect("#line directives should be emitted");
+
+        let (output_line, source_line) = map[0];
+        assert_eq!(42, source_line);
+
+        // The mapped output line immediately follows the directive.
+        let directive = source.lines().nth(output_line as usize - 2).unwrap();
+        assert!(directive.starts_with("#line 42"));
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "glsl")]
+    pub fn into_string() -> Result<(), SpirvCrossError> {
+        use crate::compile::glsl::GlslVersion;
+        use crate::compile::CompilableTarget;
+
+        let vec = Vec::from(BASIC_SPV);
+
+        let compiler: Compiler<targets::Glsl> =
+            Compiler::new(Module::from_words(bytemuck::cast_slice(&vec)))?;
+        let mut options = targets::Glsl::options();
+        options.version = GlslVersion::Glsl450;
+
+        let artifact = compiler.compile(&options)?;
+        let expected = artifact.to_string();
+
+        assert_eq!(expected, artifact.into_string());
+
+        Ok(())
+    }
 }
 
 impl Sealed for NoOptions {}
@@ -227,7 +566,7 @@ impl Sealed for NoOptions {}
 pub struct NoOptions;
 
 /// Marker trait for a compiler target that can have compiler outputs.
-pub trait CompilableTarget: Target {
+pub trait CompilableTarget: Target + sealed::CompileHooks {
     /// The options that this target accepts.
     type Options: CompilerOptions;
 
@@ -1,3 +1,4 @@
+use crate::compile::sealed::CompileHooks;
 use crate::compile::{CommonOptions, CompiledArtifact};
 use spirv_cross_sys as sys;
 
@@ -43,13 +44,17 @@ pub use spirv_cross_sys::MslShaderVariableRate as ShaderVariableRate;
 /// Maximum number of argument buffers supported.
 pub const MAX_ARGUMENT_BUFFERS: u32 = 8;
 
-use crate::error::ToContextError;
+use crate::error::{SpirvCrossError, ToContextError};
 use crate::handle::{Handle, VariableId};
+use crate::reflect::{
+    validate_identifier, BitWidth, ResourceType, Scalar, ScalarKind, ShaderResources, TypeInner,
+};
 use crate::sealed::Sealed;
 use crate::string::CompilerStr;
 use crate::targets::Msl;
 use crate::{error, Compiler, ContextRooted};
 use spirv_cross_sys::{MslResourceBinding2, MslShaderInterfaceVar2, SpvBuiltIn, SpvExecutionModel};
+use std::collections::BTreeSet;
 use std::fmt::{Debug, Formatter};
 use std::num::NonZeroU32;
 
@@ -462,6 +467,122 @@ pub struct CompilerOptions {
     pub force_fragment_with_side_effects_execution: bool,
 }
 
+impl CompilerOptions {
+    /// Create a builder for MSL compiler options, starting from the defaults.
+    pub fn builder() -> MslOptionsBuilder {
+        MslOptionsBuilder::new()
+    }
+}
+
+/// A builder for MSL [`CompilerOptions`], created by [`CompilerOptions::builder`].
+///
+/// Every option has a chainable setter of the same name. Options that are not set keep
+/// their default value.
+///
+/// ```rust
+/// use spirv_cross2::compile::msl::{CompilerOptions, MetalPlatform};
+///
+/// let options = CompilerOptions::builder()
+///     .version((2, 1))
+///     .argument_buffers(true)
+///     .platform(MetalPlatform::iOS)
+///     .build();
+///
+/// assert!(options.argument_buffers);
+/// ```
+#[derive(Debug, Default)]
+pub struct MslOptionsBuilder(CompilerOptions);
+
+impl MslOptionsBuilder {
+    /// Create a builder starting from the default options.
+    pub fn new() -> Self {
+        Self(CompilerOptions::default())
+    }
+
+    /// Build the compiler options.
+    pub fn build(self) -> CompilerOptions {
+        self.0
+    }
+}
+
+macro_rules! impl_options_builder {
+    ($($name:ident: $ty:ty,)*) => {
+        impl MslOptionsBuilder {
+            $(
+                #[doc = concat!("Set [`CompilerOptions::", stringify!($name), "`].")]
+                pub fn $name(mut self, $name: impl Into<$ty>) -> Self {
+                    self.0.$name = $name.into();
+                    self
+                }
+            )*
+        }
+    };
+}
+
+impl_options_builder! {
+    common: CommonOptions,
+    version: MslVersion,
+    texel_buffer_texture_width: u32,
+    swizzle_buffer_index: u32,
+    indirect_params_buffer_index: u32,
+    shader_output_buffer_index: u32,
+    shader_patch_output_buffer_index: u32,
+    shader_tess_factor_output_buffer_index: u32,
+    buffer_size_buffer_index: u32,
+    view_mask_buffer_index: u32,
+    dynamic_offsets_buffer_index: u32,
+    shader_input_buffer_index: u32,
+    shader_index_buffer_index: u32,
+    shader_patch_input_buffer_index: u32,
+    shader_input_workgroup_index: u32,
+    enable_point_size_builtin: bool,
+    enable_frag_depth_builtin: bool,
+    enable_frag_stencil_ref_builtin: bool,
+    disable_rasterization: bool,
+    capture_output_to_buffer: bool,
+    swizzle_texture_samples: bool,
+    pad_fragment_output_components: bool,
+    tess_domain_origin_lower_left: bool,
+    platform: MetalPlatform,
+    argument_buffers: bool,
+    argument_buffers_tier: ArgumentBuffersTier,
+    texture_buffer_native: bool,
+    multiview: bool,
+    multiview_layered_rendering: bool,
+    device_index: u32,
+    view_index_from_device_index: bool,
+    dispatch_base: bool,
+    texture_1d_as_2d: bool,
+    enable_base_index_zero: bool,
+    framebuffer_fetch_subpass: bool,
+    invariant_fp_math: bool,
+    emulate_cubemap_array: bool,
+    enable_decoration_binding: bool,
+    force_active_argument_buffer_resources: bool,
+    force_native_arrays: bool,
+    enable_frag_output_mask: u32,
+    enable_clip_distance_user_varying: bool,
+    multi_patch_workgroup: bool,
+    vertex_for_tessellation: bool,
+    vertex_index_type: IndexType,
+    arrayed_subpass_input: bool,
+    r32ui_linear_texture_alignment: u32,
+    r32ui_alignment_constant_id: u32,
+    ios_use_simdgroup_functions: bool,
+    emulate_subgroups: bool,
+    fixed_subgroup_size: u32,
+    force_sample_rate_shading: bool,
+    ios_support_base_vertex_instance: bool,
+    raw_buffer_tese_input: bool,
+    manual_helper_invocation_updates: bool,
+    check_discarded_frag_stores: bool,
+    sample_dref_lod_array_as_grad: bool,
+    readwrite_texture_fences: bool,
+    replace_recursive_inputs: bool,
+    agx_manual_cube_grad_fixup: bool,
+    force_fragment_with_side_effects_execution: bool,
+}
+
 /// The version of Metal Shading Language to compile to.
 ///
 /// Defaults to MSL 1.2.
@@ -477,6 +598,10 @@ pub struct MslVersion {
 
 impl MslVersion {
     /// Create a new `MslVersion`.
+    ///
+    /// The version is not validated. MSL versions are encoded as `major * 10000 + minor * 100 + patch`,
+    /// so a minor or patch version above 99 will carry into the next component.
+    /// Use [`MslVersion::try_new`] to validate the version.
     pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
         Self {
             major,
@@ -484,6 +609,30 @@ impl MslVersion {
             patch,
         }
     }
+
+    /// Create a new `MslVersion`, checking that it can be encoded.
+    ///
+    /// Returns [`SpirvCrossError::InvalidArgument`] if the minor or patch version is above 99,
+    /// or if the encoded version does not fit in a `u32`.
+    pub fn try_new(major: u32, minor: u32, patch: u32) -> error::Result<Self> {
+        if minor > 99 || patch > 99 {
+            return Err(SpirvCrossError::InvalidArgument(format!(
+                "MSL version {major}.{minor}.{patch} has a minor or patch version above 99"
+            )));
+        }
+
+        if major
+            .checked_mul(10000)
+            .and_then(|major| major.checked_add(minor * 100 + patch))
+            .is_none()
+        {
+            return Err(SpirvCrossError::InvalidArgument(format!(
+                "MSL version {major}.{minor}.{patch} has a major version that is too large"
+            )));
+        }
+
+        Ok(Self::new(major, minor, patch))
+    }
 }
 
 impl Default for MslVersion {
@@ -577,7 +726,7 @@ impl From<IndexType> for u32 {
         match value {
             IndexType::None => 0,
             IndexType::Uint16 => 1,
-            IndexType::Uint32 => 1,
+            IndexType::Uint32 => 2,
         }
     }
 }
@@ -608,6 +757,42 @@ pub struct BufferRequirements {
     pub needs_input_threadgroup_buffer: bool,
 }
 
+/// The `[[buffer(N)]]` indices of auxiliary buffers, as set in [`CompilerOptions`].
+#[derive(Debug, Default, Copy, Clone)]
+pub(crate) struct AuxBufferIndices {
+    swizzle: u32,
+    indirect_params: u32,
+    output: u32,
+    patch_output: u32,
+    tess_factor_output: u32,
+    buffer_size: u32,
+}
+
+impl From<&CompilerOptions> for AuxBufferIndices {
+    fn from(options: &CompilerOptions) -> Self {
+        AuxBufferIndices {
+            swizzle: options.swizzle_buffer_index,
+            indirect_params: options.indirect_params_buffer_index,
+            output: options.shader_output_buffer_index,
+            patch_output: options.shader_patch_output_buffer_index,
+            tess_factor_output: options.shader_tess_factor_output_buffer_index,
+            buffer_size: options.buffer_size_buffer_index,
+        }
+    }
+}
+
+/// The number of MSL argument table slots used by a compiled shader,
+/// created by [`CompiledArtifact<Msl>::resource_counts`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct MslResourceCounts {
+    /// The highest `[[buffer(N)]]` index used, plus one.
+    pub buffers: u32,
+    /// The highest `[[texture(N)]]` index used, plus one.
+    pub textures: u32,
+    /// The highest `[[sampler(N)]]` index used, plus one.
+    pub samplers: u32,
+}
+
 /// Pipeline binding information for a resource.
 ///
 /// Used to map a SPIR-V resource to an MSL buffer.
@@ -701,6 +886,132 @@ pub struct BindTarget {
     pub count: Option<NonZeroU32>,
 }
 
+/// The first MSL buffer, texture, and sampler indices to assign
+/// when planning bindings with [`Compiler<Msl>::auto_plan_bindings`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct MslIndexBases {
+    /// The first `[[buffer(N)]]` index to assign.
+    pub buffer: u32,
+    /// The first `[[texture(N)]]` index to assign.
+    pub texture: u32,
+    /// The first `[[sampler(N)]]` index to assign.
+    pub sampler: u32,
+}
+
+/// An attribute of the `[[stage_in]]` struct of a vertex shader,
+/// created by [`CompiledArtifact<Msl>::stage_in_layout`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct StageInEntry {
+    /// The location of the shader input.
+    pub location: u32,
+    /// The `[[attribute(N)]]` index of the input in the vertex descriptor.
+    pub attribute_index: u32,
+    /// The format of the input, as provided to [`Compiler<Msl>::add_shader_input`].
+    ///
+    /// If no format was provided for the location, this is [`ShaderVariableFormat::Other`].
+    pub format: ShaderVariableFormat,
+}
+
+/// The Metal vertex format of a vertex shader input, mirroring `MTLVertexFormat`,
+/// created by [`CompiledArtifact<Msl>::vertex_formats`].
+///
+/// SPIR-V does not record whether an input is normalized, so only the
+/// non-normalized formats are represented. A float input may also be fed by a
+/// normalized integer format, such as `MTLVertexFormatUChar4Normalized`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MtlVertexFormat {
+    /// The input type has no matching vertex format, such as 64-bit or boolean types.
+    Invalid,
+    /// One unsigned 8-bit integer.
+    UChar,
+    /// Two unsigned 8-bit integers.
+    UChar2,
+    /// Three unsigned 8-bit integers.
+    UChar3,
+    /// Four unsigned 8-bit integers.
+    UChar4,
+    /// One signed 8-bit integer.
+    Char,
+    /// Two signed 8-bit integers.
+    Char2,
+    /// Three signed 8-bit integers.
+    Char3,
+    /// Four signed 8-bit integers.
+    Char4,
+    /// One unsigned 16-bit integer.
+    UShort,
+    /// Two unsigned 16-bit integers.
+    UShort2,
+    /// Three unsigned 16-bit integers.
+    UShort3,
+    /// Four unsigned 16-bit integers.
+    UShort4,
+    /// One signed 16-bit integer.
+    Short,
+    /// Two signed 16-bit integers.
+    Short2,
+    /// Three signed 16-bit integers.
+    Short3,
+    /// Four signed 16-bit integers.
+    Short4,
+    /// One 16-bit float.
+    Half,
+    /// Two 16-bit floats.
+    Half2,
+    /// Three 16-bit floats.
+    Half3,
+    /// Four 16-bit floats.
+    Half4,
+    /// One 32-bit float.
+    Float,
+    /// Two 32-bit floats.
+    Float2,
+    /// Three 32-bit floats.
+    Float3,
+    /// Four 32-bit floats.
+    Float4,
+    /// One signed 32-bit integer.
+    Int,
+    /// Two signed 32-bit integers.
+    Int2,
+    /// Three signed 32-bit integers.
+    Int3,
+    /// Four signed 32-bit integers.
+    Int4,
+    /// One unsigned 32-bit integer.
+    UInt,
+    /// Two unsigned 32-bit integers.
+    UInt2,
+    /// Three unsigned 32-bit integers.
+    UInt3,
+    /// Four unsigned 32-bit integers.
+    UInt4,
+}
+
+impl MtlVertexFormat {
+    /// Get the vertex format for a vector with the given scalar type and component count.
+    fn from_scalar(scalar: &Scalar, components: u32) -> Self {
+        use MtlVertexFormat::*;
+
+        let formats = match (scalar.kind, scalar.size) {
+            (ScalarKind::Uint, BitWidth::Byte) => [UChar, UChar2, UChar3, UChar4],
+            (ScalarKind::Int, BitWidth::Byte) => [Char, Char2, Char3, Char4],
+            (ScalarKind::Uint, BitWidth::HalfWord) => [UShort, UShort2, UShort3, UShort4],
+            (ScalarKind::Int, BitWidth::HalfWord) => [Short, Short2, Short3, Short4],
+            (ScalarKind::Float, BitWidth::HalfWord) => [Half, Half2, Half3, Half4],
+            (ScalarKind::Float, BitWidth::Word) => [Float, Float2, Float3, Float4],
+            (ScalarKind::Int, BitWidth::Word) => [Int, Int2, Int3, Int4],
+            (ScalarKind::Uint, BitWidth::Word) => [UInt, UInt2, UInt3, UInt4],
+            _ => return Invalid,
+        };
+
+        match components {
+            1..=4 => formats[components as usize - 1],
+            _ => Invalid,
+        }
+    }
+}
+
 /// Defines MSL characteristics of a shader interface variable.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ShaderInterfaceVariable {
@@ -742,6 +1053,101 @@ impl ShaderInterfaceVariable {
     }
 }
 
+/// SPIR-V capabilities, the MSL feature they are translated to,
+/// and the minimum MSL version that supports the feature.
+const CAPABILITY_MSL_VERSIONS: &[(spirv::Capability, &str, MslVersion)] = &[
+    (
+        spirv::Capability::Tessellation,
+        "tessellation",
+        MslVersion::new(1, 2, 0),
+    ),
+    (
+        spirv::Capability::RuntimeDescriptorArray,
+        "argument buffers",
+        MslVersion::new(2, 0, 0),
+    ),
+    (
+        spirv::Capability::ShaderNonUniform,
+        "argument buffers",
+        MslVersion::new(2, 0, 0),
+    ),
+    (
+        spirv::Capability::GroupNonUniform,
+        "SIMD-group functions",
+        MslVersion::new(2, 0, 0),
+    ),
+    (
+        spirv::Capability::GroupNonUniformVote,
+        "SIMD-group functions",
+        MslVersion::new(2, 0, 0),
+    ),
+    (
+        spirv::Capability::GroupNonUniformArithmetic,
+        "SIMD-group functions",
+        MslVersion::new(2, 0, 0),
+    ),
+    (
+        spirv::Capability::GroupNonUniformBallot,
+        "SIMD-group functions",
+        MslVersion::new(2, 0, 0),
+    ),
+    (
+        spirv::Capability::GroupNonUniformShuffle,
+        "SIMD-group functions",
+        MslVersion::new(2, 0, 0),
+    ),
+    (
+        spirv::Capability::GroupNonUniformShuffleRelative,
+        "SIMD-group functions",
+        MslVersion::new(2, 0, 0),
+    ),
+    (
+        spirv::Capability::GroupNonUniformQuad,
+        "SIMD-group functions",
+        MslVersion::new(2, 0, 0),
+    ),
+    (
+        spirv::Capability::MultiViewport,
+        "viewport arrays",
+        MslVersion::new(2, 0, 0),
+    ),
+    (
+        spirv::Capability::ShaderViewportIndexLayerEXT,
+        "viewport arrays",
+        MslVersion::new(2, 0, 0),
+    ),
+    (
+        spirv::Capability::StencilExportEXT,
+        "stencil export",
+        MslVersion::new(2, 1, 0),
+    ),
+    (
+        spirv::Capability::FragmentBarycentricKHR,
+        "barycentric coordinates",
+        MslVersion::new(2, 2, 0),
+    ),
+    (
+        spirv::Capability::DemoteToHelperInvocation,
+        "helper invocation demotion",
+        MslVersion::new(2, 3, 0),
+    ),
+    (
+        spirv::Capability::RayQueryKHR,
+        "ray queries",
+        MslVersion::new(2, 3, 0),
+    ),
+    (
+        spirv::Capability::Int64Atomics,
+        "64-bit atomics",
+        MslVersion::new(2, 4, 0),
+    ),
+    (
+        spirv::Capability::MeshShadingEXT,
+        "mesh shaders",
+        MslVersion::new(3, 0, 0),
+    ),
+];
+
 /// MSL specific APIs.
 impl Compiler<Msl> {
     /// Get whether the vertex shader requires rasterization to be disabled.
@@ -772,6 +1178,37 @@ impl Compiler<Msl> {
         }
     }
 
+    /// List the MSL features used by the shader that require a higher MSL version than `target`,
+    /// along with the minimum MSL version that supports each feature.
+    ///
+    /// This is derived from [`Compiler::declared_capabilities`] with a fixed table,
+    /// for example runtime descriptor arrays need argument buffers from MSL 2.0, and
+    /// subgroup operations need SIMD-group functions from MSL 2.0. Each feature is
+    /// reported at most once, in the order of the declared capabilities.
+    ///
+    /// Features that depend on compiler options rather than the shader, such as
+    /// [`CompilerOptions::argument_buffers`], are not reported.
+    pub fn features_requiring_higher_version(
+        &self,
+        target: MslVersion,
+    ) -> error::Result<Vec<(&'static str, MslVersion)>> {
+        let mut features: Vec<(&'static str, MslVersion)> = Vec::new();
+        for capability in self.declared_capabilities()? {
+            for &(_, feature, required) in CAPABILITY_MSL_VERSIONS
+                .iter()
+                .filter(|(declared, _, _)| declared == capability)
+            {
+                if u32::from(required) > u32::from(target)
+                    && !features.iter().any(|(name, _)| *name == feature)
+                {
+                    features.push((feature, required));
+                }
+            }
+        }
+
+        Ok(features)
+    }
+
     /// Add a shader interface variable description used to fix up shader input variables.
     ///
     /// If shader inputs are provided, [`CompiledArtifact::is_shader_input_used`] will return true after
@@ -784,10 +1221,14 @@ impl Compiler<Msl> {
         location: u32,
         variable: &ShaderInterfaceVariable,
     ) -> error::Result<()> {
+        let format = variable.format;
         let variable = variable.to_raw(location);
         unsafe {
-            sys::spvc_compiler_msl_add_shader_input_2(self.ptr.as_ptr(), &variable).ok(&*self)
+            sys::spvc_compiler_msl_add_shader_input_2(self.ptr_mut(), &variable).ok(&*self)?;
         }
+
+        self.state_mut().shader_inputs.push((location, format));
+        Ok(())
     }
 
     /// Add a shader interface variable description used to fix up shader output variables.
@@ -803,9 +1244,7 @@ impl Compiler<Msl> {
         variable: &ShaderInterfaceVariable,
     ) -> error::Result<()> {
         let variable = variable.to_raw(location);
-        unsafe {
-            sys::spvc_compiler_msl_add_shader_output_2(self.ptr.as_ptr(), &variable).ok(&*self)
-        }
+        unsafe { sys::spvc_compiler_msl_add_shader_output_2(self.ptr_mut(), &variable).ok(&*self) }
     }
 
     /// Add a resource binding to indicate the MSL buffer, texture or sampler index to use for a
@@ -814,33 +1253,304 @@ impl Compiler<Msl> {
     /// If resource bindings are provided,
     /// [`CompiledArtifact<Msl>::is_resource_used`] will return true after [`Compiler::compile`] if
     /// the set/binding combination was used by the MSL code.
+    ///
+    /// Bindings are applied when compiling. Adding a binding for a stage and resource binding that was
+    /// already added replaces its bind target. Any error SPIRV-Cross raises for a binding is returned
+    /// by [`Compiler::compile`], not by this method.
+    ///
+    /// MSL can not declare runtime-sized arrays of resources, so if the binding refers to
+    /// a runtime array, [`BindTarget::count`] must be set to the size of the array, otherwise
+    /// returns [`SpirvCrossError::InvalidArgument`].
     pub fn add_resource_binding(
         &mut self,
         stage: spirv::ExecutionModel,
         binding: ResourceBinding,
         bind_target: &BindTarget,
     ) -> error::Result<()> {
-        let binding = MslResourceBinding2 {
-            stage: SpvExecutionModel(stage as u32 as i32),
-            desc_set: binding.descriptor_set(),
-            binding: binding.binding(),
-            count: bind_target.count.map_or(0, NonZeroU32::get),
-            msl_buffer: bind_target.buffer,
-            msl_texture: bind_target.texture,
-            msl_sampler: bind_target.sampler,
+        if bind_target.count.is_none() && self.binding_is_runtime_array(binding)? {
+            return Err(SpirvCrossError::InvalidArgument(format!(
+                "{binding:?} is a runtime array, but MSL requires an explicit array size. \
+                 Set BindTarget::count to the number of resources in the array"
+            )));
+        }
+
+        let bindings = &mut self.state_mut().resource_bindings;
+        match bindings
+            .iter_mut()
+            .find(|(configured_stage, configured, _)| {
+                *configured_stage == stage && *configured == binding
+            }) {
+            Some((_, _, target)) => *target = bind_target.clone(),
+            None => bindings.push((stage, binding, bind_target.clone())),
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether any resource bound at a qualified binding is a runtime array.
+    fn binding_is_runtime_array(&mut self, binding: ResourceBinding) -> error::Result<bool> {
+        let ResourceBinding::Qualified { set, binding } = binding else {
+            return Ok(false);
         };
-        unsafe {
-            sys::spvc_compiler_msl_add_resource_binding_2(self.ptr.as_ptr(), &binding).ok(&*self)
+
+        // Whether a resource is a runtime array does not change, but its set and binding
+        // decorations can, so only the variables are found once.
+        if self.state().runtime_arrays.is_none() {
+            let mut runtime_arrays = Vec::new();
+            for (_, resource) in self.shader_resources()?.iter_all() {
+                if self.descriptor_count(resource.type_id)? == 0 {
+                    runtime_arrays.push(resource.id);
+                }
+            }
+            self.state_mut().runtime_arrays = Some(runtime_arrays);
+        }
+
+        for &variable in self.state().runtime_arrays.iter().flatten() {
+            let resource_set = self
+                .decoration(variable, spirv::Decoration::DescriptorSet)?
+                .and_then(|s| s.as_literal());
+            let resource_binding = self
+                .decoration(variable, spirv::Decoration::Binding)?
+                .and_then(|b| b.as_literal());
+
+            if resource_set == Some(set) && resource_binding == Some(binding) {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Get every resource binding added with [`Compiler<Msl>::add_resource_binding`],
+    /// in the order they were added.
+    pub fn configured_resource_bindings(
+        &self,
+    ) -> Vec<(spirv::ExecutionModel, ResourceBinding, BindTarget)> {
+        self.state().resource_bindings.clone()
+    }
+
+    /// Remove every resource binding added with [`Compiler<Msl>::add_resource_binding`].
+    ///
+    /// Since bindings are only applied when compiling, removed bindings have no effect on the output.
+    pub fn clear_resource_bindings(&mut self) {
+        self.state_mut().resource_bindings.clear();
+    }
+
+    /// Plan "classic" MSL 1.0 bindings for all resources in `resources`.
+    ///
+    /// Resources are ordered by descriptor set and binding, and are assigned sequential
+    /// `[[buffer(N)]]`, `[[texture(N)]]` and `[[sampler(N)]]` indices per kind, starting from the
+    /// indices in `start`. Combined image samplers consume both a texture and a sampler index,
+    /// and arrays of resources consume as many indices as there are elements.
+    ///
+    /// Separate images and samplers that share a descriptor set and binding are planned
+    /// as a single entry, with both a texture and a sampler index.
+    ///
+    /// The plan is not applied to the compiler. Each entry can be inspected or adjusted,
+    /// then passed to [`Compiler<Msl>::add_resource_binding`]. Runtime arrays are planned
+    /// with a [`BindTarget::count`] of one, and consume a single index.
+    pub fn auto_plan_bindings(
+        &self,
+        resources: &ShaderResources,
+        start: MslIndexBases,
+    ) -> error::Result<Vec<(ResourceBinding, BindTarget)>> {
+        let mut planned = Vec::new();
+        for resource_type in [
+            ResourceType::UniformBuffer,
+            ResourceType::StorageBuffer,
+            ResourceType::PushConstant,
+            ResourceType::AccelerationStructure,
+            ResourceType::SampledImage,
+            ResourceType::SeparateImage,
+            ResourceType::StorageImage,
+            ResourceType::SubpassInput,
+            ResourceType::SeparateSamplers,
+        ] {
+            for resource in resources.resources_for_type(resource_type)? {
+                let binding = if resource_type == ResourceType::PushConstant {
+                    ResourceBinding::PushConstantBuffer
+                } else {
+                    let set = self
+                        .decoration(resource.id, spirv::Decoration::DescriptorSet)?
+                        .and_then(|s| s.as_literal())
+                        .unwrap_or(0);
+                    let binding = self
+                        .decoration(resource.id, spirv::Decoration::Binding)?
+                        .and_then(|b| b.as_literal())
+                        .unwrap_or(0);
+                    ResourceBinding::from_qualified(set, binding)
+                };
+
+                let count = self.descriptor_count(resource.type_id)?;
+                planned.push((binding, resource_type, count));
+            }
+        }
+
+        // Push constants have the maximum descriptor set, so they sort last.
+        planned.sort_by_key(|(binding, _, _)| (binding.descriptor_set(), binding.binding()));
+
+        let mut next = start;
+        let mut plan: Vec<(ResourceBinding, BindTarget)> = Vec::with_capacity(planned.len());
+        for (binding, resource_type, count) in planned {
+            // Runtime arrays still consume at least one index.
+            let slots = count.max(1);
+            let count = if count == 1 {
+                None
+            } else {
+                NonZeroU32::new(slots)
+            };
+
+            // Resources sharing a binding, such as a separate image and sampler,
+            // are merged into the same target.
+            if plan.last().map_or(true, |(last, _)| *last != binding) {
+                plan.push((
+                    binding,
+                    BindTarget {
+                        buffer: 0,
+                        texture: 0,
+                        sampler: 0,
+                        count,
+                    },
+                ));
+            }
+
+            let (_, bind_target) = plan.last_mut().expect("plan is not empty");
+            bind_target.count = bind_target.count.max(count);
+
+            match resource_type {
+                ResourceType::UniformBuffer
+                | ResourceType::StorageBuffer
+                | ResourceType::PushConstant
+                | ResourceType::AccelerationStructure => {
+                    bind_target.buffer = next.buffer;
+                    next.buffer += slots;
+                }
+                ResourceType::SampledImage => {
+                    bind_target.texture = next.texture;
+                    bind_target.sampler = next.sampler;
+                    next.texture += slots;
+                    next.sampler += slots;
+                }
+                ResourceType::SeparateSamplers => {
+                    bind_target.sampler = next.sampler;
+                    next.sampler += slots;
+                }
+                _ => {
+                    bind_target.texture = next.texture;
+                    next.texture += slots;
+                }
+            }
+        }
+
+        Ok(plan)
+    }
+
+    /// Returns whether argument buffers are recommended, because the number of buffers
+    /// used by `resources` exceeds `threshold`.
+    ///
+    /// Metal limits the number of buffers bound to the argument table of a stage to 31,
+    /// including auxiliary buffers. Arrays of buffers count as one buffer per element.
+    ///
+    /// To enable argument buffers in the compiler options, use
+    /// [`Compiler<Msl>::apply_auto_argument_buffers`].
+    pub fn auto_enable_argument_buffers(
+        &self,
+        resources: &ShaderResources,
+        threshold: u32,
+    ) -> error::Result<bool> {
+        let counts = self.argument_table_usage(resources)?;
+        Ok(counts.buffers > threshold)
+    }
+
+    /// Enable argument buffers in `options` if recommended by [`Compiler<Msl>::auto_enable_argument_buffers`],
+    /// returning whether they were enabled.
+    ///
+    /// If argument buffers are enabled, the MSL version is raised to at least 2.0 as required,
+    /// and the argument buffer tier is raised to [`ArgumentBuffersTier::Tier2`] if the resources
+    /// exceed the Tier 1 limits of 64 buffers, 128 textures, or 16 samplers.
+    ///
+    /// If argument buffers are not recommended, `options` is left unchanged.
+    pub fn apply_auto_argument_buffers(
+        &self,
+        resources: &ShaderResources,
+        threshold: u32,
+        options: &mut CompilerOptions,
+    ) -> error::Result<bool> {
+        const TIER1_LIMITS: MslResourceCounts = MslResourceCounts {
+            buffers: 64,
+            textures: 128,
+            samplers: 16,
+        };
+
+        let counts = self.argument_table_usage(resources)?;
+        if counts.buffers <= threshold {
+            return Ok(false);
+        }
+
+        options.argument_buffers = true;
+
+        let min_version = MslVersion::new(2, 0, 0);
+        if u32::from(options.version) < u32::from(min_version) {
+            options.version = min_version;
         }
+
+        if counts.buffers > TIER1_LIMITS.buffers
+            || counts.textures > TIER1_LIMITS.textures
+            || counts.samplers > TIER1_LIMITS.samplers
+        {
+            options.argument_buffers_tier = ArgumentBuffersTier::Tier2;
+        }
+
+        Ok(true)
+    }
+
+    /// Count the argument table slots needed to bind `resources` without argument buffers.
+    fn argument_table_usage(
+        &self,
+        resources: &ShaderResources,
+    ) -> error::Result<MslResourceCounts> {
+        let mut counts = MslResourceCounts::default();
+        for resource_type in [
+            ResourceType::UniformBuffer,
+            ResourceType::StorageBuffer,
+            ResourceType::PushConstant,
+            ResourceType::AccelerationStructure,
+            ResourceType::SampledImage,
+            ResourceType::SeparateImage,
+            ResourceType::StorageImage,
+            ResourceType::SubpassInput,
+            ResourceType::SeparateSamplers,
+        ] {
+            for resource in resources.resources_for_type(resource_type)? {
+                let slots = self.descriptor_count(resource.type_id)?.max(1);
+                match resource_type {
+                    ResourceType::UniformBuffer
+                    | ResourceType::StorageBuffer
+                    | ResourceType::PushConstant
+                    | ResourceType::AccelerationStructure => counts.buffers += slots,
+                    ResourceType::SampledImage => {
+                        counts.textures += slots;
+                        counts.samplers += slots;
+                    }
+                    ResourceType::SeparateSamplers => counts.samplers += slots,
+                    _ => counts.textures += slots,
+                }
+            }
+        }
+
+        Ok(counts)
     }
 
     /// When using MSL argument buffers, we can force "classic" MSL 1.0 binding schemes for certain descriptor sets.
     /// This corresponds to VK_KHR_push_descriptor in Vulkan.
     pub fn add_discrete_descriptor_set(&mut self, desc_set: u32) -> error::Result<()> {
         unsafe {
-            sys::spvc_compiler_msl_add_discrete_descriptor_set(self.ptr.as_ptr(), desc_set)
-                .ok(&*self)
+            sys::spvc_compiler_msl_add_discrete_descriptor_set(self.ptr_mut(), desc_set)
+                .ok(&*self)?;
         }
+
+        self.state_mut().discrete_descriptor_sets.push(desc_set);
+        Ok(())
     }
 
     /// This function marks a resource as using a dynamic offset
@@ -861,7 +1571,7 @@ impl Compiler<Msl> {
         index: u32,
     ) -> error::Result<()> {
         unsafe {
-            sys::spvc_compiler_msl_add_dynamic_buffer(self.ptr.as_ptr(), desc_set, binding, index)
+            sys::spvc_compiler_msl_add_dynamic_buffer(self.ptr_mut(), desc_set, binding, index)
                 .ok(&*self)
         }
     }
@@ -877,7 +1587,7 @@ impl Compiler<Msl> {
     /// buffer, instead of being referenced indirectly via pointer.
     pub fn add_inline_uniform_block(&mut self, desc_set: u32, binding: u32) -> error::Result<()> {
         unsafe {
-            sys::spvc_compiler_msl_add_inline_uniform_block(self.ptr.as_ptr(), desc_set, binding)
+            sys::spvc_compiler_msl_add_inline_uniform_block(self.ptr_mut(), desc_set, binding)
                 .ok(&*self)
         }
     }
@@ -891,7 +1601,7 @@ impl Compiler<Msl> {
     ) -> error::Result<()> {
         unsafe {
             sys::spvc_compiler_msl_set_argument_buffer_device_address_space(
-                self.ptr.as_ptr(),
+                self.ptr_mut(),
                 desc_set,
                 device_address,
             )
@@ -919,7 +1629,7 @@ impl Compiler<Msl> {
         if let Some(ycbcr) = ycbcr {
             unsafe {
                 sys::spvc_compiler_msl_remap_constexpr_sampler_ycbcr(
-                    self.ptr.as_ptr(),
+                    self.ptr_mut(),
                     id,
                     sampler,
                     ycbcr,
@@ -928,7 +1638,7 @@ impl Compiler<Msl> {
             }
         } else {
             unsafe {
-                sys::spvc_compiler_msl_remap_constexpr_sampler(self.ptr.as_ptr(), id, sampler)
+                sys::spvc_compiler_msl_remap_constexpr_sampler(self.ptr_mut(), id, sampler)
                     .ok(&*self)
             }
         }
@@ -953,7 +1663,7 @@ impl Compiler<Msl> {
         if let Some(ycbcr) = ycbcr {
             unsafe {
                 sys::spvc_compiler_msl_remap_constexpr_sampler_by_binding_ycbcr(
-                    self.ptr.as_ptr(),
+                    self.ptr_mut(),
                     desc_set,
                     binding,
                     sampler,
@@ -964,7 +1674,7 @@ impl Compiler<Msl> {
         } else {
             unsafe {
                 sys::spvc_compiler_msl_remap_constexpr_sampler_by_binding(
-                    self.ptr.as_ptr(),
+                    self.ptr_mut(),
                     desc_set,
                     binding,
                     sampler,
@@ -983,7 +1693,7 @@ impl Compiler<Msl> {
     ) -> error::Result<()> {
         unsafe {
             sys::spvc_compiler_msl_set_fragment_output_components(
-                self.ptr.as_ptr(),
+                self.ptr_mut(),
                 location,
                 components,
             )
@@ -992,21 +1702,27 @@ impl Compiler<Msl> {
     }
 
     /// Set the suffix for combined image samplers.
+    ///
+    /// The suffix must be a legal MSL identifier, consisting of ASCII letters, digits and
+    /// underscores and not starting with a digit. Otherwise, returns [`SpirvCrossError::InvalidArgument`].
     pub fn set_combined_sampler_suffix<'str>(
         &mut self,
         str: impl Into<CompilerStr<'str>>,
     ) -> error::Result<()> {
-        unsafe {
-            let str = str.into();
+        let str = str.into();
+        validate_identifier(&str)?;
 
+        unsafe {
             let suffix = str.into_cstring_ptr()?;
 
-            sys::spvc_compiler_msl_set_combined_sampler_suffix(self.ptr.as_ptr(), suffix.as_ptr())
+            sys::spvc_compiler_msl_set_combined_sampler_suffix(self.ptr_mut(), suffix.as_ptr())
                 .ok(&*self)
         }
     }
 
     /// Get the suffix for combined image samplers.
+    ///
+    /// The suffix is borrowed from the compiler, use [`ToString::to_string`] to keep an owned copy.
     pub fn combined_sampler_suffix(&self) -> CompilerStr {
         unsafe {
             let suffix = sys::spvc_compiler_msl_get_combined_sampler_suffix(self.ptr.as_ptr());
@@ -1031,7 +1747,7 @@ impl Compiler<Msl> {
         component: u32,
     ) -> crate::error::Result<()> {
         unsafe {
-            sys::spvc_compiler_mask_stage_output_by_location(self.ptr.as_ptr(), location, component)
+            sys::spvc_compiler_mask_stage_output_by_location(self.ptr_mut(), location, component)
                 .ok(&*self)
         }
     }
@@ -1053,7 +1769,7 @@ impl Compiler<Msl> {
     ) -> crate::error::Result<()> {
         unsafe {
             sys::spvc_compiler_mask_stage_output_by_builtin(
-                self.ptr.as_ptr(),
+                self.ptr_mut(),
                 SpvBuiltIn(builtin as u32 as i32),
             )
             .ok(&*self)
@@ -1061,6 +1777,34 @@ impl Compiler<Msl> {
     }
 }
 
+impl CompileHooks for Msl {
+    fn pre_compile(compiler: &mut Compiler<Self>, options: &CompilerOptions) -> error::Result<()> {
+        let state = compiler.state_mut();
+        state.vertex_for_tessellation = options.vertex_for_tessellation;
+        state.argument_buffers = options.argument_buffers;
+        state.aux_buffer_indices = AuxBufferIndices::from(options);
+
+        // SPIRV-Cross can not remove resource bindings, so they are only added once final.
+        for (stage, binding, bind_target) in compiler.state().resource_bindings.clone() {
+            let binding = MslResourceBinding2 {
+                stage: SpvExecutionModel(stage as u32 as i32),
+                desc_set: binding.descriptor_set(),
+                binding: binding.binding(),
+                count: bind_target.count.map_or(0, NonZeroU32::get),
+                msl_buffer: bind_target.buffer,
+                msl_texture: bind_target.texture,
+                msl_sampler: bind_target.sampler,
+            };
+            unsafe {
+                sys::spvc_compiler_msl_add_resource_binding_2(compiler.ptr_mut(), &binding)
+                    .ok(&*compiler)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Copy, Clone, Debug, Default)]
 #[non_exhaustive]
 /// The tier of automatic resource binding.
@@ -1107,6 +1851,121 @@ impl CompiledArtifact<Msl> {
         }
     }
 
+    /// Get the attributes of the `[[stage_in]]` struct for a vertex shader,
+    /// as needed to build a Metal vertex descriptor.
+    ///
+    /// Each statically accessed, non-builtin stage input consumes one attribute per location,
+    /// with arrays and matrices consuming consecutive attributes. The attribute index is the
+    /// same as the location. Formats are taken from [`Compiler<Msl>::add_shader_input`].
+    ///
+    /// Entries are sorted by location. If the entry point is not a vertex shader,
+    /// this is empty.
+    pub fn stage_in_layout(&self) -> error::Result<Vec<StageInEntry>> {
+        if self.execution_model()? != spirv::ExecutionModel::Vertex {
+            return Ok(Vec::new());
+        }
+
+        let resources =
+            self.shader_resources_for_active_variables(self.active_interface_variables()?)?;
+
+        let mut entries = Vec::new();
+        for input in resources.resources_for_type(ResourceType::StageInput)? {
+            if self
+                .decoration(input.id, spirv::Decoration::BuiltIn)?
+                .is_some()
+            {
+                continue;
+            }
+
+            let Some(first) = self
+                .decoration(input.id, spirv::Decoration::Location)?
+                .and_then(|l| l.as_literal())
+            else {
+                continue;
+            };
+
+            let count = self.io_location_count(input.type_id)?;
+            for location in first..first.saturating_add(count) {
+                // Later inputs override earlier ones for the same location.
+                let format = self
+                    .compiler
+                    .state()
+                    .shader_inputs
+                    .iter()
+                    .rev()
+                    .find(|(configured, _)| *configured == location)
+                    .map_or(ShaderVariableFormat::Other, |(_, format)| *format);
+
+                entries.push(StageInEntry {
+                    location,
+                    attribute_index: location,
+                    format,
+                });
+            }
+        }
+
+        entries.sort_by_key(|entry| entry.location);
+        Ok(entries)
+    }
+
+    /// Get the Metal vertex format of every location consumed by the stage inputs of
+    /// a vertex shader, as needed to build a Metal vertex descriptor.
+    ///
+    /// Formats are derived from the scalar type and component count of each input.
+    /// Arrays consume one location per element, and matrices one location per column.
+    /// Normalization is not recorded in SPIR-V, so float inputs always map to float formats,
+    /// see [`MtlVertexFormat`].
+    ///
+    /// Entries are sorted by location. If the entry point is not a vertex shader,
+    /// this is empty.
+    pub fn vertex_formats(&self) -> error::Result<Vec<(u32, MtlVertexFormat)>> {
+        if self.execution_model()? != spirv::ExecutionModel::Vertex {
+            return Ok(Vec::new());
+        }
+
+        let resources =
+            self.shader_resources_for_active_variables(self.active_interface_variables()?)?;
+
+        let mut formats = Vec::new();
+        for input in resources.resources_for_type(ResourceType::StageInput)? {
+            if self
+                .decoration(input.id, spirv::Decoration::BuiltIn)?
+                .is_some()
+            {
+                continue;
+            }
+
+            let Some(first) = self
+                .decoration(input.id, spirv::Decoration::Location)?
+                .and_then(|l| l.as_literal())
+            else {
+                continue;
+            };
+
+            let mut ty = self.type_description(input.type_id)?.inner;
+            if let TypeInner::Array { base, .. } = ty {
+                ty = self.type_description(base)?.inner;
+            }
+
+            let format = match &ty {
+                TypeInner::Scalar(scalar) => MtlVertexFormat::from_scalar(scalar, 1),
+                TypeInner::Vector { width, scalar } => MtlVertexFormat::from_scalar(scalar, *width),
+                TypeInner::Matrix { rows, scalar, .. } => {
+                    MtlVertexFormat::from_scalar(scalar, *rows)
+                }
+                _ => MtlVertexFormat::Invalid,
+            };
+
+            let count = self.io_location_count(input.type_id)?;
+            for location in first..first.saturating_add(count) {
+                formats.push((location, format));
+            }
+        }
+
+        formats.sort_by_key(|(location, _)| *location);
+        Ok(formats)
+    }
+
     /// For a variable resource ID, report the automatically assigned resource index.
     ///
     /// If the descriptor set was part of an argument buffer, report the `[[id(N)]]`,
@@ -1143,6 +2002,223 @@ impl CompiledArtifact<Msl> {
         }
     }
 
+    /// Get the number of buffer, texture and sampler slots the shader uses.
+    ///
+    /// Each count is the highest index assigned to a resource of that kind, plus one,
+    /// including automatically assigned and explicitly bound resources, arrays of resources,
+    /// and auxiliary buffers such as the swizzle or buffer size buffers.
+    ///
+    /// Resources in a descriptor set emitted as an argument buffer are not counted, since they
+    /// are encoded in the argument buffer by their `[[id(N)]]` index. The argument buffer itself
+    /// is counted by its `[[buffer(N)]]` index.
+    ///
+    /// The input threadgroup buffer of tessellation control shaders, and the view mask buffer
+    /// and dynamic offsets buffer are not counted.
+    pub fn resource_counts(&self) -> error::Result<MslResourceCounts> {
+        let mut counts = MslResourceCounts::default();
+        let mut argument_buffers = BTreeSet::new();
+        let resources = self.shader_resources()?;
+
+        for resource_type in [
+            ResourceType::UniformBuffer,
+            ResourceType::StorageBuffer,
+            ResourceType::PushConstant,
+            ResourceType::AccelerationStructure,
+            ResourceType::SampledImage,
+            ResourceType::SeparateImage,
+            ResourceType::StorageImage,
+            ResourceType::SubpassInput,
+            ResourceType::SeparateSamplers,
+        ] {
+            for resource in resources.resources_for_type(resource_type)? {
+                if resource_type != ResourceType::PushConstant {
+                    let set = self
+                        .decoration(resource.id, spirv::Decoration::DescriptorSet)?
+                        .and_then(|set| set.as_literal())
+                        .unwrap_or(0);
+                    if self.is_argument_buffer(set) {
+                        // Argument buffers are only emitted for sets with resources in use.
+                        if self
+                            .automatic_resource_binding(
+                                resource.id,
+                                AutomaticResourceBindingTier::Primary,
+                            )?
+                            .is_some()
+                        {
+                            argument_buffers.insert(set);
+                        }
+                        continue;
+                    }
+                }
+
+                let Some(primary) = self.automatic_resource_binding(
+                    resource.id,
+                    AutomaticResourceBindingTier::Primary,
+                )?
+                else {
+                    continue;
+                };
+                let secondary = self.automatic_resource_binding(
+                    resource.id,
+                    AutomaticResourceBindingTier::Secondary,
+                )?;
+
+                let slots = self.descriptor_count(resource.type_id)?.max(1);
+                let end = |index: u32| index.saturating_add(slots);
+
+                match resource_type {
+                    ResourceType::UniformBuffer
+                    | ResourceType::StorageBuffer
+                    | ResourceType::PushConstant
+                    | ResourceType::AccelerationStructure => {
+                        counts.buffers = counts.buffers.max(end(primary));
+                    }
+                    ResourceType::SampledImage => {
+                        counts.textures = counts.textures.max(end(primary));
+                        if let Some(sampler) = secondary {
+                            counts.samplers = counts.samplers.max(end(sampler));
+                        }
+                    }
+                    ResourceType::SeparateSamplers => {
+                        counts.samplers = counts.samplers.max(end(primary));
+                    }
+                    _ => {
+                        counts.textures = counts.textures.max(end(primary));
+                        // Storage images with atomics use an auxiliary atomic buffer.
+                        if let Some(atomic_buffer) = secondary {
+                            counts.buffers = counts.buffers.max(end(atomic_buffer));
+                        }
+                    }
+                }
+            }
+        }
+
+        // Argument buffers are bound at their set index, unless explicitly bound.
+        let execution_model = self.execution_model()?;
+        let state = self.compiler.state();
+        for set in argument_buffers {
+            let index = state
+                .resource_bindings
+                .iter()
+                .find(|(stage, binding, _)| {
+                    *stage == execution_model && *binding == ResourceBinding::ArgumentBuffer(set)
+                })
+                .map_or(set, |(_, _, target)| target.buffer);
+            counts.buffers = counts.buffers.max(index.saturating_add(1));
+        }
+
+        // Auxiliary buffers are not reflected as resources.
+        let requirements = self.buffer_requirements();
+        let indices = state.aux_buffer_indices;
+        let tessellation_kernel = self.is_tessellation_kernel()?;
+        for (needed, index) in [
+            (requirements.needs_swizzle_buffer, indices.swizzle),
+            (requirements.needs_buffer_size_buffer, indices.buffer_size),
+            (requirements.needs_output_buffer, indices.output),
+            (requirements.needs_patch_output_buffer, indices.patch_output),
+            (tessellation_kernel, indices.indirect_params),
+            (
+                tessellation_kernel
+                    && execution_model == spirv::ExecutionModel::TessellationControl,
+                indices.tess_factor_output,
+            ),
+        ] {
+            if needed {
+                counts.buffers = counts.buffers.max(index.saturating_add(1));
+            }
+        }
+
+        Ok(counts)
+    }
+
+    /// Get an estimate of the encoded size in bytes of the argument buffer for the given
+    /// descriptor set, as needed to allocate the buffer backing an `MTLArgumentEncoder`.
+    ///
+    /// The size is computed from the `[[id(N)]]` indices of the reflected resources in the set,
+    /// and is a best-effort estimate rather than the layout chosen by the Metal compiler.
+    /// It assumes the [`ArgumentBuffersTier::Tier2`] layout, where every buffer
+    /// address, texture, sampler and acceleration structure handle is encoded as 8 bytes
+    /// at its `[[id(N)]]` index. Combined image samplers use one index for the texture
+    /// and one for the sampler, and arrays use one index per element.
+    /// With [`ArgumentBuffersTier::Tier1`], the encoding is chosen by the driver, and the
+    /// returned size is only an estimate; prefer `MTLArgumentEncoder::encodedLength` instead.
+    ///
+    /// Inline uniform blocks added with [`Compiler<Msl>::add_inline_uniform_block`] are
+    /// embedded by value, and are not accounted for.
+    ///
+    /// If the descriptor set was not emitted as an argument buffer, or has no resources
+    /// in use, returns `None`.
+    pub fn argument_buffer_size(&self, desc_set: u32) -> error::Result<Option<usize>> {
+        /// The encoded size of a GPU address or resource ID.
+        const HANDLE_SIZE: usize = 8;
+
+        if !self.is_argument_buffer(desc_set) {
+            return Ok(None);
+        }
+
+        let resources = self.shader_resources()?;
+        let mut end = None;
+        for resource_type in [
+            ResourceType::UniformBuffer,
+            ResourceType::StorageBuffer,
+            ResourceType::AccelerationStructure,
+            ResourceType::SampledImage,
+            ResourceType::SeparateImage,
+            ResourceType::StorageImage,
+            ResourceType::SubpassInput,
+            ResourceType::SeparateSamplers,
+        ] {
+            for resource in resources.resources_for_type(resource_type)? {
+                if self
+                    .decoration(resource.id, spirv::Decoration::DescriptorSet)?
+                    .and_then(|set| set.as_literal())
+                    != Some(desc_set)
+                {
+                    continue;
+                }
+
+                let slots = self.descriptor_count(resource.type_id)?.max(1);
+                for tier in [
+                    AutomaticResourceBindingTier::Primary,
+                    AutomaticResourceBindingTier::Secondary,
+                ] {
+                    if let Some(index) = self.automatic_resource_binding(resource.id, tier)? {
+                        end = end.max(Some(index.saturating_add(slots)));
+                    }
+                }
+            }
+        }
+
+        // Sets without any resources in use are not emitted as an argument buffer.
+        Ok(end.map(|end| end as usize * HANDLE_SIZE))
+    }
+
+    /// Returns whether the descriptor set is emitted as an argument buffer,
+    /// if it has any resources in use.
+    fn is_argument_buffer(&self, desc_set: u32) -> bool {
+        let state = self.compiler.state();
+        state.argument_buffers
+            && desc_set < MAX_ARGUMENT_BUFFERS
+            && !state.discrete_descriptor_sets.contains(&desc_set)
+    }
+
+    /// Returns whether the shader was emitted as a compute kernel that is part of
+    /// a tessellation pipeline, and must be dispatched as such.
+    ///
+    /// This is the case for tessellation control shaders, and vertex shaders compiled
+    /// with [`CompilerOptions::vertex_for_tessellation`], which write their outputs
+    /// to a buffer instead of being rasterized.
+    pub fn is_tessellation_kernel(&self) -> error::Result<bool> {
+        let kernel = match self.execution_model()? {
+            spirv::ExecutionModel::TessellationControl => true,
+            spirv::ExecutionModel::Vertex => self.compiler.state().vertex_for_tessellation,
+            _ => false,
+        };
+
+        // Tessellation kernels write their outputs to a buffer instead of being rasterized.
+        Ok(kernel && self.is_rasterization_disabled())
+    }
+
     /// Query if a variable ID was used as a depth resource.
     ///
     /// This is meaningful for MSL since descriptor types depend on this knowledge.
@@ -1167,7 +2243,11 @@ impl CompiledArtifact<Msl> {
 
 #[cfg(test)]
 mod test {
-    use crate::compile::msl::CompilerOptions;
+    use crate::compile::msl::{
+        ArgumentBuffersTier, BindTarget, CompilerOptions, IndexType, MslIndexBases,
+        MslResourceCounts, MslVersion, ResourceBinding,
+    };
+    use crate::compile::CompilableTarget;
     use spirv_cross_sys::spvc_compiler_create_compiler_options;
 
     use crate::compile::sealed::ApplyCompilerOptions;
@@ -1207,4 +2287,200 @@ mod test {
         // }
         Ok(())
     }
+
+    #[test]
+    pub fn auto_plan_bindings() -> Result<(), SpirvCrossError> {
+        let words = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&words));
+
+        let compiler: Compiler<targets::Msl> = Compiler::new(words)?;
+        let resources = compiler.shader_resources()?;
+
+        let plan = compiler.auto_plan_bindings(&resources, MslIndexBases::default())?;
+        assert_eq!(2, plan.len());
+
+        let (binding, ubo) = &plan[0];
+        assert_eq!(&ResourceBinding::from_qualified(0, 0), binding);
+        assert_eq!(0, ubo.buffer);
+
+        let (binding, tex) = &plan[1];
+        assert_eq!(&ResourceBinding::from_qualified(0, 1), binding);
+        assert_eq!((0, 0), (tex.texture, tex.sampler));
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn resource_counts() -> Result<(), SpirvCrossError> {
+        let words = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&words));
+
+        let compiler: Compiler<targets::Msl> = Compiler::new(words)?;
+        let artifact = compiler.compile(&targets::Msl::options())?;
+
+        let counts = artifact.resource_counts()?;
+        assert!(counts.buffers >= 1);
+        assert!(counts.textures >= 1);
+        assert!(counts.samplers >= 1);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn resource_counts_argument_buffers() -> Result<(), SpirvCrossError> {
+        let words = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&words));
+
+        let compiler: Compiler<targets::Msl> = Compiler::new(words)?;
+        let mut options = CompilerOptions::default();
+        options.version = MslVersion::new(2, 0, 0);
+        options.argument_buffers = true;
+
+        let artifact = compiler.compile(&options)?;
+
+        // Only the argument buffer for descriptor set 0 is bound to the argument table.
+        assert_eq!(
+            MslResourceCounts {
+                buffers: 1,
+                textures: 0,
+                samplers: 0,
+            },
+            artifact.resource_counts()?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn argument_buffer_size() -> Result<(), SpirvCrossError> {
+        let words = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&words));
+
+        let compiler: Compiler<targets::Msl> = Compiler::new(words)?;
+        let mut options = CompilerOptions::default();
+        options.version = MslVersion::new(2, 0, 0);
+        options.argument_buffers = true;
+        options.argument_buffers_tier = ArgumentBuffersTier::Tier2;
+
+        let artifact = compiler.compile(&options)?;
+
+        // One uniform buffer, and one combined image sampler using two handles.
+        assert_eq!(Some(24), artifact.argument_buffer_size(0)?);
+        assert_eq!(None, artifact.argument_buffer_size(1)?);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn auto_enable_argument_buffers() -> Result<(), SpirvCrossError> {
+        let words = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&words));
+
+        let compiler: Compiler<targets::Msl> = Compiler::new(words)?;
+        let resources = compiler.shader_resources()?;
+
+        assert!(!compiler.auto_enable_argument_buffers(&resources, 1)?);
+        assert!(compiler.auto_enable_argument_buffers(&resources, 0)?);
+
+        let mut options = CompilerOptions::default();
+        assert!(compiler.apply_auto_argument_buffers(&resources, 0, &mut options)?);
+        assert!(options.argument_buffers);
+        assert_eq!(MslVersion::new(2, 0, 0), options.version);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn msl_version_try_new() {
+        assert!(MslVersion::try_new(1, 0, 100).is_err());
+        assert!(MslVersion::try_new(1, 100, 0).is_err());
+        assert!(MslVersion::try_new(u32::MAX, 0, 0).is_err());
+
+        let version = MslVersion::try_new(2, 4, 1).unwrap();
+        assert_eq!(20401, u32::from(version));
+    }
+
+    #[test]
+    pub fn index_type_conversion() {
+        assert_eq!(0, u32::from(IndexType::None));
+        assert_eq!(1, u32::from(IndexType::Uint16));
+        assert_eq!(2, u32::from(IndexType::Uint32));
+    }
+
+    #[test]
+    pub fn options_builder() -> Result<(), SpirvCrossError> {
+        let words = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&words));
+
+        let options = CompilerOptions::builder()
+            .version((2, 1))
+            .argument_buffers(true)
+            .argument_buffers_tier(ArgumentBuffersTier::Tier2)
+            .build();
+
+        assert_eq!(MslVersion::new(2, 1, 0), options.version);
+        assert!(options.argument_buffers);
+        // Options that were not set keep their defaults.
+        assert_eq!(30, options.swizzle_buffer_index);
+
+        let compiler: Compiler<targets::Msl> = Compiler::new(words)?;
+        compiler.compile(&options)?;
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn combined_sampler_suffix() -> Result<(), SpirvCrossError> {
+        let words = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&words));
+
+        let mut compiler: Compiler<targets::Msl> = Compiler::new(words)?;
+        compiler.set_combined_sampler_suffix("_smp2")?;
+        assert_eq!("_smp2", compiler.combined_sampler_suffix().as_ref());
+
+        for suffix in ["", "2smp", "smp-2", "smp\0"] {
+            assert!(matches!(
+                compiler.set_combined_sampler_suffix(suffix),
+                Err(SpirvCrossError::InvalidArgument(_))
+            ));
+        }
+
+        assert_eq!("_smp2", compiler.combined_sampler_suffix().as_ref());
+        Ok(())
+    }
+
+    #[test]
+    pub fn configured_resource_bindings() -> Result<(), SpirvCrossError> {
+        let words = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&words));
+
+        let mut compiler: Compiler<targets::Msl> = Compiler::new(words)?;
+        let stage = compiler.execution_model()?;
+        let ubo = ResourceBinding::from_qualified(0, 0);
+        let tex = ResourceBinding::from_qualified(0, 1);
+        let target = |index| BindTarget {
+            buffer: index,
+            texture: index,
+            sampler: index,
+            count: None,
+        };
+
+        compiler.add_resource_binding(stage, ubo, &target(0))?;
+        compiler.add_resource_binding(stage, tex, &target(1))?;
+        compiler.add_resource_binding(stage, ubo, &target(2))?;
+        assert_eq!(
+            vec![(stage, ubo, target(2)), (stage, tex, target(1))],
+            compiler.configured_resource_bindings()
+        );
+
+        compiler.clear_resource_bindings();
+        assert!(compiler.configured_resource_bindings().is_empty());
+
+        compiler.add_resource_binding(stage, ubo, &target(0))?;
+        let artifact = compiler.compile(&CompilerOptions::default())?;
+        assert!(artifact.is_resource_used(stage, ubo));
+        assert!(!artifact.is_resource_used(stage, tex));
+
+        Ok(())
+    }
 }
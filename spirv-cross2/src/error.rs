@@ -23,9 +23,21 @@ pub enum SpirvCrossError {
     #[error("The argument is invalid: {0}.")]
     /// The argument is invalid.
     InvalidArgument(String),
-    #[error("The tag of the handle does not match the compiler instance: {0:?}")]
-    /// The handle provided originated from a different compiler instance.
+    #[error("The ID of the handle is out of range for the compiler instance: {0:?}")]
+    /// The ID of the handle is not less than the ID bound of the compiler instance.
+    ///
+    /// This can only happen with handles forged with `Compiler::create_handle`.
     InvalidHandle(Handle<Box<dyn Id>>),
+    #[error("The handle with ID {id} originated from a different compiler instance.")]
+    /// The handle provided originated from a different compiler instance.
+    ///
+    /// Handles are tagged with the compiler instance that created them, and can not be used
+    /// with any other compiler instance, even one created from the same SPIR-V module
+    /// or with [`Compiler::try_clone`](crate::Compiler::try_clone).
+    HandleFromWrongCompiler {
+        /// The ID of the handle.
+        id: u32,
+    },
     #[error("The operation is invalid: {0:?}")]
     /// The requested operation is invalid.
     InvalidOperation(String),
@@ -57,6 +69,11 @@ pub enum SpirvCrossError {
     #[error("An unexpected enum value was found.")]
     /// An unexpected enum value was found.
     InvalidEnum,
+    #[error("The extension is not allowed: {0}.")]
+    /// The compiled output requires an extension that is not allowed.
+    ///
+    /// This is returned by `Compiler<Glsl>::compile_strict`.
+    DisallowedExtension(String),
 }
 
 pub(crate) trait ToContextError {
@@ -1,5 +1,6 @@
 use crate::error::SpirvCrossError;
 use crate::{error, Compiler, PhantomCompiler};
+use spirv_cross_sys as sys;
 use spirv_cross_sys::spvc_compiler_s;
 use std::fmt::{Debug, Formatter};
 use std::ptr::NonNull;
@@ -49,6 +50,11 @@ impl<T> Debug for PointerOnlyForComparison<T> {
 ///
 /// The usage of `Handle<T>` ensures that item IDs can not be forged from
 /// a different compiler instance or from a `u32`.
+///
+/// With the `serde` feature, handles serialize as their `u32` ID, without the
+/// compiler instance they belong to. Deserialized handles do not belong to any
+/// compiler instance, so using them with a compiler will always return
+/// [`SpirvCrossError::HandleFromWrongCompiler`].
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct Handle<T> {
     id: T,
@@ -66,8 +72,36 @@ impl<T: Id> Handle<T> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<T: Id> serde::Serialize for Handle<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.id())
+    }
+}
+
+#[cfg(feature = "serde")]
+macro_rules! impl_deserialize_handle {
+    ($($id:ident),*) => {
+        $(
+            impl<'de> serde::Deserialize<'de> for Handle<$id> {
+                fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let id = <u32 as serde::Deserialize>::deserialize(deserializer)?;
+                    Ok(Handle {
+                        id: $id(spirv_cross_sys::SpvId(id)),
+                        // A dangling pointer is never the pointer of a live compiler instance.
+                        tag: PointerOnlyForComparison(NonNull::dangling()),
+                    })
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "serde")]
+impl_deserialize_handle!(TypeId, VariableId, ConstantId);
+
 /// Trait for SPIRV-Cross ID types.
-pub trait Id: Sealed + Debug + Send + Sync + 'static {
+pub trait Id: Sealed + Debug + Copy + Send + Sync + 'static {
     /// Return the `u32` part of the Id.
     fn id(&self) -> u32;
 }
@@ -145,13 +179,28 @@ impl<T> Compiler<T> {
     }
 
     /// Yield the value of the handle, if it originated from the same compiler instance,
-    /// otherwise return [`SpirvCrossError::InvalidHandle`].
+    /// otherwise return [`SpirvCrossError::HandleFromWrongCompiler`].
+    ///
+    /// If the ID of the handle is not less than [`Compiler::id_bound`], which is only possible
+    /// for handles forged with [`Compiler::create_handle`], returns [`SpirvCrossError::InvalidHandle`].
     pub fn yield_id<I: Id>(&self, handle: Handle<I>) -> error::Result<I> {
-        if self.handle_is_valid(&handle) {
-            Ok(handle.id)
-        } else {
-            Err(SpirvCrossError::InvalidHandle(handle.erase_type()))
+        if !self.handle_is_valid(&handle) {
+            return Err(SpirvCrossError::HandleFromWrongCompiler { id: handle.id.id() });
         }
+
+        if handle.id.id() >= self.id_bound() {
+            return Err(SpirvCrossError::InvalidHandle(handle.erase_type()));
+        }
+
+        Ok(handle.id)
+    }
+
+    /// Get the current ID bound of the module.
+    ///
+    /// Every ID in the module, including IDs allocated by the compiler, is less than the bound,
+    /// so the bound can be used to size tables indexed by [`Handle::id`].
+    pub fn id_bound(&self) -> u32 {
+        unsafe { sys::spvc_compiler_get_current_id_bound(self.ptr.as_ptr()) }
     }
 }
 
@@ -172,3 +221,65 @@ impl PhantomCompiler {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::error::SpirvCrossError;
+    use crate::handle::VariableId;
+    use crate::Compiler;
+    use crate::{targets, Module};
+    use spirv_cross_sys::SpvId;
+
+    static BASIC_SPV: &[u8] = include_bytes!("../basic.spv");
+
+    #[test]
+    pub fn handle_from_wrong_compiler() -> Result<(), SpirvCrossError> {
+        let vec = Vec::from(BASIC_SPV);
+
+        let compiler: Compiler<targets::None> =
+            Compiler::new(Module::from_words(bytemuck::cast_slice(&vec)))?;
+        let other: Compiler<targets::None> =
+            Compiler::new(Module::from_words(bytemuck::cast_slice(&vec)))?;
+
+        let resources = compiler.shader_resources()?.all_resources()?;
+        let ubo = resources.uniform_buffers[0].id;
+
+        assert!(matches!(
+            other.yield_id(ubo),
+            Err(SpirvCrossError::HandleFromWrongCompiler { id }) if id == ubo.id()
+        ));
+        assert!(compiler.yield_id(ubo).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn handle_out_of_range() -> Result<(), SpirvCrossError> {
+        let vec = Vec::from(BASIC_SPV);
+
+        let compiler: Compiler<targets::None> =
+            Compiler::new(Module::from_words(bytemuck::cast_slice(&vec)))?;
+
+        let forged = unsafe { compiler.create_handle(VariableId(SpvId(compiler.id_bound()))) };
+        assert!(matches!(
+            compiler.yield_id(forged),
+            Err(SpirvCrossError::InvalidHandle(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn id_bound() -> Result<(), SpirvCrossError> {
+        let vec = Vec::from(BASIC_SPV);
+        let words: &[u32] = bytemuck::cast_slice(&vec);
+
+        let compiler: Compiler<targets::None> = Compiler::new(Module::from_words(words))?;
+        let resources = compiler.shader_resources()?.all_resources()?;
+
+        assert_eq!(words[3], compiler.id_bound());
+        assert!(resources.uniform_buffers[0].id.id() < compiler.id_bound());
+
+        Ok(())
+    }
+}
@@ -81,7 +81,32 @@
 //!
 //! ```toml
 //! [dependencies]
-//! spirv-cross2 = { features = ["f16", "gfx-maths-types", "glam-types"] }
+//! spirv-cross2 = { features = ["f16", "gfx-math-types", "glam-types"] }
+//! ```
+//!
+//! With `glam-types`, vectors and matrices such as `glam::Vec3`, `glam::IVec4`, `glam::UVec2` and `glam::Mat4`
+//! can be passed directly to [`Compiler::set_specialization_constant_value`].
+//!
+//! ### Serialization of reflection data
+//! With the `serde` feature, reflection types such as [`Type`](reflect::Type), [`AllResources`](reflect::AllResources)
+//! and [`EntryPoint`](reflect::EntryPoint) implement `Serialize` and `Deserialize`, so reflection output can be cached.
+//! Handles serialize as their raw SPIR-V ID. Deserialized handles can not be used with a compiler instance.
+//!
+//! ```toml
+//! [dependencies]
+//! spirv-cross2 = { features = ["serde"] }
+//! ```
+//!
+//! ### naga type conversion
+//! With the `naga` feature, [`Scalar`](reflect::Scalar) converts into `naga::Scalar`, and
+//! `Compiler::naga_type` converts reflected types into a `naga::UniqueArena` of naga types.
+//!
+//! naga requires Rust 1.74 or later, which is above the MSRV of this crate, so the `naga` feature
+//! is not included in `full` and must be enabled separately.
+//!
+//! ```toml
+//! [dependencies]
+//! spirv-cross2 = { features = ["naga"] }
 //! ```
 //!
 //! ## Usage
@@ -132,11 +157,16 @@
 //!
 use spirv_cross_sys::{spvc_compiler_s, SpvId};
 
-use crate::cell::{AllocationDropGuard, CrossAllocationCell};
+use crate::cell::{AllocationDropGuard, CrossAllocationCell, ParsedIr};
 use crate::sealed::{ContextRooted, Sealed};
 use crate::targets::Target;
+use std::any::Any;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::ptr::NonNull;
+use std::sync::Arc;
 
 /// Compilation of SPIR-V to a textual format.
 pub mod compile;
@@ -183,6 +213,11 @@ pub(crate) mod sealed {
 
     pub trait ContextRooted {
         fn context(&self) -> NonNull<spvc_context_s>;
+
+        /// The execution model of the current entry point, if rooted in a compiler.
+        fn execution_model(&self) -> Option<spirv::ExecutionModel> {
+            None
+        }
     }
 }
 
@@ -190,12 +225,67 @@ pub use crate::error::SpirvCrossError;
 pub use crate::string::CompilerStr;
 
 /// A SPIR-V Module represented as SPIR-V words.
-pub struct Module<'a>(&'a [SpvId]);
+pub struct Module<'a>(Cow<'a, [SpvId]>);
 
 impl<'a> Module<'a> {
     /// Create a new `Module` from SPIR-V words.
     pub fn from_words(words: &'a [u32]) -> Self {
-        Module(bytemuck::must_cast_slice(words))
+        Module(Cow::Borrowed(bytemuck::must_cast_slice(words)))
+    }
+
+    /// Create a new `Module` from the bytes of a SPIR-V binary, such as the contents of a `.spv` file.
+    ///
+    /// Unlike [`Module::from_words`], the bytes do not need to be aligned. The endianness of the
+    /// module is detected from the magic number, and the words are byte-swapped if it is the
+    /// opposite of the host endianness.
+    ///
+    /// If the length is not a multiple of 4, or the module does not start with the
+    /// SPIR-V magic number, returns [`SpirvCrossError::InvalidSpirv`].
+    pub fn from_bytes(bytes: &[u8]) -> error::Result<Module<'static>> {
+        if bytes.len() % 4 != 0 {
+            return Err(SpirvCrossError::InvalidSpirv(String::from(
+                "Module length is not a multiple of 4.",
+            )));
+        }
+
+        let mut words: Vec<u32> = bytes
+            .chunks_exact(4)
+            .map(|word| u32::from_ne_bytes([word[0], word[1], word[2], word[3]]))
+            .collect();
+
+        match words.first() {
+            Some(&spirv::MAGIC_NUMBER) => {}
+            Some(magic) if magic.swap_bytes() == spirv::MAGIC_NUMBER => {
+                for word in &mut words {
+                    *word = word.swap_bytes();
+                }
+            }
+            _ => {
+                return Err(SpirvCrossError::InvalidSpirv(String::from(
+                    "Module does not start with the SPIR-V magic number.",
+                )))
+            }
+        }
+
+        Ok(Module(Cow::Owned(words.into_iter().map(SpvId).collect())))
+    }
+
+    /// Get the number of words in the module.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether the module contains no words.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns whether the module has a complete 5-word header starting with the SPIR-V magic number.
+    ///
+    /// This is a cheap check to reject truncated or non-SPIR-V modules before creating a [`Compiler`],
+    /// the rest of the module is not validated.
+    pub fn magic_is_valid(&self) -> bool {
+        self.0.len() >= 5 && self.0[0].0 == spirv::MAGIC_NUMBER
     }
 }
 
@@ -222,6 +312,25 @@ pub trait ToStatic: Sealed {
 pub struct Compiler<T> {
     pub(crate) ptr: NonNull<spvc_compiler_s>,
     ctx: CrossAllocationCell,
+    /// The module as it was parsed, used to create clean copies of the compiler.
+    ir: ParsedIr,
+    /// The words of the module, shared with copies of the compiler.
+    words: Arc<[u32]>,
+    /// Type descriptions returned by `Compiler::type_description`, with names borrowed
+    /// from the compiler. Cleared by `Compiler::ptr_mut` whenever the compiler is mutated.
+    type_cache: RefCell<HashMap<handle::TypeId, reflect::Type<'static>>>,
+    /// The `Sampled` operand of every `OpTypeImage` and `OpTypeSampledImage` in the original
+    /// words, by type ID. Built on first use, and never invalidated since the words never change.
+    image_sampled_operands: RefCell<Option<HashMap<u32, u32>>>,
+    /// Entry point renames made with `Compiler::rename_entry_point`, in order,
+    /// as `(from, to, model)`.
+    entry_point_renames: Vec<(String, String, spirv::ExecutionModel)>,
+    /// The entry point selected with `Compiler::set_entry_point`, by its current name.
+    entry_point: Option<(String, spirv::ExecutionModel)>,
+    /// State specific to the target, a boxed `T::State`.
+    ///
+    /// The state is type erased so that `Compiler<T>` does not need a `Target` bound.
+    state: Box<dyn Any + Send>,
     _pd: PhantomData<T>,
 }
 
@@ -238,13 +347,52 @@ impl<T: Target> Compiler<T> {
     pub(crate) unsafe fn new_from_raw(
         ptr: NonNull<spvc_compiler_s>,
         ctx: CrossAllocationCell,
+        ir: ParsedIr,
+        words: Arc<[u32]>,
     ) -> Compiler<T> {
         Compiler {
             ptr,
             ctx,
+            ir,
+            words,
+            entry_point_renames: Vec::new(),
+            entry_point: None,
+            type_cache: RefCell::new(HashMap::new()),
+            image_sampled_operands: RefCell::new(None),
+            state: Box::new(T::State::default()),
             _pd: PhantomData,
         }
     }
+
+    /// Create a new compiler instance from the SPIR-V module this compiler was created with.
+    ///
+    /// The module is not parsed again. The new instance is created from a copy of the module
+    /// as it was originally parsed, so the copy is clean: mutations made to this instance,
+    /// such as decorations, names, the current entry point, or target specific state such as
+    /// resource bindings, are not carried over.
+    ///
+    /// The copy shares the parsed module and [`Compiler::original_words`] with this instance.
+    ///
+    /// This allows compiling the same module multiple times with different options,
+    /// since [`Compiler::compile`] consumes the instance.
+    pub fn try_clone(&self) -> error::Result<Compiler<T>> {
+        let allocs = CrossAllocationCell::new()?;
+        allocs.into_compiler_from_ir(self.ir.clone(), Arc::clone(&self.words))
+    }
+
+    /// Get the target specific state of the compiler instance.
+    pub(crate) fn state(&self) -> &T::State {
+        self.state
+            .downcast_ref()
+            .expect("compiler state should always be the state of its target")
+    }
+
+    /// Get the target specific state of the compiler instance mutably.
+    pub(crate) fn state_mut(&mut self) -> &mut T::State {
+        self.state
+            .downcast_mut()
+            .expect("compiler state should always be the state of its target")
+    }
 }
 
 /// Holds on to the pointer for a compiler instance,
@@ -270,6 +418,28 @@ pub(crate) struct PhantomCompiler {
 }
 
 impl<T> Compiler<T> {
+    /// Get the SPIR-V words the compiler was created with.
+    ///
+    /// SPIRV-Cross never re-emits SPIR-V. Mutations made through the reflection API,
+    /// such as [`Compiler::set_decoration`] or [`Compiler::set_name`], only affect the
+    /// generated source, and are **not** reflected in the returned words.
+    ///
+    /// To produce modified SPIR-V, copy these words and patch decoration literals in-place
+    /// at the offsets returned by [`Compiler::binary_offset_for_decoration`].
+    pub fn original_words(&self) -> &[u32] {
+        &self.words
+    }
+
+    /// Get a pointer to the compiler instance to mutate it through FFI.
+    ///
+    /// Cached type descriptions borrow names from the compiler, so they are cleared here
+    /// before any mutation can change or free them. Methods that mutate the compiler
+    /// must use this instead of `ptr`.
+    pub(crate) fn ptr_mut(&mut self) -> *mut spvc_compiler_s {
+        self.type_cache.get_mut().clear();
+        self.ptr.as_ptr()
+    }
+
     /// Create a type erased phantom for lifetime tracking purposes.
     ///
     /// This function is unsafe because a [`PhantomCompiler`] can be used to
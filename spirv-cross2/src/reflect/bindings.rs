@@ -0,0 +1,321 @@
+use crate::error;
+use crate::handle::{Handle, TypeId, VariableId};
+use crate::reflect::{ArrayDimension, ResourceType, TypeInner};
+use crate::sealed::Sealed;
+use crate::string::CompilerStr;
+use crate::{Compiler, ToStatic};
+use std::collections::BTreeMap;
+
+/// The resource types that consume a descriptor binding.
+pub(crate) const DESCRIPTOR_RESOURCE_TYPES: [ResourceType; 9] = [
+    ResourceType::UniformBuffer,
+    ResourceType::StorageBuffer,
+    ResourceType::SubpassInput,
+    ResourceType::StorageImage,
+    ResourceType::SampledImage,
+    ResourceType::AtomicCounter,
+    ResourceType::SeparateImage,
+    ResourceType::SeparateSamplers,
+    ResourceType::AccelerationStructure,
+];
+
+/// Descriptor binding information for a shader resource.
+#[derive(Debug)]
+pub struct BindingInfo<'a> {
+    /// A handle to the variable of the resource.
+    pub id: Handle<VariableId>,
+    /// The name of the resource.
+    pub name: CompilerStr<'a>,
+    /// The descriptor set of the resource.
+    ///
+    /// If the resource has no `DescriptorSet` decoration, this is 0.
+    pub set: u32,
+    /// The binding of the resource.
+    ///
+    /// If the resource has no `Binding` decoration, this is 0.
+    pub binding: u32,
+    /// The type of the resource.
+    pub resource_type: ResourceType,
+    /// The number of descriptors consumed by the resource.
+    ///
+    /// This is 1 for non-arrayed resources, and 0 for runtime arrays.
+    pub descriptor_count: u32,
+    /// The execution model of the current entry point.
+    pub stage: spirv::ExecutionModel,
+}
+
+impl Sealed for BindingInfo<'_> {}
+impl ToStatic for BindingInfo<'_> {
+    type Static<'a>
+        = BindingInfo<'static>
+    where
+        'a: 'static;
+
+    fn to_static(&self) -> Self::Static<'static> {
+        BindingInfo {
+            id: self.id,
+            name: CompilerStr::from_string(self.name.to_string()),
+            set: self.set,
+            binding: self.binding,
+            resource_type: self.resource_type,
+            descriptor_count: self.descriptor_count,
+            stage: self.stage,
+        }
+    }
+}
+
+impl Clone for BindingInfo<'_> {
+    fn clone(&self) -> BindingInfo<'static> {
+        self.to_static()
+    }
+}
+
+impl<'a, 'b> From<&'a BindingInfo<'b>> for Handle<VariableId> {
+    fn from(value: &'a BindingInfo<'b>) -> Self {
+        value.id
+    }
+}
+
+/// Descriptor binding information for an acceleration structure.
+#[derive(Debug)]
+pub struct AccelStructBinding<'a> {
+    /// A handle to the variable of the acceleration structure.
+    pub id: Handle<VariableId>,
+    /// The name of the acceleration structure.
+    pub name: CompilerStr<'a>,
+    /// The descriptor set of the acceleration structure.
+    ///
+    /// If the resource has no `DescriptorSet` decoration, this is 0.
+    pub set: u32,
+    /// The binding of the acceleration structure.
+    ///
+    /// If the resource has no `Binding` decoration, this is 0.
+    pub binding: u32,
+}
+
+impl Sealed for AccelStructBinding<'_> {}
+impl ToStatic for AccelStructBinding<'_> {
+    type Static<'a>
+        = AccelStructBinding<'static>
+    where
+        'a: 'static;
+
+    fn to_static(&self) -> Self::Static<'static> {
+        AccelStructBinding {
+            id: self.id,
+            name: CompilerStr::from_string(self.name.to_string()),
+            set: self.set,
+            binding: self.binding,
+        }
+    }
+}
+
+impl Clone for AccelStructBinding<'_> {
+    fn clone(&self) -> AccelStructBinding<'static> {
+        self.to_static()
+    }
+}
+
+impl<'a, 'b> From<&'a AccelStructBinding<'b>> for Handle<VariableId> {
+    fn from(value: &'a AccelStructBinding<'b>) -> Self {
+        value.id
+    }
+}
+
+/// Reflection of descriptor bindings.
+impl<T> Compiler<T> {
+    /// Get the descriptor binding information for every resource
+    /// that consumes a descriptor in the current entry point.
+    ///
+    /// This covers uniform and storage buffers, sampled, storage and separate images,
+    /// separate samplers, subpass inputs, atomic counters and acceleration structures.
+    /// Push constants and stage inputs and outputs are not included.
+    pub fn binding_table(&self) -> error::Result<Vec<BindingInfo<'static>>> {
+        let resources = self.shader_resources()?;
+        let stage = self.execution_model()?;
+
+        let mut bindings = Vec::new();
+        for resource_type in DESCRIPTOR_RESOURCE_TYPES {
+            for resource in resources.resources_for_type(resource_type)? {
+                let set = self
+                    .decoration(resource.id, spirv::Decoration::DescriptorSet)?
+                    .and_then(|s| s.as_literal())
+                    .unwrap_or(0);
+                let binding = self
+                    .decoration(resource.id, spirv::Decoration::Binding)?
+                    .and_then(|b| b.as_literal())
+                    .unwrap_or(0);
+                let descriptor_count = self.descriptor_count(resource.type_id)?;
+
+                bindings.push(BindingInfo {
+                    id: resource.id,
+                    name: resource.name,
+                    set,
+                    binding,
+                    resource_type,
+                    descriptor_count,
+                    stage,
+                });
+            }
+        }
+
+        Ok(bindings)
+    }
+
+    /// Get the descriptor set and binding of every acceleration structure
+    /// in the current entry point.
+    pub fn acceleration_structures(&self) -> error::Result<Vec<AccelStructBinding<'static>>> {
+        let resources = self.shader_resources()?;
+
+        let mut structures = Vec::new();
+        for resource in resources.resources_for_type(ResourceType::AccelerationStructure)? {
+            let set = self
+                .decoration(resource.id, spirv::Decoration::DescriptorSet)?
+                .and_then(|s| s.as_literal())
+                .unwrap_or(0);
+            let binding = self
+                .decoration(resource.id, spirv::Decoration::Binding)?
+                .and_then(|b| b.as_literal())
+                .unwrap_or(0);
+
+            structures.push(AccelStructBinding {
+                id: resource.id,
+                name: resource.name,
+                set,
+                binding,
+            });
+        }
+
+        Ok(structures)
+    }
+
+    /// Get the highest `DescriptorSet` decoration across all resources that
+    /// consume a descriptor binding.
+    ///
+    /// Returns `None` if no resource has a `DescriptorSet` decoration.
+    pub fn max_descriptor_set(&self) -> error::Result<Option<u32>> {
+        let resources = self.shader_resources()?;
+
+        let mut max_set = None;
+        for resource_type in DESCRIPTOR_RESOURCE_TYPES {
+            for resource in resources.resources_for_type(resource_type)? {
+                let set = self
+                    .decoration(resource.id, spirv::Decoration::DescriptorSet)?
+                    .and_then(|s| s.as_literal());
+                max_set = max_set.max(set);
+            }
+        }
+
+        Ok(max_set)
+    }
+
+    /// Group the variables that share a descriptor set and binding.
+    ///
+    /// Only groups with more than one variable are returned, such as a separate image
+    /// and separate sampler declared at the same binding, sorted by set and binding.
+    /// Variables within a group are in the order of [`Compiler::binding_table`].
+    /// Resources without a `Binding` decoration are ignored.
+    pub fn binding_aliases(&self) -> error::Result<Vec<Vec<Handle<VariableId>>>> {
+        let resources = self.shader_resources()?;
+
+        let mut groups: BTreeMap<(u32, u32), Vec<Handle<VariableId>>> = BTreeMap::new();
+        for resource_type in DESCRIPTOR_RESOURCE_TYPES {
+            for resource in resources.resources_for_type(resource_type)? {
+                let Some(binding) = self
+                    .decoration(resource.id, spirv::Decoration::Binding)?
+                    .and_then(|b| b.as_literal())
+                else {
+                    continue;
+                };
+                let set = self
+                    .decoration(resource.id, spirv::Decoration::DescriptorSet)?
+                    .and_then(|s| s.as_literal())
+                    .unwrap_or(0);
+
+                let group = groups.entry((set, binding)).or_default();
+                if !group.contains(&resource.id) {
+                    group.push(resource.id);
+                }
+            }
+        }
+
+        Ok(groups
+            .into_values()
+            .filter(|group| group.len() > 1)
+            .collect())
+    }
+
+    /// Get the number of descriptors consumed by a resource of the given type.
+    ///
+    /// Array dimensions specified as specialization constants are resolved
+    /// with their current value.
+    pub(crate) fn descriptor_count(&self, type_id: Handle<TypeId>) -> error::Result<u32> {
+        let TypeInner::Array { dimensions, .. } = self.type_description(type_id)?.inner else {
+            return Ok(1);
+        };
+
+        let mut count = 1u32;
+        for dim in dimensions {
+            match dim {
+                ArrayDimension::Literal(a) => count = count.saturating_mul(a),
+                ArrayDimension::Constant(c) => {
+                    let value = self.specialization_constant_value::<u32>(c)?;
+                    count = count.saturating_mul(value);
+                }
+            }
+        }
+
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::SpirvCrossError;
+    use crate::reflect::ResourceType;
+    use crate::Compiler;
+    use crate::{targets, Module};
+
+    static BASIC_SPV: &[u8] = include_bytes!("../../basic.spv");
+
+    #[test]
+    pub fn binding_table() -> Result<(), SpirvCrossError> {
+        let vec = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&vec));
+
+        let compiler: Compiler<targets::None> = Compiler::new(words)?;
+        let bindings = compiler.binding_table()?;
+
+        assert_eq!(2, bindings.len());
+
+        let ubo = &bindings[0];
+        assert_eq!(ResourceType::UniformBuffer, ubo.resource_type);
+        assert_eq!((0, 0), (ubo.set, ubo.binding));
+        assert_eq!(1, ubo.descriptor_count);
+        assert_eq!(spirv::ExecutionModel::Fragment, ubo.stage);
+
+        let tex = &bindings[1];
+        assert_eq!(ResourceType::SampledImage, tex.resource_type);
+        assert_eq!("tex", tex.name);
+        assert_eq!((0, 1), (tex.set, tex.binding));
+        assert_eq!(1, tex.descriptor_count);
+        assert_eq!(spirv::ExecutionModel::Fragment, tex.stage);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn max_descriptor_set() -> Result<(), SpirvCrossError> {
+        let vec = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&vec));
+
+        let mut compiler: Compiler<targets::None> = Compiler::new(words)?;
+        assert_eq!(Some(0), compiler.max_descriptor_set()?);
+
+        let tex = compiler.binding_table()?[1].id;
+        compiler.set_decoration(tex, spirv::Decoration::DescriptorSet, Some(2u32))?;
+        assert_eq!(Some(2), compiler.max_descriptor_set()?);
+
+        Ok(())
+    }
+}
@@ -1,18 +1,36 @@
 use crate::error;
-use crate::error::ToContextError;
-use crate::handle::Handle;
+use crate::error::{SpirvCrossError, ToContextError};
+use crate::handle::{Handle, TypeId};
+use crate::reflect::{ArrayDimension, ResourceType, TypeInner};
 use crate::Compiler;
+use spirv::StorageClass;
 use spirv_cross_sys as sys;
 use spirv_cross_sys::{SpvId, VariableId};
 
 /// A range over a buffer.
 pub use spirv_cross_sys::BufferRange;
 
+/// The kind of buffer a block variable is, created by [`Compiler::classify_buffer`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum BufferClass {
+    /// A uniform buffer, in the `Uniform` storage class with a `Block` decoration.
+    Uniform,
+    /// A storage buffer, in the `StorageBuffer` storage class, or the `Uniform`
+    /// storage class with a `BufferBlock` decoration.
+    Storage,
+    /// A push constant block, in the `PushConstant` storage class.
+    PushConstant,
+    /// A shader record buffer, in the `ShaderRecordBufferKHR` storage class.
+    ShaderRecord,
+}
+
 /// Reflection of buffers (UBO, SSBOs, and PushConstant blocks).
 impl<T> Compiler<T> {
     /// Returns a list of which members of a struct are potentially in use by a
     /// SPIR-V shader. The granularity of this analysis is per-member of a struct.
     /// This can be used for Buffer (UBO), BufferBlock/StorageBuffer (SSBO) and PushConstant blocks.
+    ///
+    /// If no members of the buffer are used, returns an empty slice.
     pub fn active_buffer_ranges(
         &self,
         handle: impl Into<Handle<VariableId>>,
@@ -31,10 +49,229 @@ impl<T> Compiler<T> {
             )
             .ok(self)?;
 
+            // A buffer with no active members may not have a valid pointer to its ranges.
+            if size == 0 || ranges.is_null() {
+                return Ok(&[]);
+            }
+
             Ok(std::slice::from_raw_parts(ranges, size))
         }
     }
 
+    /// Returns the `(offset, size)` byte ranges of the push constant block that are
+    /// used by the current entry point.
+    ///
+    /// Ranges of adjacent or overlapping members are merged, and the result is sorted by offset.
+    /// This can be used to narrow a `VkPushConstantRange` to what the stage of the current
+    /// entry point actually accesses.
+    ///
+    /// If the entry point has no push constant block, returns an empty list.
+    pub fn active_push_constant_ranges(&self) -> error::Result<Vec<(u32, u32)>> {
+        let resources = self.shader_resources()?;
+        let mut ranges = Vec::new();
+        for push_constant in resources.resources_for_type(ResourceType::PushConstant)? {
+            for range in self.active_buffer_ranges(push_constant.id)? {
+                ranges.push((range.offset as u32, range.range as u32));
+            }
+        }
+
+        ranges.sort_unstable();
+
+        let mut merged: Vec<(u32, u32)> = Vec::with_capacity(ranges.len());
+        for (offset, size) in ranges {
+            if let Some((last_offset, last_size)) = merged.last_mut() {
+                let last_end = *last_offset + *last_size;
+                if offset <= last_end {
+                    *last_size = last_end.max(offset + size) - *last_offset;
+                    continue;
+                }
+            }
+            merged.push((offset, size));
+        }
+
+        Ok(merged)
+    }
+
+    /// Get the flattened names of every leaf member of a buffer block, as exposed by
+    /// legacy OpenGL to `glGetUniformLocation`, along with their types and absolute byte offsets.
+    ///
+    /// Names are dotted access paths prefixed with the block name, such as `Block.inner.value`.
+    /// Arrays are expanded with an `[i]` index for every element up to the declared size,
+    /// so runtime arrays produce no names. Members without a name are named `_mN`
+    /// after their index, matching SPIRV-Cross.
+    ///
+    /// If the variable is not a buffer block, returns [`SpirvCrossError::InvalidArgument`].
+    pub fn flattened_uniform_names(
+        &self,
+        variable: impl Into<Handle<VariableId>>,
+    ) -> error::Result<Vec<(String, Handle<TypeId>, u32)>> {
+        let variable = variable.into();
+        let mut type_id = self.variable_type(variable)?;
+        if let TypeInner::Pointer { base, .. } = self.type_description(type_id)?.inner {
+            type_id = base;
+        }
+
+        let ty = self.type_description(type_id)?;
+        if !matches!(ty.inner, TypeInner::Struct(_)) {
+            return Err(SpirvCrossError::InvalidArgument(String::from(
+                "The variable is not a buffer block",
+            )));
+        }
+
+        let block_name = match ty.name {
+            Some(name) => name.to_string(),
+            None => self
+                .name(variable)?
+                .map(|name| name.to_string())
+                .unwrap_or_default(),
+        };
+
+        let mut names = Vec::new();
+        self.flatten_uniform_names(block_name, type_id, 0, &mut names)?;
+        Ok(names)
+    }
+
+    fn flatten_uniform_names(
+        &self,
+        name: String,
+        type_id: Handle<TypeId>,
+        offset: u32,
+        names: &mut Vec<(String, Handle<TypeId>, u32)>,
+    ) -> error::Result<()> {
+        match self.type_description(type_id)?.inner {
+            TypeInner::Struct(ty) => {
+                for member in ty.members {
+                    let member_name = match member.name {
+                        Some(member_name) => format!("{name}.{member_name}"),
+                        None => format!("{name}._m{}", member.index),
+                    };
+                    self.flatten_uniform_names(
+                        member_name,
+                        member.id,
+                        offset + member.offset,
+                        names,
+                    )?;
+                }
+            }
+            TypeInner::Array {
+                base,
+                dimensions,
+                stride,
+                ..
+            } => {
+                let mut counts = Vec::with_capacity(dimensions.len());
+                for dimension in dimensions {
+                    counts.push(match dimension {
+                        ArrayDimension::Literal(count) => count,
+                        ArrayDimension::Constant(constant) => {
+                            self.specialization_constant_value::<u32>(constant)?
+                        }
+                    });
+                }
+
+                // The stride is of the outermost dimension, so divide out the inner dimensions.
+                let inner_counts = &counts[..counts.len().saturating_sub(1)];
+                let element_stride = match stride {
+                    Some(stride) => stride / inner_counts.iter().product::<u32>().max(1),
+                    None => self.type_description(base)?.size_hint.declared() as u32,
+                };
+
+                self.flatten_uniform_array_names(
+                    &name,
+                    base,
+                    offset,
+                    &counts,
+                    element_stride,
+                    names,
+                )?;
+            }
+            _ => names.push((name, type_id, offset)),
+        }
+
+        Ok(())
+    }
+
+    /// Expand array dimensions, in SPIR-V order from innermost to outermost.
+    fn flatten_uniform_array_names(
+        &self,
+        name: &str,
+        base: Handle<TypeId>,
+        offset: u32,
+        counts: &[u32],
+        element_stride: u32,
+        names: &mut Vec<(String, Handle<TypeId>, u32)>,
+    ) -> error::Result<()> {
+        let Some((&count, inner_counts)) = counts.split_last() else {
+            return self.flatten_uniform_names(name.to_string(), base, offset, names);
+        };
+
+        let stride = element_stride * inner_counts.iter().product::<u32>();
+        for index in 0..count {
+            self.flatten_uniform_array_names(
+                &format!("{name}[{index}]"),
+                base,
+                offset + index * stride,
+                inner_counts,
+                element_stride,
+                names,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Classify a buffer block variable by its storage class and block decoration.
+    ///
+    /// This does not depend on the [`ResourceType`] the variable is reflected as,
+    /// and accounts for storage buffers declared with the legacy `BufferBlock` decoration.
+    ///
+    /// If the variable is not a buffer block, returns [`SpirvCrossError::InvalidArgument`].
+    pub fn classify_buffer(
+        &self,
+        variable: impl Into<Handle<VariableId>>,
+    ) -> error::Result<BufferClass> {
+        let type_id = self.variable_type(variable)?;
+        // Arrays of blocks are reflected as arrays with the storage class of the pointer.
+        let (block_type, storage) = match self.type_description(type_id)?.inner {
+            TypeInner::Pointer { base, storage, .. } | TypeInner::Array { base, storage, .. } => {
+                (base, storage)
+            }
+            _ => {
+                return Err(SpirvCrossError::InvalidArgument(String::from(
+                    "The variable is not a buffer block",
+                )))
+            }
+        };
+
+        match storage {
+            StorageClass::PushConstant => return Ok(BufferClass::PushConstant),
+            StorageClass::ShaderRecordBufferKHR => return Ok(BufferClass::ShaderRecord),
+            StorageClass::StorageBuffer => return Ok(BufferClass::Storage),
+            StorageClass::Uniform => {}
+            _ => {
+                return Err(SpirvCrossError::InvalidArgument(String::from(
+                    "The variable is not a buffer block",
+                )))
+            }
+        }
+
+        if self
+            .decoration(block_type, spirv::Decoration::BufferBlock)?
+            .is_some()
+        {
+            Ok(BufferClass::Storage)
+        } else if self
+            .decoration(block_type, spirv::Decoration::Block)?
+            .is_some()
+        {
+            Ok(BufferClass::Uniform)
+        } else {
+            Err(SpirvCrossError::InvalidArgument(String::from(
+                "The variable is not a buffer block",
+            )))
+        }
+    }
+
     /// Queries if a buffer object has a neighbor "counter" buffer.
     /// If so, the ID of that counter buffer will be returned.
     ///
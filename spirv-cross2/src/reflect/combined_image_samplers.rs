@@ -58,11 +58,8 @@ impl<T> Compiler<T> {
     ) -> error::Result<BuiltDummySamplerProof> {
         unsafe {
             let mut var_id = VariableId::from(0);
-            sys::spvc_compiler_build_dummy_sampler_for_combined_images(
-                self.ptr.as_ptr(),
-                &mut var_id,
-            )
-            .ok(&*self)?;
+            sys::spvc_compiler_build_dummy_sampler_for_combined_images(self.ptr_mut(), &mut var_id)
+                .ok(&*self)?;
 
             let sampler_id = self.create_handle_if_not_zero(var_id);
 
@@ -106,7 +103,7 @@ impl<T> Compiler<T> {
         }
 
         unsafe {
-            sys::spvc_compiler_build_combined_image_samplers(self.ptr.as_ptr()).ok(&*self)?;
+            sys::spvc_compiler_build_combined_image_samplers(self.ptr_mut()).ok(&*self)?;
 
             Ok(())
         }
@@ -1,5 +1,6 @@
 use crate::sealed::Sealed;
 use spirv_cross_sys::{spvc_constant, spvc_specialization_constant, TypeId};
+use std::collections::BTreeMap;
 use std::mem::MaybeUninit;
 use std::ops::{Index, IndexMut};
 use std::slice;
@@ -7,6 +8,7 @@ use std::slice;
 use crate::error::{SpirvCrossError, ToContextError};
 use crate::handle::{ConstantId, Handle};
 use crate::iter::impl_iterator;
+use crate::reflect::{BitWidth, ExecutionModeArguments, ScalarKind, TypeInner};
 use crate::{error, Compiler, PhantomCompiler};
 use spirv_cross_sys as sys;
 
@@ -88,6 +90,7 @@ impl ConstantScalar for bool {
 
 /// A SPIR-V specialization constant
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SpecializationConstant {
     /// The handle to the constant.
     pub id: Handle<ConstantId>,
@@ -173,7 +176,7 @@ impl<T> Compiler<T> {
         let constant = self.yield_id(handle)?;
         unsafe {
             // SAFETY: yield_id ensures safety.
-            let handle = sys::spvc_compiler_get_constant_handle(self.ptr.as_ptr(), constant);
+            let handle = sys::spvc_compiler_get_constant_handle(self.ptr_mut(), constant);
             Self::bounds_check_constant(handle, column, row)?;
             S::set(handle, column, row, value)
         }
@@ -246,6 +249,30 @@ impl<T> Compiler<T> {
         }
     }
 
+    /// Set the value of a subconstant of a composite specialization constant.
+    ///
+    /// This resolves the subconstant at `index` of the composite, as returned by
+    /// [`Compiler::specialization_sub_constants`], and sets its value with
+    /// [`Compiler::set_specialization_constant_value`].
+    ///
+    /// If the composite has no subconstant at `index`,
+    /// [`SpirvCrossError::IndexOutOfBounds`] will be returned.
+    pub fn set_sub_constant_value<S: ConstantValue>(
+        &mut self,
+        composite: Handle<ConstantId>,
+        index: usize,
+        value: S,
+    ) -> error::Result<()> {
+        let Some(sub_constant) = self.specialization_sub_constants(composite)?.nth(index) else {
+            return Err(SpirvCrossError::IndexOutOfBounds {
+                row: index as u32,
+                column: 0,
+            });
+        };
+
+        self.set_specialization_constant_value(sub_constant, value)
+    }
+
     /// In SPIR-V, the compute work group size can be represented by a constant vector, in which case
     /// the LocalSize execution mode is ignored.
     ///
@@ -315,6 +342,45 @@ impl<T> Compiler<T> {
         }
     }
 
+    /// Get the effective workgroup size of the current entry point.
+    ///
+    /// For `LocalSizeId`, each dimension is the current value of its constant. For `LocalSize`,
+    /// dimensions which are specialization constants, as returned by
+    /// [`Compiler::work_group_size_specialization_constants`], are replaced with the current
+    /// value of the constant, so the result reflects any prior call to
+    /// [`Compiler::set_specialization_constant_value`].
+    ///
+    /// If the entry point does not declare a workgroup size, returns [`SpirvCrossError::InvalidOperation`].
+    pub fn workgroup_size(&self) -> error::Result<[u32; 3]> {
+        if let Some(ExecutionModeArguments::LocalSizeId { x, y, z }) =
+            self.execution_mode_arguments(spirv::ExecutionMode::LocalSizeId)?
+        {
+            return Ok([
+                self.specialization_constant_value::<u32>(x)?,
+                self.specialization_constant_value::<u32>(y)?,
+                self.specialization_constant_value::<u32>(z)?,
+            ]);
+        }
+
+        let Some(ExecutionModeArguments::LocalSize { x, y, z }) =
+            self.execution_mode_arguments(spirv::ExecutionMode::LocalSize)?
+        else {
+            return Err(SpirvCrossError::InvalidOperation(String::from(
+                "The entry point does not declare a workgroup size",
+            )));
+        };
+
+        let constants = self.work_group_size_specialization_constants();
+        let mut size = [x, y, z];
+        for (dimension, constant) in size.iter_mut().zip([constants.x, constants.y, constants.z]) {
+            if let Some(constant) = constant {
+                *dimension = self.specialization_constant_value::<u32>(constant.id)?;
+            }
+        }
+
+        Ok(size)
+    }
+
     /// Get the type of the specialization constant.
     pub fn specialization_constant_type(
         &self,
@@ -370,6 +436,71 @@ impl<T: ConstantScalar> ConstantValue for T {
     }
 }
 
+/// The value of a scalar constant, widened to the largest type of its kind.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScalarValue {
+    /// A boolean value.
+    Bool(bool),
+    /// A signed integer value of any width.
+    Int(i64),
+    /// An unsigned integer value of any width.
+    Uint(u64),
+    /// A floating point value of any width.
+    Float(f64),
+}
+
+/// The value of a component of a constant, in the declared type of the constant.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ConstantNumeric {
+    /// A boolean value.
+    Bool(bool),
+    /// An 8-bit signed integer.
+    I8(i8),
+    /// A 16-bit signed integer.
+    I16(i16),
+    /// A 32-bit signed integer.
+    I32(i32),
+    /// A 64-bit signed integer.
+    I64(i64),
+    /// An 8-bit unsigned integer.
+    U8(u8),
+    /// A 16-bit unsigned integer.
+    U16(u16),
+    /// A 32-bit unsigned integer.
+    U32(u32),
+    /// A 64-bit unsigned integer.
+    U64(u64),
+    /// A 16-bit float, widened to an `f32`.
+    F16(f32),
+    /// A 32-bit float.
+    F32(f32),
+    /// A 64-bit float.
+    F64(f64),
+}
+
+impl ConstantNumeric {
+    /// Get the value as an `f64`.
+    ///
+    /// Booleans are `1.0` if true and `0.0` if false. 64-bit integers
+    /// may lose precision.
+    pub fn as_f64(&self) -> f64 {
+        match *self {
+            ConstantNumeric::Bool(value) => f64::from(u8::from(value)),
+            ConstantNumeric::I8(value) => value.into(),
+            ConstantNumeric::I16(value) => value.into(),
+            ConstantNumeric::I32(value) => value.into(),
+            ConstantNumeric::I64(value) => value as f64,
+            ConstantNumeric::U8(value) => value.into(),
+            ConstantNumeric::U16(value) => value.into(),
+            ConstantNumeric::U32(value) => value.into(),
+            ConstantNumeric::U64(value) => value as f64,
+            ConstantNumeric::F16(value) | ConstantNumeric::F32(value) => value.into(),
+            ConstantNumeric::F64(value) => value,
+        }
+    }
+}
+
 impl<T> Compiler<T> {
     /// Get the value of the specialization value.
     ///
@@ -422,7 +553,7 @@ impl<T> Compiler<T> {
         let constant = self.yield_id(handle)?;
         unsafe {
             // SAFETY: yield_id ensures safety.
-            let handle = sys::spvc_compiler_get_constant_handle(self.ptr.as_ptr(), constant);
+            let handle = sys::spvc_compiler_get_constant_handle(self.ptr_mut(), constant);
 
             // bounds check the limits of the type.
             Self::bounds_check_constant(handle, S::COLUMNS as u32 - 1, S::VECSIZE as u32 - 1)?;
@@ -436,6 +567,134 @@ impl<T> Compiler<T> {
         }
         Ok(())
     }
+
+    /// Get the value of a scalar specialization constant, widened to a [`ScalarValue`].
+    ///
+    /// Unlike [`Compiler::specialization_constant_value`], the value is read according to
+    /// the declared type of the constant. If the constant is not a scalar, returns `None`.
+    pub fn specialization_constant_scalar_value(
+        &self,
+        handle: Handle<ConstantId>,
+    ) -> error::Result<Option<ScalarValue>> {
+        let ty = self.type_description(self.specialization_constant_type(handle)?)?;
+        let TypeInner::Scalar(_) = ty.inner else {
+            return Ok(None);
+        };
+
+        let value = match self.constant_numeric(handle)?[0] {
+            ConstantNumeric::Bool(value) => ScalarValue::Bool(value),
+            ConstantNumeric::I8(value) => ScalarValue::Int(value.into()),
+            ConstantNumeric::I16(value) => ScalarValue::Int(value.into()),
+            ConstantNumeric::I32(value) => ScalarValue::Int(value.into()),
+            ConstantNumeric::I64(value) => ScalarValue::Int(value),
+            ConstantNumeric::U8(value) => ScalarValue::Uint(value.into()),
+            ConstantNumeric::U16(value) => ScalarValue::Uint(value.into()),
+            ConstantNumeric::U32(value) => ScalarValue::Uint(value.into()),
+            ConstantNumeric::U64(value) => ScalarValue::Uint(value),
+            ConstantNumeric::F16(value) | ConstantNumeric::F32(value) => {
+                ScalarValue::Float(value.into())
+            }
+            ConstantNumeric::F64(value) => ScalarValue::Float(value),
+        };
+
+        Ok(Some(value))
+    }
+
+    /// Get the value of a scalar, vector or matrix specialization constant, read according to
+    /// the declared type of the constant.
+    ///
+    /// Scalars return a single value. Vectors and matrices return every component,
+    /// in column-major order.
+    ///
+    /// If the constant is not a scalar, vector or matrix, returns
+    /// [`SpirvCrossError::InvalidArgument`].
+    pub fn constant_numeric(
+        &self,
+        handle: Handle<ConstantId>,
+    ) -> error::Result<Vec<ConstantNumeric>> {
+        let ty = self.type_description(self.specialization_constant_type(handle)?)?;
+        let (columns, rows, scalar) = match ty.inner {
+            TypeInner::Scalar(scalar) => (1, 1, scalar),
+            TypeInner::Vector { width, scalar } => (1, width, scalar),
+            TypeInner::Matrix {
+                columns,
+                rows,
+                scalar,
+            } => (columns, rows, scalar),
+            _ => {
+                return Err(SpirvCrossError::InvalidArgument(String::from(
+                    "The constant is not a scalar, vector or matrix",
+                )))
+            }
+        };
+
+        let constant = self.yield_id(handle)?;
+        let mut values = Vec::with_capacity((columns * rows) as usize);
+        unsafe {
+            // SAFETY: yield_id ensures safety.
+            let constant = sys::spvc_compiler_get_constant_handle(self.ptr.as_ptr(), constant);
+            Self::bounds_check_constant(constant, columns - 1, rows - 1)?;
+
+            for column in 0..columns {
+                for row in 0..rows {
+                    values.push(match (scalar.kind, scalar.size) {
+                        (ScalarKind::Bool, _) => {
+                            ConstantNumeric::Bool(bool::get(constant, column, row))
+                        }
+                        (ScalarKind::Int, BitWidth::Byte) => {
+                            ConstantNumeric::I8(i8::get(constant, column, row))
+                        }
+                        (ScalarKind::Int, BitWidth::HalfWord) => {
+                            ConstantNumeric::I16(i16::get(constant, column, row))
+                        }
+                        (ScalarKind::Int, BitWidth::DoubleWord) => {
+                            ConstantNumeric::I64(i64::get(constant, column, row))
+                        }
+                        (ScalarKind::Int, _) => {
+                            ConstantNumeric::I32(i32::get(constant, column, row))
+                        }
+                        (ScalarKind::Uint, BitWidth::Byte) => {
+                            ConstantNumeric::U8(u8::get(constant, column, row))
+                        }
+                        (ScalarKind::Uint, BitWidth::HalfWord) => {
+                            ConstantNumeric::U16(u16::get(constant, column, row))
+                        }
+                        (ScalarKind::Uint, BitWidth::DoubleWord) => {
+                            ConstantNumeric::U64(u64::get(constant, column, row))
+                        }
+                        (ScalarKind::Uint, _) => {
+                            ConstantNumeric::U32(u32::get(constant, column, row))
+                        }
+                        (ScalarKind::Float, BitWidth::HalfWord) => ConstantNumeric::F16(
+                            sys::spvc_constant_get_scalar_fp16(constant, column, row),
+                        ),
+                        (ScalarKind::Float, BitWidth::DoubleWord) => {
+                            ConstantNumeric::F64(f64::get(constant, column, row))
+                        }
+                        (ScalarKind::Float, _) => {
+                            ConstantNumeric::F32(f32::get(constant, column, row))
+                        }
+                    });
+                }
+            }
+        }
+
+        Ok(values)
+    }
+
+    /// Get the default values of all scalar specialization constants, keyed by `constant_id`.
+    ///
+    /// Composite specialization constants are skipped.
+    pub fn specialization_defaults(&self) -> error::Result<BTreeMap<u32, ScalarValue>> {
+        let mut defaults = BTreeMap::new();
+        for constant in self.specialization_constants()? {
+            if let Some(value) = self.specialization_constant_scalar_value(constant.id)? {
+                defaults.insert(constant.constant_id, value);
+            }
+        }
+
+        Ok(defaults)
+    }
 }
 
 pub(self) use impl_vec_constant;
\ No newline at end of file
@@ -1,6 +1,6 @@
 use crate::error::{SpirvCrossError, ToContextError};
-use crate::handle::{ConstantId, Handle, Id, TypeId, VariableId};
-use crate::reflect::StructMember;
+use crate::handle::{Handle, Id, TypeId, VariableId};
+use crate::reflect::{ResourceType, StructMember, TypeInner};
 use crate::sealed::Sealed;
 use crate::string::CompilerStr;
 use crate::Compiler;
@@ -10,6 +10,9 @@ use spirv_cross_sys as sys;
 use spirv_cross_sys::{SpvDecoration, SpvId};
 
 /// A value accompanying an `OpDecoration`
+///
+/// Values are compared structurally, strings are compared by their contents
+/// regardless of whether they are owned or borrowed from a compiler instance.
 #[derive(Debug, Eq, PartialEq)]
 pub enum DecorationValue<'a> {
     /// Returned by the following decorations.
@@ -26,13 +29,12 @@ pub enum DecorationValue<'a> {
     /// - [`ArrayStride`](Decoration::ArrayStride).
     /// - [`MatrixStride`](Decoration::MatrixStride).
     /// - [`Index`](Decoration::Index).
+    /// - [`SpecId`](Decoration::SpecId), the `constant_id` of the specialization constant.
     Literal(u32),
     /// Only for decoration [`BuiltIn`](Decoration::BuiltIn).
     BuiltIn(spirv::BuiltIn),
     /// Only for decoration [`FPRoundingMode`](Decoration::FPRoundingMode).
     RoundingMode(spirv::FPRoundingMode),
-    /// Only for decoration [`SpecId`](Decoration::SpecId).
-    Constant(Handle<ConstantId>),
     /// Only for decoration [`HlslSemanticGOOGLE`](Decoration::HlslSemanticGOOGLE) and [`UserTypeGOOGLE`](Decoration::HlslSemanticGOOGLE).
     String(CompilerStr<'a>),
     /// All other decorations to indicate the presence of a decoration.
@@ -53,6 +55,30 @@ impl DecorationValue<'_> {
             _ => None,
         }
     }
+
+    /// Get the value if it is a built-in.
+    pub fn as_builtin(&self) -> Option<spirv::BuiltIn> {
+        match self {
+            Self::BuiltIn(builtin) => Some(*builtin),
+            _ => None,
+        }
+    }
+
+    /// Get the value if it is a rounding mode.
+    pub fn as_rounding_mode(&self) -> Option<spirv::FPRoundingMode> {
+        match self {
+            Self::RoundingMode(rounding_mode) => Some(*rounding_mode),
+            _ => None,
+        }
+    }
+
+    /// Get the value if it is a string.
+    pub fn as_string(&self) -> Option<&str> {
+        match self {
+            Self::String(string) => Some(string.as_ref()),
+            _ => None,
+        }
+    }
 }
 
 impl From<u32> for DecorationValue<'_> {
@@ -67,12 +93,6 @@ impl From<()> for DecorationValue<'_> {
     }
 }
 
-impl From<Handle<ConstantId>> for DecorationValue<'_> {
-    fn from(value: Handle<ConstantId>) -> Self {
-        DecorationValue::Constant(value)
-    }
-}
-
 impl<'a> From<&'a str> for DecorationValue<'a> {
     fn from(value: &'a str) -> Self {
         DecorationValue::String(CompilerStr::from_str(value))
@@ -94,7 +114,7 @@ impl<'a> From<CompilerStr<'a>> for DecorationValue<'a> {
 impl Sealed for DecorationValue<'_> {}
 impl ToStatic for DecorationValue<'_> {
     type Static<'a>
-    = DecorationValue<'static>
+        = DecorationValue<'static>
     where
         'a: 'static;
 
@@ -103,7 +123,6 @@ impl ToStatic for DecorationValue<'_> {
             DecorationValue::Literal(a) => DecorationValue::Literal(*a),
             DecorationValue::BuiltIn(a) => DecorationValue::BuiltIn(*a),
             DecorationValue::RoundingMode(a) => DecorationValue::RoundingMode(*a),
-            DecorationValue::Constant(a) => DecorationValue::Constant(*a),
             DecorationValue::String(c) => {
                 let owned = c.to_string();
                 DecorationValue::String(CompilerStr::from_string(owned))
@@ -119,6 +138,61 @@ impl<'a> Clone for DecorationValue<'a> {
     }
 }
 
+/// A stage output captured by transform feedback,
+/// created by [`Compiler::transform_feedback_layout`].
+#[derive(Debug)]
+pub struct XfbVarying<'a> {
+    /// A handle to the output variable.
+    pub id: Handle<VariableId>,
+    /// The name of the output variable.
+    pub name: CompilerStr<'a>,
+    /// The transform feedback buffer the output is captured to.
+    ///
+    /// If the output has no `XfbBuffer` decoration, this is 0.
+    pub buffer: u32,
+    /// The stride of the transform feedback buffer.
+    ///
+    /// If the output has no `XfbStride` decoration, this is 0.
+    pub stride: u32,
+    /// The byte offset of the output in the transform feedback buffer.
+    pub offset: u32,
+    /// The vertex stream the output is emitted to.
+    ///
+    /// If the output has no `Stream` decoration, this is 0.
+    pub stream: u32,
+}
+
+impl Sealed for XfbVarying<'_> {}
+impl ToStatic for XfbVarying<'_> {
+    type Static<'a>
+        = XfbVarying<'static>
+    where
+        'a: 'static;
+
+    fn to_static(&self) -> Self::Static<'static> {
+        XfbVarying {
+            id: self.id,
+            name: CompilerStr::from_string(self.name.to_string()),
+            buffer: self.buffer,
+            stride: self.stride,
+            offset: self.offset,
+            stream: self.stream,
+        }
+    }
+}
+
+impl Clone for XfbVarying<'_> {
+    fn clone(&self) -> XfbVarying<'static> {
+        self.to_static()
+    }
+}
+
+impl<'a, 'b> From<&'a XfbVarying<'b>> for Handle<VariableId> {
+    fn from(value: &'a XfbVarying<'b>) -> Self {
+        value.id
+    }
+}
+
 impl DecorationValue<'_> {
     /// Check that the value is valid for the decoration type.
     pub fn type_is_valid_for_decoration(&self, decoration: spirv::Decoration) -> bool {
@@ -126,14 +200,12 @@ impl DecorationValue<'_> {
             DecorationValue::Literal(_) => decoration_is_literal(decoration),
             DecorationValue::BuiltIn(_) => decoration == Decoration::BuiltIn,
             DecorationValue::RoundingMode(_) => decoration == Decoration::FPRoundingMode,
-            DecorationValue::Constant(_) => decoration == Decoration::SpecId,
             DecorationValue::String(_) => decoration_is_string(decoration),
             DecorationValue::Present => {
                 !decoration_is_literal(decoration)
                     && !decoration_is_string(decoration)
                     && decoration != Decoration::BuiltIn
                     && decoration != Decoration::FPRoundingMode
-                    && decoration != Decoration::SpecId
             }
         }
     }
@@ -151,7 +223,8 @@ fn decoration_is_literal(decoration: spirv::Decoration) -> bool {
         | Decoration::InputAttachmentIndex
         | Decoration::ArrayStride
         | Decoration::MatrixStride
-        | Decoration::Index => true,
+        | Decoration::Index
+        | Decoration::SpecId => true,
         _ => false,
     }
 }
@@ -163,6 +236,21 @@ fn decoration_is_string(decoration: Decoration) -> bool {
     }
 }
 
+fn decoration_is_transferable(decoration: Decoration) -> bool {
+    !matches!(decoration, Decoration::BuiltIn | Decoration::SpecId)
+}
+
+/// Iterator over the decorations set on an ID, created by [`Compiler::decorations`].
+pub struct DecorationIter<'a>(std::vec::IntoIter<(Decoration, DecorationValue<'a>)>);
+
+impl<'a> Iterator for DecorationIter<'a> {
+    type Item = (Decoration, DecorationValue<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
 impl<T> Compiler<T> {
     /// Gets the value for decorations which take arguments.
     pub fn decoration<I: Id>(
@@ -175,6 +263,33 @@ impl<T> Compiler<T> {
 
         // SAFETY: id is yielded by the instance so it's safe to use.
         let id = SpvId(self.yield_id(id)?.id());
+        self.decoration_by_spv_id(id, decoration)
+    }
+
+    /// Gets every decoration set on an ID, with its value.
+    ///
+    /// Decorations are yielded in the order of their SPIR-V enumerant values.
+    /// Decorations that do not take arguments have the value [`DecorationValue::Present`].
+    pub fn decorations<I: Id>(&self, id: Handle<I>) -> error::Result<DecorationIter> {
+        // SAFETY: id is yielded by the instance so it's safe to use.
+        let id = SpvId(self.yield_id(id)?.id());
+
+        let mut decorations = Vec::new();
+        // Decoration enumerants, including those of extensions, all fit in 16 bits.
+        for decoration in (0..=u32::from(u16::MAX)).filter_map(Decoration::from_u32) {
+            if let Some(value) = self.decoration_by_spv_id(id, decoration)? {
+                decorations.push((decoration, value));
+            }
+        }
+
+        Ok(DecorationIter(decorations.into_iter()))
+    }
+
+    fn decoration_by_spv_id(
+        &self,
+        id: SpvId,
+        decoration: Decoration,
+    ) -> error::Result<Option<DecorationValue>> {
         unsafe {
             let has_decoration = sys::spvc_compiler_has_decoration(
                 self.ptr.as_ptr(),
@@ -272,7 +387,7 @@ impl<T> Compiler<T> {
         unsafe {
             let Some(value) = value else {
                 sys::spvc_compiler_unset_decoration(
-                    self.ptr.as_ptr(),
+                    self.ptr_mut(),
                     id,
                     SpvDecoration(decoration as u32 as i32),
                 );
@@ -291,7 +406,7 @@ impl<T> Compiler<T> {
             match value {
                 DecorationValue::Literal(literal) => {
                     sys::spvc_compiler_set_decoration(
-                        self.ptr.as_ptr(),
+                        self.ptr_mut(),
                         id,
                         SpvDecoration(decoration as u32 as i32),
                         literal,
@@ -299,7 +414,7 @@ impl<T> Compiler<T> {
                 }
                 DecorationValue::BuiltIn(builtin) => {
                     sys::spvc_compiler_set_decoration(
-                        self.ptr.as_ptr(),
+                        self.ptr_mut(),
                         id,
                         SpvDecoration(decoration as u32 as i32),
                         builtin as u32,
@@ -307,24 +422,15 @@ impl<T> Compiler<T> {
                 }
                 DecorationValue::RoundingMode(rounding_mode) => {
                     sys::spvc_compiler_set_decoration(
-                        self.ptr.as_ptr(),
+                        self.ptr_mut(),
                         id,
                         SpvDecoration(decoration as u32 as i32),
                         rounding_mode as u32,
                     );
                 }
-                DecorationValue::Constant(constant) => {
-                    let constant = self.yield_id(constant)?;
-                    sys::spvc_compiler_set_decoration(
-                        self.ptr.as_ptr(),
-                        id,
-                        SpvDecoration(decoration as u32 as i32),
-                        constant.id(),
-                    );
-                }
                 DecorationValue::Present => {
                     sys::spvc_compiler_set_decoration(
-                        self.ptr.as_ptr(),
+                        self.ptr_mut(),
                         id,
                         SpvDecoration(decoration as u32 as i32),
                         1,
@@ -342,7 +448,7 @@ impl<T> Compiler<T> {
                     })?;
 
                     sys::spvc_compiler_set_decoration_string(
-                        self.ptr.as_ptr(),
+                        self.ptr_mut(),
                         id,
                         SpvDecoration(decoration as u32 as i32),
                         cstring.as_ptr(),
@@ -358,6 +464,48 @@ impl<T> Compiler<T> {
         Ok(())
     }
 
+    /// Set the value of a decoration for an ID, only if it differs from the current value.
+    ///
+    /// Returns `true` if the decoration was changed, or `false` if the decoration already
+    /// had the given value. Passing `None` unsets the decoration, which is a change only
+    /// if the decoration was set.
+    pub fn set_decoration_if_changed<'value, I: Id>(
+        &mut self,
+        id: Handle<I>,
+        decoration: spirv::Decoration,
+        value: Option<impl Into<DecorationValue<'value>>>,
+    ) -> error::Result<bool> {
+        let value = value.map(Into::into);
+        if self.decoration(id, decoration)? == value {
+            return Ok(false);
+        }
+
+        self.set_decoration(id, decoration, value)?;
+        Ok(true)
+    }
+
+    /// Copy every decoration set on an ID onto another ID, with its value.
+    ///
+    /// Decorations already set on `to` are overwritten if they are also set on `from`,
+    /// and kept otherwise. `BuiltIn` is not copied, since it would turn `to` into a builtin,
+    /// and neither is `SpecId`, since the `constant_id` of a specialization constant must be unique.
+    pub fn copy_decorations<I: Id>(&mut self, from: Handle<I>, to: Handle<I>) -> error::Result<()> {
+        // Check `to` up front so an invalid handle does not leave it partially decorated.
+        self.yield_id(to)?;
+
+        let decorations: Vec<_> = self
+            .decorations(from)?
+            .filter(|(decoration, _)| decoration_is_transferable(*decoration))
+            .map(|(decoration, value)| (decoration, DecorationValue::to_static(&value)))
+            .collect();
+
+        for (decoration, value) in decorations {
+            self.set_decoration(to, decoration, Some(value))?;
+        }
+
+        Ok(())
+    }
+
     /// Set the value of a decoration for a struct member.
     pub fn set_member_decoration<'value>(
         &mut self,
@@ -388,7 +536,7 @@ impl<T> Compiler<T> {
         unsafe {
             let Some(value) = value else {
                 sys::spvc_compiler_unset_member_decoration(
-                    self.ptr.as_ptr(),
+                    self.ptr_mut(),
                     struct_type,
                     index,
                     SpvDecoration(decoration as u32 as i32),
@@ -408,7 +556,7 @@ impl<T> Compiler<T> {
             match value {
                 DecorationValue::Literal(literal) => {
                     sys::spvc_compiler_set_member_decoration(
-                        self.ptr.as_ptr(),
+                        self.ptr_mut(),
                         struct_type,
                         index,
                         SpvDecoration(decoration as u32 as i32),
@@ -417,7 +565,7 @@ impl<T> Compiler<T> {
                 }
                 DecorationValue::BuiltIn(builtin) => {
                     sys::spvc_compiler_set_member_decoration(
-                        self.ptr.as_ptr(),
+                        self.ptr_mut(),
                         struct_type,
                         index,
                         SpvDecoration(decoration as u32 as i32),
@@ -426,26 +574,16 @@ impl<T> Compiler<T> {
                 }
                 DecorationValue::RoundingMode(rounding_mode) => {
                     sys::spvc_compiler_set_member_decoration(
-                        self.ptr.as_ptr(),
+                        self.ptr_mut(),
                         struct_type,
                         index,
                         SpvDecoration(decoration as u32 as i32),
                         rounding_mode as u32,
                     );
                 }
-                DecorationValue::Constant(constant) => {
-                    let constant = self.yield_id(constant)?;
-                    sys::spvc_compiler_set_member_decoration(
-                        self.ptr.as_ptr(),
-                        struct_type,
-                        index,
-                        SpvDecoration(decoration as u32 as i32),
-                        constant.id(),
-                    );
-                }
                 DecorationValue::Present => {
                     sys::spvc_compiler_set_member_decoration(
-                        self.ptr.as_ptr(),
+                        self.ptr_mut(),
                         struct_type,
                         index,
                         SpvDecoration(decoration as u32 as i32),
@@ -464,7 +602,7 @@ impl<T> Compiler<T> {
                     })?;
 
                     sys::spvc_compiler_set_member_decoration_string(
-                        self.ptr.as_ptr(),
+                        self.ptr_mut(),
                         struct_type,
                         index,
                         SpvDecoration(decoration as u32 as i32),
@@ -481,6 +619,137 @@ impl<T> Compiler<T> {
         Ok(())
     }
 
+    /// Gets the `Component` decoration of a shader interface variable.
+    ///
+    /// Multiple variables can be packed into the same location with the `Component` decoration,
+    /// for example two `vec2` variables at components 0 and 2 of the same location.
+    ///
+    /// Returns `None` if the variable has no `Component` decoration, in which case it starts
+    /// at component 0.
+    pub fn interface_variable_component(
+        &self,
+        variable: impl Into<Handle<VariableId>>,
+    ) -> error::Result<Option<u32>> {
+        Ok(self
+            .decoration(variable.into(), Decoration::Component)?
+            .and_then(|component| component.as_literal()))
+    }
+
+    /// Get the stage outputs captured by transform feedback.
+    ///
+    /// An output is captured if it has an `Offset` decoration, i.e. `xfb_offset` in GLSL.
+    /// Outputs within blocks and built-in outputs are not included.
+    pub fn transform_feedback_layout(&self) -> error::Result<Vec<XfbVarying<'static>>> {
+        let literal =
+            |id: Handle<VariableId>, decoration: Decoration| -> error::Result<Option<u32>> {
+                Ok(self
+                    .decoration(id, decoration)?
+                    .and_then(|value| value.as_literal()))
+            };
+
+        let resources = self.shader_resources()?;
+        let mut varyings = Vec::new();
+        for output in resources.resources_for_type(ResourceType::StageOutput)? {
+            let Some(offset) = literal(output.id, Decoration::Offset)? else {
+                continue;
+            };
+
+            varyings.push(XfbVarying {
+                id: output.id,
+                name: output.name,
+                buffer: literal(output.id, Decoration::XfbBuffer)?.unwrap_or(0),
+                stride: literal(output.id, Decoration::XfbStride)?.unwrap_or(0),
+                offset,
+                stream: literal(output.id, Decoration::Stream)?.unwrap_or(0),
+            });
+        }
+
+        Ok(varyings)
+    }
+
+    /// Get the fragment outputs used for dual-source blending, with their
+    /// `Location` and `Index` decorations, sorted by location then index.
+    ///
+    /// Dual-source blending is enabled when any output has an `Index` decoration,
+    /// i.e. `layout(location = 0, index = 1)` in GLSL. Outputs without an `Index` decoration
+    /// use index 0. If no output has an `Index` decoration, or the entry point is not a
+    /// fragment shader, returns an empty list.
+    pub fn dual_source_outputs(&self) -> error::Result<Vec<(Handle<VariableId>, u32, u32)>> {
+        if self.execution_model()? != spirv::ExecutionModel::Fragment {
+            return Ok(Vec::new());
+        }
+
+        let resources = self.shader_resources()?;
+        let mut outputs = Vec::new();
+        let mut dual_source = false;
+        for output in resources.resources_for_type(ResourceType::StageOutput)? {
+            let Some(location) = self
+                .decoration(output.id, Decoration::Location)?
+                .and_then(|location| location.as_literal())
+            else {
+                continue;
+            };
+
+            let index = self
+                .decoration(output.id, Decoration::Index)?
+                .and_then(|index| index.as_literal());
+            dual_source |= index.is_some();
+
+            outputs.push((output.id, location, index.unwrap_or(0)));
+        }
+
+        if !dual_source {
+            return Ok(Vec::new());
+        }
+
+        outputs.sort_by_key(|&(_, location, index)| (location, index));
+        Ok(outputs)
+    }
+
+    /// Get the storage buffers and storage images decorated `Coherent`.
+    ///
+    /// These are the resources SPIRV-Cross declares `globallycoherent` when compiling to HLSL.
+    /// A storage buffer is coherent if the variable is decorated `Coherent`, or if every member
+    /// of its block is, which is how GLSL frontends decorate a `coherent` buffer block.
+    pub fn coherent_uav_resources(&self) -> error::Result<Vec<Handle<VariableId>>> {
+        let resources = self.shader_resources()?;
+        let mut coherent = Vec::new();
+
+        for buffer in resources.resources_for_type(ResourceType::StorageBuffer)? {
+            if self.decoration(buffer.id, Decoration::Coherent)?.is_some() {
+                coherent.push(buffer.id);
+                continue;
+            }
+
+            let TypeInner::Struct(block) = self.type_description(buffer.base_type_id)?.inner else {
+                continue;
+            };
+
+            let mut members_coherent = !block.members.is_empty();
+            for member in &block.members {
+                if self
+                    .member_decoration(member, Decoration::Coherent)?
+                    .is_none()
+                {
+                    members_coherent = false;
+                    break;
+                }
+            }
+
+            if members_coherent {
+                coherent.push(buffer.id);
+            }
+        }
+
+        for image in resources.resources_for_type(ResourceType::StorageImage)? {
+            if self.decoration(image.id, Decoration::Coherent)?.is_some() {
+                coherent.push(image.id);
+            }
+        }
+
+        Ok(coherent)
+    }
+
     /// Gets the offset in SPIR-V words (uint32_t) for a decoration which was originally declared in the SPIR-V binary.
     /// The offset will point to one or more uint32_t literals which can be modified in-place before using the SPIR-V binary.
     ///
@@ -539,11 +808,6 @@ impl<T> Compiler<T> {
                 };
                 Ok(Some(DecorationValue::RoundingMode(rounding_mode)))
             }
-            Decoration::SpecId => unsafe {
-                Ok(Some(DecorationValue::Constant(
-                    self.create_handle(ConstantId(SpvId(value))),
-                )))
-            },
             _ => {
                 if value == 1 {
                     Ok(Some(DecorationValue::Present))
@@ -591,6 +855,8 @@ impl<T> Compiler<T> {
 #[cfg(test)]
 mod test {
     use crate::error::SpirvCrossError;
+    use crate::reflect::DecorationValue;
+    use crate::string::CompilerStr;
     use crate::Compiler;
 
     use crate::{targets, Module};
@@ -608,4 +874,167 @@ mod test {
         // compiler.set_decoration(Decoration::HlslSemanticGOOGLE, DecorationValue::String(Cow::Borrowed("hello")));
         Ok(())
     }
+
+    #[test]
+    pub fn original_words_unchanged() -> Result<(), SpirvCrossError> {
+        let vec = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&vec));
+
+        let mut compiler: Compiler<targets::None> = Compiler::new(words)?;
+        let resources = compiler.shader_resources()?.all_resources()?;
+
+        compiler.set_decoration(
+            resources.uniform_buffers[0].id,
+            spirv::Decoration::Binding,
+            Some(5u32),
+        )?;
+
+        assert_eq!(
+            bytemuck::cast_slice::<u8, u32>(&vec),
+            compiler.original_words()
+        );
+        Ok(())
+    }
+
+    #[test]
+    pub fn set_decoration_if_changed() -> Result<(), SpirvCrossError> {
+        let vec = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&vec));
+
+        let mut compiler: Compiler<targets::None> = Compiler::new(words)?;
+        let resources = compiler.shader_resources()?.all_resources()?;
+        let tex = resources.sampled_images[0].id;
+
+        assert!(!compiler.set_decoration_if_changed(
+            tex,
+            spirv::Decoration::Binding,
+            Some(1u32)
+        )?);
+        assert!(compiler.set_decoration_if_changed(tex, spirv::Decoration::Binding, Some(2u32))?);
+        assert_eq!(
+            Some(2),
+            compiler
+                .decoration(tex, spirv::Decoration::Binding)?
+                .and_then(|b| b.as_literal())
+        );
+
+        assert!(!compiler.set_decoration_if_changed(tex, spirv::Decoration::Flat, None::<()>)?);
+        assert!(compiler.set_decoration_if_changed(tex, spirv::Decoration::Flat, Some(()))?);
+        assert!(!compiler.set_decoration_if_changed(tex, spirv::Decoration::Flat, Some(()))?);
+        Ok(())
+    }
+
+    #[test]
+    pub fn decoration_value_eq() {
+        assert_eq!(DecorationValue::Literal(1), DecorationValue::Literal(1));
+        assert_ne!(DecorationValue::Literal(1), DecorationValue::Literal(2));
+
+        assert_eq!(
+            DecorationValue::BuiltIn(spirv::BuiltIn::Position),
+            DecorationValue::BuiltIn(spirv::BuiltIn::Position)
+        );
+        assert_ne!(
+            DecorationValue::BuiltIn(spirv::BuiltIn::Position),
+            DecorationValue::BuiltIn(spirv::BuiltIn::PointSize)
+        );
+
+        assert_eq!(
+            DecorationValue::RoundingMode(spirv::FPRoundingMode::RTE),
+            DecorationValue::RoundingMode(spirv::FPRoundingMode::RTE)
+        );
+        assert_ne!(
+            DecorationValue::RoundingMode(spirv::FPRoundingMode::RTE),
+            DecorationValue::RoundingMode(spirv::FPRoundingMode::RTZ)
+        );
+
+        assert_eq!(
+            DecorationValue::from("semantic"),
+            DecorationValue::String(CompilerStr::from_string(String::from("semantic")))
+        );
+        assert_ne!(
+            DecorationValue::from("semantic"),
+            DecorationValue::from("other")
+        );
+
+        assert_eq!(DecorationValue::Present, DecorationValue::Present);
+        assert_ne!(DecorationValue::Present, DecorationValue::Literal(1));
+        assert_ne!(DecorationValue::Literal(0), DecorationValue::from("0"));
+    }
+
+    #[test]
+    pub fn decorations() -> Result<(), SpirvCrossError> {
+        let vec = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&vec));
+
+        let compiler: Compiler<targets::None> = Compiler::new(words)?;
+        let resources = compiler.shader_resources()?.all_resources()?;
+
+        let decorations: Vec<_> = compiler
+            .decorations(resources.uniform_buffers[0].id)?
+            .collect();
+
+        assert_eq!(
+            vec![
+                (spirv::Decoration::Binding, DecorationValue::Literal(0)),
+                (
+                    spirv::Decoration::DescriptorSet,
+                    DecorationValue::Literal(0)
+                ),
+            ],
+            decorations
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn copy_decorations() -> Result<(), SpirvCrossError> {
+        let vec = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&vec));
+
+        let mut compiler: Compiler<targets::None> = Compiler::new(words)?;
+        let resources = compiler.shader_resources()?.all_resources()?;
+        let ubo = resources.uniform_buffers[0].id;
+        let tex = resources.sampled_images[0].id;
+
+        compiler.set_decoration(ubo, spirv::Decoration::Binding, Some(3u32))?;
+        compiler.set_decoration(ubo, spirv::Decoration::Flat, Some(()))?;
+        compiler.set_decoration(
+            ubo,
+            spirv::Decoration::BuiltIn,
+            Some(DecorationValue::BuiltIn(spirv::BuiltIn::Position)),
+        )?;
+        compiler.copy_decorations(ubo, tex)?;
+
+        assert_eq!(
+            Some(DecorationValue::Literal(3)),
+            compiler.decoration(tex, spirv::Decoration::Binding)?
+        );
+        assert_eq!(
+            Some(DecorationValue::Present),
+            compiler.decoration(tex, spirv::Decoration::Flat)?
+        );
+        assert_eq!(None, compiler.decoration(tex, spirv::Decoration::BuiltIn)?);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn decoration_value_accessors() {
+        let builtin = DecorationValue::BuiltIn(spirv::BuiltIn::Position);
+        assert_eq!(Some(spirv::BuiltIn::Position), builtin.as_builtin());
+        assert_eq!(None, builtin.as_literal());
+
+        let rounding_mode = DecorationValue::RoundingMode(spirv::FPRoundingMode::RTE);
+        assert_eq!(
+            Some(spirv::FPRoundingMode::RTE),
+            rounding_mode.as_rounding_mode()
+        );
+        assert_eq!(None, rounding_mode.as_builtin());
+
+        let string = DecorationValue::from("semantic");
+        assert_eq!(Some("semantic"), string.as_string());
+        assert_eq!(None, DecorationValue::Literal(1).as_string());
+        assert_eq!(None, DecorationValue::Present.as_string());
+    }
 }
@@ -0,0 +1,490 @@
+use crate::error;
+use crate::error::SpirvCrossError;
+use crate::reflect::entry_points::decode_literal_string;
+use crate::Compiler;
+use spirv::Op;
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+/// The kind of an instruction operand, which determines how it is printed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Operand {
+    Id,
+    Literal,
+    String,
+    Capability,
+    ExecutionModel,
+    ExecutionMode,
+    AddressingModel,
+    MemoryModel,
+    SourceLanguage,
+    StorageClass,
+    Decoration,
+    BuiltIn,
+    Dim,
+    ImageFormat,
+}
+
+/// A scalar type declared with `OpTypeInt` or `OpTypeFloat`, used to print constants.
+#[derive(Debug, Copy, Clone)]
+struct ScalarLayout {
+    float: bool,
+    signed: bool,
+    width: u32,
+}
+
+/// Returns whether an instruction has a result type, and whether it has a result ID.
+fn result_layout(op: Op) -> (bool, bool) {
+    match op {
+        Op::String | Op::ExtInstImport | Op::Label | Op::DecorationGroup => (false, true),
+        // Type declarations have a result ID but no result type.
+        Op::TypeVoid
+        | Op::TypeBool
+        | Op::TypeInt
+        | Op::TypeFloat
+        | Op::TypeVector
+        | Op::TypeMatrix
+        | Op::TypeImage
+        | Op::TypeSampler
+        | Op::TypeSampledImage
+        | Op::TypeArray
+        | Op::TypeRuntimeArray
+        | Op::TypeStruct
+        | Op::TypeOpaque
+        | Op::TypePointer
+        | Op::TypeFunction
+        | Op::TypeEvent
+        | Op::TypeDeviceEvent
+        | Op::TypeReserveId
+        | Op::TypeQueue
+        | Op::TypePipe
+        | Op::TypePipeStorage
+        | Op::TypeNamedBarrier
+        | Op::TypeCooperativeMatrixKHR
+        | Op::TypeRayQueryKHR
+        | Op::TypeHitObjectNV
+        | Op::TypeAccelerationStructureKHR
+        | Op::TypeCooperativeMatrixNV
+        | Op::TypeVmeImageINTEL
+        | Op::TypeAvcImePayloadINTEL
+        | Op::TypeAvcRefPayloadINTEL
+        | Op::TypeAvcSicPayloadINTEL
+        | Op::TypeAvcMcePayloadINTEL
+        | Op::TypeAvcMceResultINTEL
+        | Op::TypeAvcImeResultINTEL
+        | Op::TypeAvcImeResultSingleReferenceStreamoutINTEL
+        | Op::TypeAvcImeResultDualReferenceStreamoutINTEL
+        | Op::TypeAvcImeSingleReferenceStreaminINTEL
+        | Op::TypeAvcImeDualReferenceStreaminINTEL
+        | Op::TypeAvcRefResultINTEL
+        | Op::TypeAvcSicResultINTEL
+        | Op::TypeBufferSurfaceINTEL => (false, true),
+        Op::TypeForwardPointer | Op::TypeStructContinuedINTEL => (false, false),
+        Op::Nop
+        | Op::SourceContinued
+        | Op::Source
+        | Op::SourceExtension
+        | Op::Name
+        | Op::MemberName
+        | Op::Line
+        | Op::NoLine
+        | Op::ModuleProcessed
+        | Op::Extension
+        | Op::MemoryModel
+        | Op::EntryPoint
+        | Op::ExecutionMode
+        | Op::ExecutionModeId
+        | Op::Capability
+        | Op::Decorate
+        | Op::MemberDecorate
+        | Op::DecorateId
+        | Op::DecorateString
+        | Op::MemberDecorateString
+        | Op::GroupDecorate
+        | Op::GroupMemberDecorate
+        | Op::FunctionEnd
+        | Op::Store
+        | Op::CopyMemory
+        | Op::CopyMemorySized
+        | Op::ImageWrite
+        | Op::EmitVertex
+        | Op::EndPrimitive
+        | Op::EmitStreamVertex
+        | Op::EndStreamPrimitive
+        | Op::ControlBarrier
+        | Op::MemoryBarrier
+        | Op::AtomicStore
+        | Op::LoopMerge
+        | Op::SelectionMerge
+        | Op::Branch
+        | Op::BranchConditional
+        | Op::Switch
+        | Op::Kill
+        | Op::Return
+        | Op::ReturnValue
+        | Op::Unreachable
+        | Op::LifetimeStart
+        | Op::LifetimeStop
+        | Op::TerminateInvocation
+        | Op::DemoteToHelperInvocation
+        | Op::IgnoreIntersectionKHR
+        | Op::TerminateRayKHR
+        | Op::TraceRayKHR
+        | Op::ExecuteCallableKHR
+        | Op::EmitMeshTasksEXT
+        | Op::SetMeshOutputsEXT
+        | Op::BeginInvocationInterlockEXT
+        | Op::EndInvocationInterlockEXT
+        | Op::RayQueryInitializeKHR
+        | Op::RayQueryTerminateKHR
+        | Op::RayQueryGenerateIntersectionKHR
+        | Op::RayQueryConfirmIntersectionKHR => (false, false),
+        _ => (true, true),
+    }
+}
+
+/// Returns the kinds of the operands following the result type and result ID of an instruction,
+/// and the kind of every operand past those.
+fn operand_layout(op: Op) -> (&'static [Operand], Operand) {
+    use Operand::*;
+    match op {
+        Op::Capability => (&[Capability], Literal),
+        Op::Extension
+        | Op::SourceExtension
+        | Op::ModuleProcessed
+        | Op::ExtInstImport
+        | Op::String
+        | Op::SourceContinued => (&[String], Literal),
+        Op::MemoryModel => (&[AddressingModel, MemoryModel], Literal),
+        Op::EntryPoint => (&[ExecutionModel, Id, String], Id),
+        Op::ExecutionMode => (&[Id, ExecutionMode], Literal),
+        Op::ExecutionModeId => (&[Id, ExecutionMode], Id),
+        Op::Source => (&[SourceLanguage, Literal, Id, String], Literal),
+        Op::Name => (&[Id, String], Literal),
+        Op::MemberName => (&[Id, Literal, String], Literal),
+        Op::Line => (&[Id], Literal),
+        Op::Decorate | Op::DecorateId | Op::DecorateString => (&[Id, Decoration], Literal),
+        Op::MemberDecorate | Op::MemberDecorateString => (&[Id, Literal, Decoration], Literal),
+        Op::TypeInt | Op::TypeFloat | Op::Constant | Op::SpecConstant => (&[], Literal),
+        Op::TypeVector | Op::TypeMatrix => (&[Id], Literal),
+        Op::TypeImage => (
+            &[Id, Dim, Literal, Literal, Literal, Literal, ImageFormat],
+            Literal,
+        ),
+        Op::TypePointer | Op::Variable => (&[StorageClass], Id),
+        Op::TypeForwardPointer => (&[Id, StorageClass], Literal),
+        Op::Function => (&[Literal, Id], Literal),
+        Op::SpecConstantOp => (&[Literal], Id),
+        Op::ExtInst => (&[Id, Literal], Id),
+        Op::Load | Op::CompositeExtract | Op::SelectionMerge => (&[Id], Literal),
+        Op::Store | Op::CompositeInsert | Op::VectorShuffle | Op::LoopMerge => (&[Id, Id], Literal),
+        Op::BranchConditional => (&[Id, Id, Id], Literal),
+        // The selector and default label, followed by pairs of a literal and a label.
+        Op::Switch => (&[Id, Id], Id),
+        Op::ImageSampleImplicitLod
+        | Op::ImageSampleExplicitLod
+        | Op::ImageSampleProjImplicitLod
+        | Op::ImageSampleProjExplicitLod
+        | Op::ImageFetch
+        | Op::ImageRead => (&[Id, Id, Literal], Id),
+        Op::ImageSampleDrefImplicitLod
+        | Op::ImageSampleDrefExplicitLod
+        | Op::ImageSampleProjDrefImplicitLod
+        | Op::ImageSampleProjDrefExplicitLod
+        | Op::ImageGather
+        | Op::ImageDrefGather
+        | Op::ImageWrite => (&[Id, Id, Id, Literal], Id),
+        _ => (&[], Id),
+    }
+}
+
+/// Print an enumerant by name, or by value if it is unknown.
+fn enumerant<E: Debug>(value: Option<E>, word: u32) -> String {
+    value.map_or_else(|| word.to_string(), |value| format!("{value:?}"))
+}
+
+/// Print the value of an `OpConstant` of a scalar type.
+fn scalar_value(scalar: ScalarLayout, words: &[u32]) -> Option<String> {
+    let low = *words.first()?;
+    let wide = || Some(u64::from(low) | u64::from(*words.get(1)?) << 32);
+
+    Some(match (scalar.float, scalar.signed, scalar.width) {
+        (true, _, 32) => format!("{:?}", f32::from_bits(low)),
+        (true, _, 64) => format!("{:?}", f64::from_bits(wide()?)),
+        (false, true, 64) => (wide()? as i64).to_string(),
+        (false, false, 64) => wide()?.to_string(),
+        (false, true, width @ 1..=32) => {
+            // Narrow signed integers are sign-extended into the low-order bits.
+            let shift = 32 - width;
+            (((low << shift) as i32) >> shift).to_string()
+        }
+        (false, false, _) => low.to_string(),
+        _ => return None,
+    })
+}
+
+/// Disassembly of the SPIR-V module.
+impl<T> Compiler<T> {
+    /// Disassemble the SPIR-V module this compiler was created with into SPIR-V assembly text,
+    /// in a format similar to `spirv-dis`.
+    ///
+    /// Every instruction of [`Compiler::original_words`] is printed with its result ID,
+    /// so IDs can be cross-referenced with [`Handle::id`](crate::handle::Handle::id).
+    /// Changes made through the compiler, such as new decorations, are not reflected.
+    ///
+    /// This is a debugging aid, and is not meant to be reassembled. Enumerants of
+    /// module-level instructions, such as capabilities, storage classes and decorations,
+    /// are printed by name, and scalar constants are printed by value. Other operands are
+    /// printed as IDs unless the instruction is known to take a literal, for example the
+    /// indices of `OpCompositeExtract` or the memory operands of `OpLoad`.
+    pub fn disassemble(&self) -> error::Result<String> {
+        let words = self.original_words();
+        let Some(&[_magic, version, generator, bound, schema]) = words.get(..5) else {
+            return Err(SpirvCrossError::InvalidSpirv(String::from(
+                "Module header is out of bounds.",
+            )));
+        };
+
+        let mut out = format!(
+            "; SPIR-V\n; Version: {}.{}\n; Generator: {generator:#010x}\n; Bound: {bound}\n; Schema: {schema}\n",
+            (version >> 16) & 0xff,
+            (version >> 8) & 0xff,
+        );
+
+        let mut scalars = HashMap::new();
+        let mut instructions = &words[5..];
+        while let Some(&first) = instructions.first() {
+            let word_count = (first >> 16) as usize;
+            if word_count == 0 || word_count > instructions.len() {
+                return Err(SpirvCrossError::InvalidSpirv(String::from(
+                    "Instruction word count is out of bounds.",
+                )));
+            }
+
+            let mut operands = &instructions[1..word_count];
+            instructions = &instructions[word_count..];
+
+            let Some(op) = Op::from_u32(first & 0xffff) else {
+                out.push_str(&format!("{:15}Op{}", "", first & 0xffff));
+                for word in operands {
+                    out.push_str(&format!(" {word}"));
+                }
+                out.push('\n');
+                continue;
+            };
+
+            let (has_result_type, has_result) = result_layout(op);
+            let mut result_type = None;
+            let mut result = None;
+            if has_result_type {
+                if let Some((&id, rest)) = operands.split_first() {
+                    result_type = Some(id);
+                    operands = rest;
+                }
+            }
+
+            if has_result {
+                if let Some((&id, rest)) = operands.split_first() {
+                    result = Some(id);
+                    operands = rest;
+                }
+            }
+
+            match result {
+                Some(result) => {
+                    let result = format!("%{result}");
+                    out.push_str(&format!("{result:>12} = "));
+                }
+                None => out.push_str(&format!("{:15}", "")),
+            }
+
+            out.push_str(&format!("Op{op:?}"));
+            if let Some(result_type) = result_type {
+                out.push_str(&format!(" %{result_type}"));
+            }
+
+            if let (Some(result), Op::TypeInt | Op::TypeFloat) = (result, op) {
+                if let Some(&width) = operands.first() {
+                    scalars.insert(
+                        result,
+                        ScalarLayout {
+                            float: op == Op::TypeFloat,
+                            signed: op == Op::TypeInt && operands.get(1) == Some(&1),
+                            width,
+                        },
+                    );
+                }
+            }
+
+            if matches!(op, Op::Constant | Op::SpecConstant) {
+                if let Some(value) = result_type
+                    .and_then(|ty| scalars.get(&ty))
+                    .and_then(|&scalar| scalar_value(scalar, operands))
+                {
+                    out.push_str(&format!(" {value}\n"));
+                    continue;
+                }
+            }
+
+            let (fixed, mut rest) = operand_layout(op);
+            let mut index = 0;
+            let mut count = 0;
+            while let Some(&word) = operands.get(index) {
+                let kind = match fixed.get(count) {
+                    Some(&kind) => kind,
+                    // The targets of OpSwitch are pairs of a literal and a label.
+                    None if op == Op::Switch && (count - fixed.len()) % 2 == 0 => Operand::Literal,
+                    None => rest,
+                };
+
+                count += 1;
+                index += 1;
+
+                let operand = match kind {
+                    Operand::Id => format!("%{word}"),
+                    Operand::Literal => word.to_string(),
+                    Operand::String => {
+                        // Strings are nul-terminated and padded to a whole number of words.
+                        let remaining = &operands[index - 1..];
+                        let len = remaining
+                            .iter()
+                            .position(|word| word.to_le_bytes().contains(&0))
+                            .map_or(remaining.len(), |end| end + 1);
+                        index += len - 1;
+
+                        // A trailing string is followed by literals, such as the linkage type
+                        // of LinkageAttributes.
+                        if rest == Operand::String {
+                            rest = Operand::Literal;
+                        }
+
+                        format!("{:?}", decode_literal_string(&remaining[..len])?)
+                    }
+                    Operand::Capability => enumerant(spirv::Capability::from_u32(word), word),
+                    Operand::ExecutionModel => {
+                        enumerant(spirv::ExecutionModel::from_u32(word), word)
+                    }
+                    Operand::ExecutionMode => enumerant(spirv::ExecutionMode::from_u32(word), word),
+                    Operand::AddressingModel => {
+                        enumerant(spirv::AddressingModel::from_u32(word), word)
+                    }
+                    Operand::MemoryModel => enumerant(spirv::MemoryModel::from_u32(word), word),
+                    Operand::SourceLanguage => {
+                        enumerant(spirv::SourceLanguage::from_u32(word), word)
+                    }
+                    Operand::StorageClass => enumerant(spirv::StorageClass::from_u32(word), word),
+                    Operand::Decoration => {
+                        let decoration = spirv::Decoration::from_u32(word);
+                        rest = match decoration {
+                            Some(spirv::Decoration::BuiltIn) => Operand::BuiltIn,
+                            Some(
+                                spirv::Decoration::AlignmentId
+                                | spirv::Decoration::MaxByteOffsetId
+                                | spirv::Decoration::CounterBuffer,
+                            ) => Operand::Id,
+                            Some(
+                                spirv::Decoration::LinkageAttributes
+                                | spirv::Decoration::UserSemantic
+                                | spirv::Decoration::UserTypeGOOGLE,
+                            ) => Operand::String,
+                            _ => Operand::Literal,
+                        };
+                        enumerant(decoration, word)
+                    }
+                    Operand::BuiltIn => enumerant(spirv::BuiltIn::from_u32(word), word),
+                    Operand::Dim => enumerant(spirv::Dim::from_u32(word), word),
+                    Operand::ImageFormat => enumerant(spirv::ImageFormat::from_u32(word), word),
+                };
+
+                out.push(' ');
+                out.push_str(&operand);
+            }
+
+            out.push('\n');
+        }
+
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::SpirvCrossError;
+    use crate::Compiler;
+    use crate::{targets, Module};
+
+    static BASIC_SPV: &[u8] = include_bytes!("../../basic.spv");
+
+    #[test]
+    pub fn disassemble() -> Result<(), SpirvCrossError> {
+        let vec = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&vec));
+
+        let compiler: Compiler<targets::None> = Compiler::new(words)?;
+        let resources = compiler.shader_resources()?.all_resources()?;
+        let ubo = resources.uniform_buffers[0].id.id();
+
+        let disassembly = compiler.disassemble()?;
+        eprintln!("{disassembly}");
+
+        assert!(disassembly.starts_with("; SPIR-V\n"));
+        assert!(disassembly.contains("OpCapability Shader\n"));
+        assert!(disassembly.contains("OpEntryPoint Fragment %"));
+        assert!(disassembly.contains(" \"main\""));
+        assert!(disassembly.contains(&format!("OpDecorate %{ubo} Binding 0\n")));
+        assert!(disassembly.contains("OpTypeFloat 32\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn disassemble_switch() -> Result<(), SpirvCrossError> {
+        // A compute shader containing `switch (0) { case 1: break; }`.
+        #[rustfmt::skip]
+        let words: [u32; 57] = [
+            0x07230203, 0x00010000, 0, 9, 0,
+            // OpCapability Shader
+            0x00020011, 1,
+            // OpMemoryModel Logical GLSL450
+            0x0003000E, 0, 1,
+            // OpEntryPoint GLCompute %5 "main"
+            0x0005000F, 5, 5, 0x6E69616D, 0,
+            // OpExecutionMode %5 LocalSize 1 1 1
+            0x00060010, 5, 17, 1, 1, 1,
+            // %1 = OpTypeVoid
+            0x00020013, 1,
+            // %2 = OpTypeFunction %1
+            0x00030021, 2, 1,
+            // %3 = OpTypeInt 32 1
+            0x00040015, 3, 32, 1,
+            // %4 = OpConstant %3 0
+            0x0004002B, 3, 4, 0,
+            // %5 = OpFunction %1 None %2
+            0x00050036, 1, 5, 0, 2,
+            // %6 = OpLabel
+            0x000200F8, 6,
+            // OpSelectionMerge %8 None
+            0x000300F7, 8, 0,
+            // OpSwitch %4 %8 1 %7
+            0x000500FB, 4, 8, 1, 7,
+            // %7 = OpLabel
+            0x000200F8, 7,
+            // OpBranch %8
+            0x000200F9, 8,
+            // %8 = OpLabel
+            0x000200F8, 8,
+            // OpReturn
+            0x000100FD,
+            // OpFunctionEnd
+            0x00010038,
+        ];
+
+        let compiler: Compiler<targets::None> = Compiler::new(Module::from_words(&words))?;
+        let disassembly = compiler.disassemble()?;
+
+        assert!(disassembly.contains("%3 = OpTypeInt 32 1\n"));
+        assert!(disassembly.contains("OpSwitch %4 %8 1 %7\n"));
+
+        Ok(())
+    }
+}
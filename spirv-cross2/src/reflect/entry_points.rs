@@ -3,12 +3,14 @@ use crate::error;
 use crate::error::{SpirvCrossError, ToContextError};
 use crate::handle::Handle;
 use crate::iter::impl_iterator;
-use crate::reflect::try_valid_slice;
+use crate::reflect::{try_valid_slice, Instructions};
 use crate::string::CompilerStr;
 use crate::Compiler;
 use core::slice;
 use spirv_cross_sys as sys;
-use spirv_cross_sys::{spvc_entry_point, SpvBuiltIn, SpvExecutionModel, SpvStorageClass};
+use spirv_cross_sys::{
+    spvc_entry_point, SpvBuiltIn, SpvExecutionModel, SpvId, SpvStorageClass, VariableId,
+};
 use std::ffi::c_char;
 
 /// Iterator for declared extensions, created by [`Compiler::declared_extensions`].
@@ -20,6 +22,66 @@ impl_iterator!(ExtensionsIter<'c>: CompilerStr<'c> as map |s, ptr: &*const c_cha
     }
 } for <'c> [0]);
 
+/// SPIR-V capabilities and the minimum Vulkan version where they are available,
+/// as a core feature or through an extension that requires that version.
+const CAPABILITY_VULKAN_VERSIONS: &[(spirv::Capability, (u32, u32))] = &[
+    (spirv::Capability::GroupNonUniform, (1, 1)),
+    (spirv::Capability::GroupNonUniformVote, (1, 1)),
+    (spirv::Capability::GroupNonUniformArithmetic, (1, 1)),
+    (spirv::Capability::GroupNonUniformBallot, (1, 1)),
+    (spirv::Capability::GroupNonUniformShuffle, (1, 1)),
+    (spirv::Capability::GroupNonUniformShuffleRelative, (1, 1)),
+    (spirv::Capability::GroupNonUniformClustered, (1, 1)),
+    (spirv::Capability::GroupNonUniformQuad, (1, 1)),
+    (spirv::Capability::DrawParameters, (1, 1)),
+    (spirv::Capability::MultiView, (1, 1)),
+    (spirv::Capability::DeviceGroup, (1, 1)),
+    (spirv::Capability::VariablePointers, (1, 1)),
+    (spirv::Capability::VariablePointersStorageBuffer, (1, 1)),
+    (spirv::Capability::StorageBuffer16BitAccess, (1, 1)),
+    (
+        spirv::Capability::UniformAndStorageBuffer16BitAccess,
+        (1, 1),
+    ),
+    (spirv::Capability::StoragePushConstant16, (1, 1)),
+    (spirv::Capability::StorageInputOutput16, (1, 1)),
+    (spirv::Capability::Float16, (1, 2)),
+    (spirv::Capability::Int8, (1, 2)),
+    (spirv::Capability::StorageBuffer8BitAccess, (1, 2)),
+    (spirv::Capability::UniformAndStorageBuffer8BitAccess, (1, 2)),
+    (spirv::Capability::StoragePushConstant8, (1, 2)),
+    (spirv::Capability::ShaderNonUniform, (1, 2)),
+    (spirv::Capability::RuntimeDescriptorArray, (1, 2)),
+    (spirv::Capability::PhysicalStorageBufferAddresses, (1, 2)),
+    (spirv::Capability::VulkanMemoryModel, (1, 2)),
+    (spirv::Capability::VulkanMemoryModelDeviceScope, (1, 2)),
+    (spirv::Capability::ShaderViewportIndex, (1, 2)),
+    (spirv::Capability::ShaderLayer, (1, 2)),
+    (spirv::Capability::DenormPreserve, (1, 2)),
+    (spirv::Capability::DenormFlushToZero, (1, 2)),
+    (spirv::Capability::SignedZeroInfNanPreserve, (1, 2)),
+    (spirv::Capability::RoundingModeRTE, (1, 2)),
+    (spirv::Capability::RoundingModeRTZ, (1, 2)),
+    // Ray tracing and mesh shading require SPIR-V 1.4.
+    (spirv::Capability::RayTracingKHR, (1, 2)),
+    (spirv::Capability::RayQueryKHR, (1, 2)),
+    (spirv::Capability::MeshShadingEXT, (1, 2)),
+    (spirv::Capability::DemoteToHelperInvocation, (1, 3)),
+    (spirv::Capability::DotProduct, (1, 3)),
+    (spirv::Capability::DotProductInputAll, (1, 3)),
+    (spirv::Capability::DotProductInput4x8Bit, (1, 3)),
+    (spirv::Capability::DotProductInput4x8BitPacked, (1, 3)),
+];
+
+/// Decode a nul-terminated SPIR-V literal string.
+pub(super) fn decode_literal_string(words: &[u32]) -> error::Result<String> {
+    let bytes: Vec<u8> = words.iter().flat_map(|word| word.to_le_bytes()).collect();
+    let bytes = bytes.split(|&b| b == 0).next().unwrap_or_default();
+    String::from_utf8(bytes.to_vec()).map_err(|_| {
+        SpirvCrossError::InvalidSpirv(String::from("Literal string is not valid UTF-8."))
+    })
+}
+
 /// Querying declared properties of the SPIR-V module.
 impl<T> Compiler<T> {
     /// Gets the list of all SPIR-V Capabilities which were declared in the SPIR-V module.
@@ -54,6 +116,160 @@ impl<T> Compiler<T> {
         }
     }
 
+    /// Gets the list of source language extensions declared with `OpSourceExtension`,
+    /// such as `GL_GOOGLE_include_directive`.
+    ///
+    /// These are the extensions that were enabled in the original source, which are distinct
+    /// from the SPIR-V extensions returned by [`Compiler::declared_extensions`].
+    /// SPIRV-Cross does not track source extensions, so they are found by scanning
+    /// [`Compiler::original_words`].
+    pub fn source_extensions(&self) -> error::Result<Vec<String>> {
+        let mut extensions = Vec::new();
+
+        for instruction in Instructions::new(self.original_words()) {
+            let (op, operands) = instruction?;
+            if op == Some(spirv::Op::SourceExtension) {
+                extensions.push(decode_literal_string(operands)?);
+            }
+        }
+
+        Ok(extensions)
+    }
+
+    /// Returns whether the module uses buffer device addresses, i.e. whether
+    /// any pointer may be in the `PhysicalStorageBuffer` storage class.
+    ///
+    /// This is derived from the `PhysicalStorageBufferAddresses` capability,
+    /// which must be declared for the storage class to be used.
+    pub fn uses_buffer_device_address(&self) -> error::Result<bool> {
+        Ok(self
+            .declared_capabilities()?
+            .contains(&spirv::Capability::PhysicalStorageBufferAddresses))
+    }
+
+    /// Get the SPIR-V version declared in the module header, as a `(major, minor)` pair.
+    pub fn spirv_version(&self) -> (u8, u8) {
+        // The module was parsed by SPIRV-Cross, so the 5 word header is present.
+        let version = self.original_words()[1];
+        ((version >> 16) as u8, (version >> 8) as u8)
+    }
+
+    /// Get the generator magic number declared in the module header.
+    ///
+    /// The high 16 bits are the tool ID registered in the SPIR-V registry, for example
+    /// 8 for glslang, and the low 16 bits are a version number defined by the tool.
+    pub fn generator_magic(&self) -> u32 {
+        // The module was parsed by SPIRV-Cross, so the 5 word header is present.
+        self.original_words()[2]
+    }
+
+    /// Get the lowest Vulkan version, as a `(major, minor)` pair, that supports the
+    /// SPIR-V version and capabilities declared by the module.
+    ///
+    /// This is a best-effort, conservative estimate. Capabilities are mapped to the Vulkan
+    /// version where they became core, or where the extension exposing them became usable,
+    /// for example subgroup operations require Vulkan 1.1, the Vulkan memory model
+    /// requires Vulkan 1.2, and ray tracing requires Vulkan 1.2 along with the
+    /// corresponding extensions. Optional device features and extensions still need to be
+    /// checked separately. The result is at least Vulkan 1.0.
+    pub fn minimum_vulkan_version(&self) -> error::Result<(u32, u32)> {
+        let mut minimum = match self.spirv_version() {
+            (1, 0..=2) => (1, 0),
+            (1, 3) => (1, 1),
+            (1, 4 | 5) => (1, 2),
+            _ => (1, 3),
+        };
+
+        for capability in self.declared_capabilities()? {
+            for (_, required) in CAPABILITY_VULKAN_VERSIONS
+                .iter()
+                .filter(|(declared, _)| declared == capability)
+            {
+                minimum = minimum.max(*required);
+            }
+        }
+
+        Ok(minimum)
+    }
+
+    /// Returns whether the module uses explicit derivative instructions, such as
+    /// `dFdx`, `dFdy` or `fwidth` in GLSL.
+    ///
+    /// There is no capability for derivatives, so this scans the instructions in
+    /// [`Compiler::original_words`] for `OpDPdx`, `OpDPdy`, `OpFwidth`, and their
+    /// fine and coarse variants. Implicit derivatives from sampling with an implicit
+    /// LOD are not included.
+    pub fn uses_derivatives(&self) -> error::Result<bool> {
+        for instruction in Instructions::new(self.original_words()) {
+            let (op, _) = instruction?;
+            if matches!(
+                op,
+                Some(
+                    spirv::Op::DPdx
+                        | spirv::Op::DPdy
+                        | spirv::Op::Fwidth
+                        | spirv::Op::DPdxFine
+                        | spirv::Op::DPdyFine
+                        | spirv::Op::FwidthFine
+                        | spirv::Op::DPdxCoarse
+                        | spirv::Op::DPdyCoarse
+                        | spirv::Op::FwidthCoarse
+                )
+            ) {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Returns whether the module is a library, i.e. declares no entry points.
+    ///
+    /// Library modules declare the `Linkage` capability, and export functions to be linked
+    /// into other modules later, which can be found with [`Compiler::exported_functions`].
+    pub fn is_library(&self) -> error::Result<bool> {
+        Ok(self.entry_points()?.next().is_none())
+    }
+
+    /// Get the functions exported by the module with the
+    /// [`LinkageAttributes`](spirv::Decoration::LinkageAttributes) decoration,
+    /// along with their linkage names.
+    ///
+    /// SPIRV-Cross does not track linkage attributes, so they are found by scanning
+    /// [`Compiler::original_words`].
+    pub fn exported_functions(&self) -> error::Result<Vec<(String, Handle<VariableId>)>> {
+        let mut exports = Vec::new();
+
+        for instruction in Instructions::new(self.original_words()) {
+            let (op, operands) = instruction?;
+            if op != Some(spirv::Op::Decorate) {
+                continue;
+            }
+
+            let [target, decoration, literals @ ..] = operands else {
+                continue;
+            };
+
+            if *decoration != spirv::Decoration::LinkageAttributes as u32 {
+                continue;
+            }
+
+            // The name is a nul-terminated string, followed by the linkage type.
+            let Some((&linkage, name)) = literals.split_last() else {
+                continue;
+            };
+
+            if linkage != spirv::LinkageType::Export as u32 {
+                continue;
+            }
+
+            let name = decode_literal_string(name)?;
+            exports.push((name, self.create_handle(VariableId(SpvId(*target)))));
+        }
+
+        Ok(exports)
+    }
+
     /// Get the execution model of the module.
     pub fn execution_model(&self) -> error::Result<spirv::ExecutionModel> {
         unsafe {
@@ -77,7 +293,7 @@ impl<T> Compiler<T> {
     /// Update active built-ins in the SPIR-V module.
     pub fn update_active_builtins(&mut self) -> ActiveBuiltinsUpdatedProof {
         unsafe {
-            sys::spvc_compiler_update_active_builtins(self.ptr.as_ptr());
+            sys::spvc_compiler_update_active_builtins(self.ptr_mut());
             ActiveBuiltinsUpdatedProof(self.create_handle(()))
         }
     }
@@ -106,6 +322,26 @@ impl<T> Compiler<T> {
             ))
         }
     }
+
+    /// Returns whether the shader uses any of the `BaseVertex`, `BaseInstance`, or `DrawIndex`
+    /// built-ins, which require `VK_KHR_shader_draw_parameters` in Vulkan.
+    ///
+    /// This updates the set of active builtins with [`Compiler::update_active_builtins`],
+    /// which is why it requires a mutable reference.
+    pub fn uses_draw_parameters(&mut self) -> error::Result<bool> {
+        let proof = self.update_active_builtins();
+        for builtin in [
+            spirv::BuiltIn::BaseVertex,
+            spirv::BuiltIn::BaseInstance,
+            spirv::BuiltIn::DrawIndex,
+        ] {
+            if self.has_active_builtin(builtin, spirv::StorageClass::Input, proof)? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
 }
 
 /// Iterator type created by [`Compiler::entry_points`].
@@ -113,6 +349,7 @@ pub struct EntryPointIter<'a>(slice::Iter<'a, spvc_entry_point>, AllocationDropG
 
 /// A SPIR-V entry point.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EntryPoint<'a> {
     /// The execution model for the entry point.
     pub execution_model: spirv::ExecutionModel,
@@ -227,23 +464,60 @@ impl<T> Compiler<T> {
         model: spirv::ExecutionModel,
     ) -> error::Result<()> {
         let name = name.into();
+        let selected = name.as_ref().to_string();
         unsafe {
             let name = name.into_cstring_ptr()?;
 
             sys::spvc_compiler_set_entry_point(
-                self.ptr.as_ptr(),
+                self.ptr_mut(),
                 name.as_ptr(),
                 SpvExecutionModel(model as u32 as i32),
             )
-            .ok(&*self)
+            .ok(&*self)?;
+        }
+
+        self.entry_point = Some((selected, model));
+        Ok(())
+    }
+
+    /// Set the current entry point by name only, for modules where the name
+    /// identifies a single entry point.
+    ///
+    /// If no entry point has the given name, or entry points with the given name exist for
+    /// more than one execution model, returns [`SpirvCrossError::InvalidArgument`] listing the
+    /// candidate execution models. Use [`Compiler::set_entry_point`] to disambiguate.
+    pub fn set_entry_point_by_name<'str>(
+        &mut self,
+        name: impl Into<CompilerStr<'str>>,
+    ) -> error::Result<()> {
+        let name = name.into();
+        let models: Vec<spirv::ExecutionModel> = self
+            .entry_points()?
+            .filter(|entry_point| entry_point.name == name)
+            .map(|entry_point| entry_point.execution_model)
+            .collect();
+
+        match models.as_slice() {
+            [model] => self.set_entry_point(name, *model),
+            [] => Err(SpirvCrossError::InvalidArgument(format!(
+                "No entry point is named {:?}",
+                name.as_ref()
+            ))),
+            _ => Err(SpirvCrossError::InvalidArgument(format!(
+                "Entry point {:?} is ambiguous between execution models {models:?}",
+                name.as_ref()
+            ))),
         }
     }
 
     /// Renames an entry point from `from` to `to`.
     ///
-    /// If old_name is currently selected as the current entry point, it will continue to be the current entry point,
+    /// If `from` is currently selected as the current entry point, it will continue to be the current entry point,
     /// albeit with a new name.
     ///
+    /// Entry points are identified by both name and execution model, so this can be used to give
+    /// distinct names to entry points of different stages that are both named `main`.
+    ///
     /// Values returned from [`Compiler::entry_points`] before this call will be outdated.
     pub fn rename_entry_point<'str>(
         &mut self,
@@ -253,19 +527,43 @@ impl<T> Compiler<T> {
     ) -> error::Result<()> {
         let from = from.into();
         let to = to.into();
+        let rename = (from.as_ref().to_string(), to.as_ref().to_string(), model);
 
         unsafe {
             let from = from.into_cstring_ptr()?;
             let to = to.into_cstring_ptr()?;
 
             sys::spvc_compiler_rename_entry_point(
-                self.ptr.as_ptr(),
+                self.ptr_mut(),
                 from.as_ptr(),
                 to.as_ptr(),
                 SpvExecutionModel(model as u32 as i32),
             )
-            .ok(&*self)
+            .ok(&*self)?;
         }
+
+        if let Some((selected, selected_model)) = &mut self.entry_point {
+            if *selected == rename.0 && *selected_model == model {
+                selected.clone_from(&rename.1);
+            }
+        }
+
+        self.entry_point_renames.push(rename);
+        Ok(())
+    }
+
+    /// Apply the entry point renames and the entry point selected on this instance to
+    /// `compiler`, which must have been created from the same module.
+    pub(crate) fn copy_entry_point_to<U>(&self, compiler: &mut Compiler<U>) -> error::Result<()> {
+        for (from, to, model) in &self.entry_point_renames {
+            compiler.rename_entry_point(from.as_str(), to.as_str(), *model)?;
+        }
+
+        if let Some((name, model)) = &self.entry_point {
+            compiler.set_entry_point(name.as_str(), *model)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -328,6 +626,51 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    pub fn library() -> Result<(), SpirvCrossError> {
+        // A module with the Linkage capability that only exports `foo`.
+        #[rustfmt::skip]
+        let words: &[u32] = &[
+            0x07230203, 0x00010000, 0, 5, 0,
+            // OpCapability Shader
+            0x00020011, 1,
+            // OpCapability Linkage
+            0x00020011, 5,
+            // OpMemoryModel Logical GLSL450
+            0x0003000e, 0, 1,
+            // OpDecorate %4 LinkageAttributes "foo" Export
+            0x00050047, 4, 41, u32::from_le_bytes(*b"foo\0"), 0,
+            // %1 = OpTypeVoid
+            0x00020013, 1,
+            // %2 = OpTypeFunction %1
+            0x00030021, 2, 1,
+            // %4 = OpFunction %1 None %2
+            0x00050036, 1, 4, 0, 2,
+            // %3 = OpLabel
+            0x000200f8, 3,
+            // OpReturn
+            0x000100fd,
+            // OpFunctionEnd
+            0x00010038,
+        ];
+
+        let compiler: Compiler<targets::None> = Compiler::new(Module::from_words(words))?;
+        assert!(compiler.is_library()?);
+
+        let exports = compiler.exported_functions()?;
+        assert_eq!(1, exports.len());
+        assert_eq!("foo", exports[0].0);
+        assert_eq!(4, exports[0].1.id());
+
+        let vec = Vec::from(BASIC_SPV);
+        let compiler: Compiler<targets::None> =
+            Compiler::new(Module::from_words(bytemuck::cast_slice(&vec)))?;
+        assert!(!compiler.is_library()?);
+        assert!(compiler.exported_functions()?.is_empty());
+
+        Ok(())
+    }
+
     #[test]
     pub fn capabilities() -> Result<(), SpirvCrossError> {
         let vec = Vec::from(BASIC_SPV);
@@ -342,4 +685,34 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    pub fn set_entry_point_by_name() -> Result<(), SpirvCrossError> {
+        let vec = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&vec));
+
+        let mut compiler: Compiler<targets::None> = Compiler::new(words)?;
+        compiler.set_entry_point_by_name("main")?;
+        assert_eq!(ExecutionModel::Fragment, compiler.execution_model()?);
+
+        assert!(matches!(
+            compiler.set_entry_point_by_name("missing"),
+            Err(SpirvCrossError::InvalidArgument(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn module_header() -> Result<(), SpirvCrossError> {
+        let vec = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&vec));
+
+        let compiler: Compiler<targets::None> = Compiler::new(words)?;
+        assert_eq!((1, 0), compiler.spirv_version());
+        // Google shaderc over glslang.
+        assert_eq!(13, compiler.generator_magic() >> 16);
+
+        Ok(())
+    }
 }
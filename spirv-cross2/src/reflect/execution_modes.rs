@@ -1,5 +1,5 @@
 use crate::error;
-use crate::error::ToContextError;
+use crate::error::{SpirvCrossError, ToContextError};
 use crate::handle::Handle;
 use crate::reflect::try_valid_slice;
 use crate::Compiler;
@@ -44,6 +44,47 @@ impl ExecutionModeArguments {
             ExecutionModeArguments::LocalSizeId { x, y, z } => [x.id(), y.id(), z.id()],
         }
     }
+
+    /// Get the arguments as the literal operands of `OpExecutionMode`.
+    fn to_operands(&self) -> Vec<u32> {
+        match self {
+            ExecutionModeArguments::None => Vec::new(),
+            ExecutionModeArguments::Literal(a) => vec![*a],
+            ExecutionModeArguments::LocalSize { x, y, z } => vec![*x, *y, *z],
+            ExecutionModeArguments::LocalSizeId { x, y, z } => vec![x.id(), y.id(), z.id()],
+        }
+    }
+}
+
+/// The primitive topology consumed by a geometry or tessellation evaluation shader,
+/// created by [`Compiler::input_topology`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum InputTopology {
+    /// Points, from the `InputPoints` execution mode.
+    Points,
+    /// Lines, from the `InputLines` execution mode.
+    Lines,
+    /// Lines with adjacency, from the `InputLinesAdjacency` execution mode.
+    LinesAdjacency,
+    /// Triangles, from the `Triangles` execution mode.
+    Triangles,
+    /// Triangles with adjacency, from the `InputTrianglesAdjacency` execution mode.
+    TrianglesAdjacency,
+    /// The quad tessellation domain, from the `Quads` execution mode.
+    Quads,
+    /// The isoline tessellation domain, from the `Isolines` execution mode.
+    Isolines,
+}
+
+/// The number of operands of an execution mode that SPIRV-Cross keeps track of.
+fn operand_count(mode: spirv::ExecutionMode) -> u32 {
+    match mode {
+        spirv::ExecutionMode::LocalSize | spirv::ExecutionMode::LocalSizeId => 3,
+        spirv::ExecutionMode::Invocations
+        | spirv::ExecutionMode::OutputVertices
+        | spirv::ExecutionMode::OutputPrimitivesEXT => 1,
+        _ => 0,
+    }
 }
 
 impl<T> Compiler<T> {
@@ -59,7 +100,7 @@ impl<T> Compiler<T> {
         unsafe {
             let Some(arguments) = arguments else {
                 return sys::spvc_compiler_unset_execution_mode(
-                    self.ptr.as_ptr(),
+                    self.ptr_mut(),
                     SpvExecutionMode(mode as u32 as i32),
                 );
             };
@@ -67,7 +108,7 @@ impl<T> Compiler<T> {
             let [x, y, z] = arguments.expand();
 
             sys::spvc_compiler_set_execution_mode_with_arguments(
-                self.ptr.as_ptr(),
+                self.ptr_mut(),
                 SpvExecutionMode(mode as u32 as i32),
                 x,
                 y,
@@ -76,6 +117,48 @@ impl<T> Compiler<T> {
         }
     }
 
+    /// Set an execution mode with the literal operands of `OpExecutionMode`.
+    ///
+    /// The number of operands must match the execution mode: 3 for `LocalSize` and `LocalSizeId`,
+    /// 1 for `Invocations`, `OutputVertices` and `OutputPrimitivesEXT`, and none for all other
+    /// modes. Otherwise, returns [`SpirvCrossError::InvalidArgument`].
+    pub fn set_execution_mode_with_operands(
+        &mut self,
+        mode: spirv::ExecutionMode,
+        operands: &[u32],
+    ) -> error::Result<()> {
+        let expected = operand_count(mode);
+        if operands.len() != expected as usize {
+            return Err(SpirvCrossError::InvalidArgument(format!(
+                "execution mode {mode:?} takes {expected} operands, got {}",
+                operands.len()
+            )));
+        }
+
+        let mut arguments = [0; 3];
+        arguments[..operands.len()].copy_from_slice(operands);
+        let [x, y, z] = arguments;
+
+        unsafe {
+            sys::spvc_compiler_set_execution_mode_with_arguments(
+                self.ptr_mut(),
+                SpvExecutionMode(mode as u32 as i32),
+                x,
+                y,
+                z,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Unset an execution mode.
+    ///
+    /// This is equivalent to calling [`Compiler::set_execution_mode`] with `None`.
+    pub fn unset_execution_mode(&mut self, mode: spirv::ExecutionMode) {
+        self.set_execution_mode(mode, None)
+    }
+
     /// Query `OpExecutionMode`.
     pub fn execution_modes(&self) -> error::Result<&[spirv::ExecutionMode]> {
         unsafe {
@@ -94,6 +177,60 @@ impl<T> Compiler<T> {
         }
     }
 
+    /// Query `OpExecutionMode` for the current entry point, along with the operands of each mode.
+    ///
+    /// Operands are as returned by [`Compiler::execution_mode_arguments`], i.e. the
+    /// workgroup size for `LocalSize`, constant IDs for `LocalSizeId`, and the literal for
+    /// `Invocations`, `OutputVertices` and `OutputPrimitivesEXT`. All other modes have no operands.
+    pub fn execution_modes_with_arguments(
+        &self,
+    ) -> error::Result<Vec<(spirv::ExecutionMode, Vec<u32>)>> {
+        let mut modes = Vec::new();
+        for &mode in self.execution_modes()? {
+            let operands = self
+                .execution_mode_arguments(mode)?
+                .map_or_else(Vec::new, |arguments| arguments.to_operands());
+            modes.push((mode, operands));
+        }
+
+        Ok(modes)
+    }
+
+    /// Get the input primitive topology of the current entry point.
+    ///
+    /// For geometry shaders, this is the input primitive. For tessellation shaders, this is
+    /// the abstract patch type of the tessellation domain. Returns `None` for other
+    /// execution models, or if no input primitive is declared.
+    pub fn input_topology(&self) -> error::Result<Option<InputTopology>> {
+        let geometry = match self.execution_model()? {
+            spirv::ExecutionModel::Geometry => true,
+            spirv::ExecutionModel::TessellationControl
+            | spirv::ExecutionModel::TessellationEvaluation => false,
+            _ => return Ok(None),
+        };
+
+        for mode in self.execution_modes()? {
+            let topology = match mode {
+                spirv::ExecutionMode::InputPoints if geometry => InputTopology::Points,
+                spirv::ExecutionMode::InputLines if geometry => InputTopology::Lines,
+                spirv::ExecutionMode::InputLinesAdjacency if geometry => {
+                    InputTopology::LinesAdjacency
+                }
+                spirv::ExecutionMode::InputTrianglesAdjacency if geometry => {
+                    InputTopology::TrianglesAdjacency
+                }
+                spirv::ExecutionMode::Triangles => InputTopology::Triangles,
+                spirv::ExecutionMode::Quads if !geometry => InputTopology::Quads,
+                spirv::ExecutionMode::Isolines if !geometry => InputTopology::Isolines,
+                _ => continue,
+            };
+
+            return Ok(Some(topology));
+        }
+
+        Ok(None)
+    }
+
     /// Get arguments used by the execution mode.
     ///
     /// If the execution mode is unused, returns `None`.
@@ -179,6 +316,32 @@ impl<T> Compiler<T> {
             }
         })
     }
+
+    /// Get a single literal argument of an execution mode by index.
+    ///
+    /// Returns `None` if the execution mode is unused, or if the mode has no operand at `index`.
+    /// Only `LocalSize` and `LocalSizeId` (3 operands), and `Invocations`, `OutputVertices`
+    /// and `OutputPrimitivesEXT` (1 operand) are tracked by SPIRV-Cross. For `LocalSizeId`,
+    /// the argument is the ID of the constant.
+    pub fn execution_mode_argument(
+        &self,
+        mode: spirv::ExecutionMode,
+        index: u32,
+    ) -> error::Result<Option<u32>> {
+        if index >= operand_count(mode) || !self.execution_modes()?.contains(&mode) {
+            return Ok(None);
+        }
+
+        let argument = unsafe {
+            sys::spvc_compiler_get_execution_mode_argument_by_index(
+                self.ptr.as_ptr(),
+                SpvExecutionMode(mode as u32 as i32),
+                index,
+            )
+        };
+
+        Ok(Some(argument))
+    }
 }
 
 #[cfg(test)]
@@ -202,4 +365,46 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    pub fn execution_modes_with_arguments() -> Result<(), SpirvCrossError> {
+        let vec = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&vec));
+
+        let compiler: Compiler<targets::None> = Compiler::new(words)?;
+        assert_eq!(
+            vec![(spirv::ExecutionMode::OriginUpperLeft, vec![])],
+            compiler.execution_modes_with_arguments()?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn set_execution_mode_with_operands() -> Result<(), SpirvCrossError> {
+        let vec = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&vec));
+
+        let mut compiler: Compiler<targets::None> = Compiler::new(words)?;
+
+        compiler.unset_execution_mode(spirv::ExecutionMode::OriginUpperLeft);
+        assert!(compiler.execution_modes()?.is_empty());
+
+        compiler.set_execution_mode_with_operands(spirv::ExecutionMode::OriginUpperLeft, &[])?;
+        assert_eq!(
+            [spirv::ExecutionMode::OriginUpperLeft],
+            compiler.execution_modes()?
+        );
+
+        assert!(matches!(
+            compiler.set_execution_mode_with_operands(spirv::ExecutionMode::OriginUpperLeft, &[1]),
+            Err(SpirvCrossError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            compiler.set_execution_mode_with_operands(spirv::ExecutionMode::LocalSize, &[64, 1]),
+            Err(SpirvCrossError::InvalidArgument(_))
+        ));
+
+        Ok(())
+    }
 }
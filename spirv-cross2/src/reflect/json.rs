@@ -0,0 +1,203 @@
+#![cfg(feature = "serde-json")]
+#![cfg_attr(docsrs, doc(cfg(feature = "serde-json")))]
+use crate::error;
+use crate::handle::{ConstantId, Handle};
+use crate::reflect::{ResourceType, ScalarValue, TypeInner};
+use crate::Compiler;
+use serde_json::{json, Value};
+
+/// The version of the schema emitted by [`Compiler::reflection_json`].
+///
+/// This is incremented whenever a field is removed or changes meaning.
+/// Adding fields does not change the version.
+pub const REFLECTION_JSON_SCHEMA_VERSION: u32 = 1;
+
+/// The name of an execution model in the reflection schema.
+fn execution_model_name(model: spirv::ExecutionModel) -> &'static str {
+    use spirv::ExecutionModel;
+    match model {
+        ExecutionModel::Vertex => "Vertex",
+        ExecutionModel::TessellationControl => "TessellationControl",
+        ExecutionModel::TessellationEvaluation => "TessellationEvaluation",
+        ExecutionModel::Geometry => "Geometry",
+        ExecutionModel::Fragment => "Fragment",
+        ExecutionModel::GLCompute => "GLCompute",
+        ExecutionModel::Kernel => "Kernel",
+        ExecutionModel::TaskNV => "TaskNV",
+        ExecutionModel::MeshNV => "MeshNV",
+        ExecutionModel::RayGenerationNV => "RayGenerationNV",
+        ExecutionModel::IntersectionNV => "IntersectionNV",
+        ExecutionModel::AnyHitNV => "AnyHitNV",
+        ExecutionModel::ClosestHitNV => "ClosestHitNV",
+        ExecutionModel::MissNV => "MissNV",
+        ExecutionModel::CallableNV => "CallableNV",
+        ExecutionModel::TaskEXT => "TaskEXT",
+        ExecutionModel::MeshEXT => "MeshEXT",
+    }
+}
+
+/// The name of a resource type in the reflection schema.
+fn resource_type_name(resource_type: ResourceType) -> &'static str {
+    match resource_type {
+        ResourceType::Unknown => "Unknown",
+        ResourceType::UniformBuffer => "UniformBuffer",
+        ResourceType::StorageBuffer => "StorageBuffer",
+        ResourceType::StageInput => "StageInput",
+        ResourceType::StageOutput => "StageOutput",
+        ResourceType::SubpassInput => "SubpassInput",
+        ResourceType::StorageImage => "StorageImage",
+        ResourceType::SampledImage => "SampledImage",
+        ResourceType::AtomicCounter => "AtomicCounter",
+        ResourceType::PushConstant => "PushConstant",
+        ResourceType::SeparateImage => "SeparateImage",
+        ResourceType::SeparateSamplers => "SeparateSamplers",
+        ResourceType::AccelerationStructure => "AccelerationStructure",
+        ResourceType::RayQuery => "RayQuery",
+        ResourceType::ShaderRecordBuffer => "ShaderRecordBuffer",
+        ResourceType::GlPlainUniform => "GlPlainUniform",
+    }
+}
+
+/// Curated reflection output.
+impl<T> Compiler<T> {
+    /// Export reflection information as JSON, with a stable, versioned schema.
+    ///
+    /// Unlike the [`Json`](crate::targets::Json) compile target, which emits SPIRV-Cross's own
+    /// format, this schema is defined by this crate. It has the following shape.
+    ///
+    /// ```json
+    /// {
+    ///   "schema_version": 1,
+    ///   "entry_points": [{ "name": "main", "execution_model": "Fragment" }],
+    ///   "resources": [{
+    ///     "name": "tex",
+    ///     "type": "SampledImage",
+    ///     "set": 0,
+    ///     "binding": 1,
+    ///     "descriptor_count": 1
+    ///   }],
+    ///   "push_constants": [{
+    ///     "name": "constants",
+    ///     "size": 16,
+    ///     "members": [{ "name": "tint", "offset": 0, "size": 16 }]
+    ///   }],
+    ///   "specialization_constants": [{ "constant_id": 0, "default": 1 }]
+    /// }
+    /// ```
+    ///
+    /// Resources are those returned by [`Compiler::binding_table`] for the current entry point.
+    /// The `default` of a specialization constant is `null` if it is not a scalar.
+    pub fn reflection_json(&self) -> error::Result<String> {
+        let mut entry_points = Vec::new();
+        for entry_point in self.entry_points()? {
+            entry_points.push(json!({
+                "name": entry_point.name.to_string(),
+                "execution_model": execution_model_name(entry_point.execution_model),
+            }));
+        }
+
+        let mut resources = Vec::new();
+        for binding in self.binding_table()? {
+            resources.push(json!({
+                "name": binding.name.to_string(),
+                "type": resource_type_name(binding.resource_type),
+                "set": binding.set,
+                "binding": binding.binding,
+                "descriptor_count": binding.descriptor_count,
+            }));
+        }
+
+        let mut push_constants = Vec::new();
+        let shader_resources = self.shader_resources()?;
+        for push_constant in shader_resources.resources_for_type(ResourceType::PushConstant)? {
+            let TypeInner::Struct(block) = self.type_description(push_constant.base_type_id)?.inner
+            else {
+                continue;
+            };
+
+            let members: Vec<Value> = block
+                .members
+                .iter()
+                .map(|member| {
+                    json!({
+                        "name": member.name.as_ref().map(|name| name.to_string()),
+                        "offset": member.offset,
+                        "size": member.size,
+                    })
+                })
+                .collect();
+
+            push_constants.push(json!({
+                "name": push_constant.name.to_string(),
+                "size": block.size,
+                "members": members,
+            }));
+        }
+
+        let mut specialization_constants = Vec::new();
+        for constant in self.specialization_constants()? {
+            specialization_constants.push(json!({
+                "constant_id": constant.constant_id,
+                "default": self.specialization_constant_default(constant.id)?,
+            }));
+        }
+
+        let reflection = json!({
+            "schema_version": REFLECTION_JSON_SCHEMA_VERSION,
+            "entry_points": entry_points,
+            "resources": resources,
+            "push_constants": push_constants,
+            "specialization_constants": specialization_constants,
+        });
+
+        Ok(reflection.to_string())
+    }
+
+    fn specialization_constant_default(
+        &self,
+        constant: Handle<ConstantId>,
+    ) -> error::Result<Value> {
+        let value = match self.specialization_constant_scalar_value(constant)? {
+            Some(ScalarValue::Bool(value)) => json!(value),
+            Some(ScalarValue::Int(value)) => json!(value),
+            Some(ScalarValue::Uint(value)) => json!(value),
+            Some(ScalarValue::Float(value)) => json!(value),
+            None => Value::Null,
+        };
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::SpirvCrossError;
+    use crate::Compiler;
+    use crate::{targets, Module};
+    use serde_json::Value;
+
+    static BASIC_SPV: &[u8] = include_bytes!("../../basic.spv");
+
+    #[test]
+    pub fn reflection_json() -> Result<(), SpirvCrossError> {
+        let vec = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&vec));
+
+        let compiler: Compiler<targets::None> = Compiler::new(words)?;
+        let json = compiler.reflection_json()?;
+
+        let value: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(1, value["schema_version"]);
+        assert_eq!("Fragment", value["entry_points"][0]["execution_model"]);
+
+        let resources = value["resources"].as_array().unwrap();
+        assert_eq!(2, resources.len());
+        assert_eq!("SampledImage", resources[1]["type"]);
+        assert_eq!(1, resources[1]["binding"]);
+
+        assert!(value["push_constants"].as_array().unwrap().is_empty());
+        assert!(value["specialization_constants"].is_array());
+
+        Ok(())
+    }
+}
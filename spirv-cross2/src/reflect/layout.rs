@@ -0,0 +1,321 @@
+use crate::error;
+use crate::error::SpirvCrossError;
+use crate::handle::{Handle, TypeId, VariableId};
+use crate::reflect::{ArrayDimension, DecorationValue, StructMember, StructType, TypeInner};
+use crate::Compiler;
+use spirv_cross_sys as sys;
+
+/// The layout decoration of a struct member that a [`LayoutWarning`] refers to.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum LayoutWarningKind {
+    /// The `Offset` of the member.
+    Offset,
+    /// The `ArrayStride` of an array member.
+    ArrayStride,
+    /// The `MatrixStride` of a matrix member, or array of matrices.
+    MatrixStride,
+}
+
+/// A struct member whose layout decoration differs from the one std430 would compute,
+/// created by [`Compiler::validate_std430_layout`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LayoutWarning {
+    /// A handle to the struct type containing the member.
+    pub struct_type: Handle<TypeId>,
+    /// The index of the member in the struct.
+    pub index: usize,
+    /// The name of the member, if any.
+    pub name: Option<String>,
+    /// The decoration that differs.
+    pub kind: LayoutWarningKind,
+    /// The value std430 would compute.
+    pub expected: u32,
+    /// The value declared in the SPIR-V module.
+    pub declared: u32,
+}
+
+/// A region of implicit padding between the members of a struct,
+/// created by [`Compiler::struct_padding`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct PaddingRegion {
+    /// The index of the member the padding follows.
+    pub after_member_index: usize,
+    /// The size of the padding in bytes.
+    pub bytes: u32,
+}
+
+/// The std430 layout of a type.
+struct Std430Layout {
+    align: u32,
+    size: u32,
+    array_stride: Option<u32>,
+    matrix_stride: Option<u32>,
+}
+
+impl Std430Layout {
+    const fn plain(align: u32, size: u32) -> Self {
+        Std430Layout {
+            align,
+            size,
+            array_stride: None,
+            matrix_stride: None,
+        }
+    }
+}
+
+const fn align_to(value: u32, align: u32) -> u32 {
+    if align == 0 {
+        return value;
+    }
+    (value + align - 1) / align * align
+}
+
+/// The std430 alignment of a vector with the given component count and scalar size.
+const fn vector_align(width: u32, scalar_size: u32) -> u32 {
+    if width == 2 {
+        2 * scalar_size
+    } else if width >= 3 {
+        4 * scalar_size
+    } else {
+        scalar_size
+    }
+}
+
+/// Validation of buffer block layouts.
+impl<T> Compiler<T> {
+    /// Validate the layout decorations of a buffer block against the std430 layout rules.
+    ///
+    /// Every member of the block, including members of nested structs, is checked for an
+    /// `Offset`, `ArrayStride` and `MatrixStride` that differs from what std430 would compute
+    /// from the preceding members. Offsets are computed from the declared offset and size of the
+    /// previous member, so a single mismatch is not repeated for every following member.
+    ///
+    /// This is a diagnostic for hand-written layout decorations, a block with warnings is
+    /// not necessarily invalid SPIR-V.
+    ///
+    /// If the variable is not a block, returns [`SpirvCrossError::InvalidArgument`].
+    pub fn validate_std430_layout(
+        &self,
+        variable: impl Into<Handle<VariableId>>,
+    ) -> error::Result<Vec<LayoutWarning>> {
+        let type_id = self.variable_type(variable)?;
+        let type_id = self.yield_id(type_id)?;
+
+        let base_type_id = unsafe {
+            // Pointers and arrays refer to the block type as their base type.
+            let ty = sys::spvc_compiler_get_type_handle(self.ptr.as_ptr(), type_id);
+            self.create_handle(sys::spvc_type_get_base_type_id(ty))
+        };
+
+        let TypeInner::Struct(block) = self.type_description(base_type_id)?.inner else {
+            return Err(SpirvCrossError::InvalidArgument(String::from(
+                "The variable is not a buffer block",
+            )));
+        };
+
+        let mut warnings = Vec::new();
+        self.std430_struct_layout(&block, &mut warnings)?;
+        Ok(warnings)
+    }
+
+    /// Set the `ArrayStride` decoration of an array struct member, for example to tightly pack
+    /// the elements of a storage buffer.
+    ///
+    /// The stride must be at least the size of an element of the outermost dimension,
+    /// as computed by the std430 rules without padding the element to its alignment,
+    /// so that elements do not overlap. Otherwise, returns [`SpirvCrossError::InvalidArgument`].
+    ///
+    /// The decoration is set on the array type of the member, so it applies to every
+    /// use of that array type.
+    pub fn set_array_stride(&mut self, member: &StructMember, stride: u32) -> error::Result<()> {
+        let TypeInner::Array {
+            base, dimensions, ..
+        } = self.type_description(member.id)?.inner
+        else {
+            return Err(SpirvCrossError::InvalidArgument(String::from(
+                "The struct member is not an array",
+            )));
+        };
+
+        let row_major = self.member_decoration(member, spirv::Decoration::RowMajor)?
+            == Some(DecorationValue::Present);
+        let element = self.std430_layout(base, row_major, &mut Vec::new())?;
+
+        let mut minimum = element.size;
+        if let Some((_, inner)) = dimensions.split_last() {
+            for dim in inner {
+                minimum = minimum.saturating_mul(self.array_dimension_length(dim)?);
+            }
+        }
+
+        if stride < minimum {
+            return Err(SpirvCrossError::InvalidArgument(format!(
+                "Array stride {stride} is smaller than the element size {minimum}"
+            )));
+        }
+
+        self.set_decoration(member.id, spirv::Decoration::ArrayStride, Some(stride))
+    }
+
+    /// Get the implicit padding between the members of a struct type, as laid out
+    /// by its `Offset` decorations.
+    ///
+    /// A padding region is reported after a member whenever the next member, or the end of the struct
+    /// for the last member, starts past the end of the member. Members of nested structs are not visited.
+    ///
+    /// If the type is not a struct, returns [`SpirvCrossError::InvalidArgument`].
+    pub fn struct_padding(
+        &self,
+        struct_type: impl Into<Handle<TypeId>>,
+    ) -> error::Result<Vec<PaddingRegion>> {
+        let TypeInner::Struct(struct_type) = self.type_description(struct_type.into())?.inner
+        else {
+            return Err(SpirvCrossError::InvalidArgument(String::from(
+                "The type is not a struct",
+            )));
+        };
+
+        let mut padding = Vec::new();
+        for (i, member) in struct_type.members.iter().enumerate() {
+            let end = member.offset.saturating_add(member.size as u32);
+            let next = struct_type
+                .members
+                .get(i + 1)
+                .map_or(struct_type.size as u32, |next| next.offset);
+
+            if next > end {
+                padding.push(PaddingRegion {
+                    after_member_index: member.index,
+                    bytes: next - end,
+                });
+            }
+        }
+
+        Ok(padding)
+    }
+
+    fn std430_struct_layout(
+        &self,
+        struct_type: &StructType,
+        warnings: &mut Vec<LayoutWarning>,
+    ) -> error::Result<Std430Layout> {
+        let mut end = 0;
+        let mut struct_align = 1;
+
+        for member in &struct_type.members {
+            let row_major = self.member_decoration(member, spirv::Decoration::RowMajor)?
+                == Some(DecorationValue::Present);
+            let layout = self.std430_layout(member.id, row_major, warnings)?;
+
+            let mut warn = |kind, expected, declared| {
+                if expected != declared {
+                    warnings.push(LayoutWarning {
+                        struct_type: struct_type.id,
+                        index: member.index,
+                        name: member.name.as_ref().map(|name| name.to_string()),
+                        kind,
+                        expected,
+                        declared,
+                    })
+                }
+            };
+
+            warn(
+                LayoutWarningKind::Offset,
+                align_to(end, layout.align),
+                member.offset,
+            );
+
+            if let (Some(expected), Some(declared)) = (layout.array_stride, member.array_stride) {
+                warn(LayoutWarningKind::ArrayStride, expected, declared);
+            }
+
+            if let (Some(expected), Some(declared)) = (layout.matrix_stride, member.matrix_stride) {
+                warn(LayoutWarningKind::MatrixStride, expected, declared);
+            }
+
+            // Use the declared size so a mismatched stride does not also offset every following member.
+            end = member.offset.saturating_add(member.size as u32);
+            struct_align = struct_align.max(layout.align);
+        }
+
+        Ok(Std430Layout::plain(
+            struct_align,
+            align_to(end, struct_align),
+        ))
+    }
+
+    fn std430_layout(
+        &self,
+        id: Handle<TypeId>,
+        row_major: bool,
+        warnings: &mut Vec<LayoutWarning>,
+    ) -> error::Result<Std430Layout> {
+        Ok(match self.type_description(id)?.inner {
+            TypeInner::Scalar(scalar) => {
+                let size = scalar.size.byte_size() as u32;
+                Std430Layout::plain(size, size)
+            }
+            TypeInner::Vector { width, scalar } => {
+                let size = scalar.size.byte_size() as u32;
+                Std430Layout::plain(vector_align(width, size), width * size)
+            }
+            TypeInner::Matrix {
+                columns,
+                rows,
+                scalar,
+            } => {
+                // Column-major matrices are an array of column vectors,
+                // and row-major matrices are an array of row vectors.
+                let (vectors, width) = if row_major {
+                    (rows, columns)
+                } else {
+                    (columns, rows)
+                };
+
+                let stride = vector_align(width, scalar.size.byte_size() as u32);
+                Std430Layout {
+                    align: stride,
+                    size: stride * vectors,
+                    array_stride: None,
+                    matrix_stride: Some(stride),
+                }
+            }
+            TypeInner::Array {
+                base, dimensions, ..
+            } => {
+                let element = self.std430_layout(base, row_major, warnings)?;
+                let mut stride = align_to(element.size, element.align);
+                let mut count = 1u32;
+
+                // The declared stride is the stride of the outermost dimension.
+                if let Some((outermost, inner)) = dimensions.split_last() {
+                    for dim in inner {
+                        stride = stride.saturating_mul(self.array_dimension_length(dim)?);
+                    }
+                    count = self.array_dimension_length(outermost)?;
+                }
+
+                Std430Layout {
+                    align: element.align,
+                    size: stride.saturating_mul(count),
+                    array_stride: Some(stride),
+                    matrix_stride: element.matrix_stride,
+                }
+            }
+            TypeInner::Struct(struct_type) => self.std430_struct_layout(&struct_type, warnings)?,
+            // Physical storage buffer pointers are 64-bit addresses.
+            TypeInner::Pointer { .. } => Std430Layout::plain(8, 8),
+            _ => Std430Layout::plain(1, 0),
+        })
+    }
+
+    fn array_dimension_length(&self, dimension: &ArrayDimension) -> error::Result<u32> {
+        match dimension {
+            ArrayDimension::Literal(length) => Ok(*length),
+            ArrayDimension::Constant(constant) => {
+                self.specialization_constant_value::<u32>(*constant)
+            }
+        }
+    }
+}
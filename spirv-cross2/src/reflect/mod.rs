@@ -1,23 +1,35 @@
+mod bindings;
 mod buffers;
 mod combined_image_samplers;
 mod constants;
 mod decorations;
+mod disassemble;
 mod entry_points;
 mod execution_modes;
+mod json;
+mod layout;
 mod names;
 mod resources;
+mod snapshot;
 mod types;
 
 use crate::{error, SpirvCrossError};
+pub use bindings::*;
 pub use buffers::*;
 pub use combined_image_samplers::*;
 pub use constants::*;
 pub use decorations::*;
 pub use entry_points::*;
 pub use execution_modes::*;
+#[cfg(feature = "serde-json")]
+pub use json::*;
+pub use layout::*;
 pub use resources::*;
+pub use snapshot::*;
 pub use types::*;
 
+pub(crate) use names::validate_identifier;
+
 /// Check if an enum slice contains u32 max.
 #[inline(always)]
 fn enum_slice_is_not_max<T>(enum_slice: &[i32]) -> bool {
@@ -53,3 +65,36 @@ unsafe fn try_valid_slice<'a, T>(ptr: *const T, size: usize) -> error::Result<&'
         }
     }
 }
+
+/// An iterator over the instructions of a SPIR-V module, yielding the
+/// opcode and operands of each instruction.
+///
+/// Iteration stops with [`SpirvCrossError::InvalidSpirv`] if an instruction
+/// word count is out of bounds.
+struct Instructions<'a>(&'a [u32]);
+
+impl<'a> Instructions<'a> {
+    /// Iterate over the instructions of a module, skipping the 5 word header.
+    fn new(words: &'a [u32]) -> Self {
+        Self(words.get(5..).unwrap_or_default())
+    }
+}
+
+impl<'a> Iterator for Instructions<'a> {
+    type Item = error::Result<(Option<spirv::Op>, &'a [u32])>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let &first = self.0.first()?;
+        let word_count = (first >> 16) as usize;
+        if word_count == 0 || word_count > self.0.len() {
+            self.0 = &[];
+            return Some(Err(SpirvCrossError::InvalidSpirv(String::from(
+                "Instruction word count is out of bounds.",
+            ))));
+        }
+
+        let operands = &self.0[1..word_count];
+        self.0 = &self.0[word_count..];
+        Some(Ok((spirv::Op::from_u32(first & 0xffff), operands)))
+    }
+}
@@ -1,11 +1,33 @@
 use crate::error;
+use crate::error::SpirvCrossError;
 use crate::handle::{Handle, Id};
+use crate::reflect::ResourceType;
 use crate::Compiler;
 
 use crate::string::CompilerStr;
 use spirv_cross_sys as sys;
 use spirv_cross_sys::{SpvId, TypeId, VariableId};
 
+/// Check that a name is a valid identifier in every backend.
+///
+/// GLSL, HLSL and MSL all restrict identifiers to ASCII letters, digits and underscores,
+/// not starting with a digit.
+pub(crate) fn validate_identifier(name: &str) -> error::Result<()> {
+    let Some(first) = name.chars().next() else {
+        return Err(SpirvCrossError::InvalidArgument(String::from(
+            "Identifier must not be empty",
+        )));
+    };
+
+    if first.is_ascii_digit() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(SpirvCrossError::InvalidArgument(format!(
+            "`{name}` is not a valid identifier"
+        )));
+    }
+
+    Ok(())
+}
+
 impl<T> Compiler<T> {
     /// Gets the identifier (`OpName`) of an ID.
     pub fn name<I: Id>(&self, handle: Handle<I>) -> error::Result<Option<CompilerStr>> {
@@ -25,6 +47,9 @@ impl<T> Compiler<T> {
     ///
     /// Identifiers beginning with underscores or identifiers which contain double underscores
     /// are reserved by the implementation.
+    ///
+    /// If the identifier is empty, or contains characters other than ASCII letters, digits and
+    /// underscores, or starts with a digit, returns [`SpirvCrossError::InvalidArgument`].
     pub fn set_name<'str, I: Id>(
         &mut self,
         handle: Handle<I>,
@@ -32,11 +57,12 @@ impl<T> Compiler<T> {
     ) -> error::Result<()> {
         let id = self.yield_id(handle)?;
         let string = string.into();
+        validate_identifier(&string)?;
 
         unsafe {
             let cstring = string.into_cstring_ptr()?;
 
-            sys::spvc_compiler_set_name(self.ptr.as_ptr(), SpvId(id.id()), cstring.as_ptr());
+            sys::spvc_compiler_set_name(self.ptr_mut(), SpvId(id.id()), cstring.as_ptr());
 
             // Sanity drop to show that the lifetime of the cstring is only up until
             // we have returned. AFAIK, SPIRV-Cross will do a string copy.
@@ -67,6 +93,9 @@ impl<T> Compiler<T> {
     }
 
     /// Sets the member identifier for the given struct member.
+    ///
+    /// If the identifier is empty, or contains characters other than ASCII letters, digits and
+    /// underscores, or starts with a digit, returns [`SpirvCrossError::InvalidArgument`].
     pub fn set_member_name<'str>(
         &mut self,
         struct_type: Handle<TypeId>,
@@ -76,12 +105,13 @@ impl<T> Compiler<T> {
         let struct_type_id = self.yield_id(struct_type)?;
         let index = index;
         let string = string.into();
+        validate_identifier(&string)?;
 
         unsafe {
             let cstring = string.into_cstring_ptr()?;
 
             sys::spvc_compiler_set_member_name(
-                self.ptr.as_ptr(),
+                self.ptr_mut(),
                 struct_type_id,
                 index,
                 cstring.as_ptr(),
@@ -96,6 +126,42 @@ impl<T> Compiler<T> {
     }
 }
 
+impl<T> Compiler<T> {
+    /// Returns whether the module carries any debug names for its resources.
+    ///
+    /// This checks whether any resource variable or its base type has a non-empty
+    /// `OpName`. If this returns false, the module was likely stripped, and looking up
+    /// resources by name will not work.
+    pub fn has_debug_names(&self) -> error::Result<bool> {
+        let resources = self.shader_resources()?;
+        for resource_type in [
+            ResourceType::UniformBuffer,
+            ResourceType::StorageBuffer,
+            ResourceType::StageInput,
+            ResourceType::StageOutput,
+            ResourceType::SubpassInput,
+            ResourceType::StorageImage,
+            ResourceType::SampledImage,
+            ResourceType::AtomicCounter,
+            ResourceType::PushConstant,
+            ResourceType::SeparateImage,
+            ResourceType::SeparateSamplers,
+            ResourceType::AccelerationStructure,
+            ResourceType::ShaderRecordBuffer,
+            ResourceType::GlPlainUniform,
+        ] {
+            for resource in resources.resources_for_type(resource_type)? {
+                if self.name(resource.id)?.is_some() || self.name(resource.base_type_id)?.is_some()
+                {
+                    return Ok(true);
+                }
+            }
+        }
+
+        Ok(false)
+    }
+}
+
 impl<T> Compiler<T> {
     /// When declaring buffer blocks in GLSL, the name declared in the GLSL source
     /// might not be the same as the name declared in the SPIR-V module due to naming conflicts.
@@ -133,3 +199,70 @@ impl<T> Compiler<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::error::SpirvCrossError;
+    use crate::Compiler;
+    use crate::{targets, Module};
+
+    static BASIC_SPV: &[u8] = include_bytes!("../../basic.spv");
+
+    #[test]
+    pub fn has_debug_names() -> Result<(), SpirvCrossError> {
+        let vec = Vec::from(BASIC_SPV);
+        let words: &[u32] = bytemuck::cast_slice(&vec);
+
+        let compiler: Compiler<targets::None> = Compiler::new(Module::from_words(words))?;
+        assert!(compiler.has_debug_names()?);
+
+        // Strip OpName and OpMemberName instructions.
+        let mut stripped = Vec::from(&words[..5]);
+        let mut instructions = &words[5..];
+        while let Some(&first) = instructions.first() {
+            let (instruction, rest) = instructions.split_at((first >> 16) as usize);
+            let opcode = first & 0xffff;
+            if opcode != spirv::Op::Name as u32 && opcode != spirv::Op::MemberName as u32 {
+                stripped.extend_from_slice(instruction);
+            }
+            instructions = rest;
+        }
+
+        let compiler: Compiler<targets::None> = Compiler::new(Module::from_words(&stripped))?;
+        assert!(!compiler.has_debug_names()?);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn set_name_validates_identifier() -> Result<(), SpirvCrossError> {
+        let vec = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&vec));
+
+        let mut compiler: Compiler<targets::None> = Compiler::new(words)?;
+        let resources = compiler.shader_resources()?.all_resources()?;
+        let ubo = &resources.uniform_buffers[0];
+
+        for name in ["", "0ubo", "my ubo", "ubo.data", "ubö"] {
+            assert!(matches!(
+                compiler.set_name(ubo.id, name),
+                Err(SpirvCrossError::InvalidArgument(_))
+            ));
+            assert!(matches!(
+                compiler.set_member_name(ubo.base_type_id, 0, name),
+                Err(SpirvCrossError::InvalidArgument(_))
+            ));
+        }
+
+        compiler.set_name(ubo.id, "_ubo0")?;
+        assert_eq!(Some("_ubo0"), compiler.name(ubo.id)?.as_deref());
+
+        compiler.set_member_name(ubo.base_type_id, 0, "mvp_2")?;
+        assert_eq!(
+            Some("mvp_2"),
+            compiler.member_name(ubo.base_type_id, 0)?.as_deref()
+        );
+
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 use crate::error::{SpirvCrossError, ToContextError};
 use crate::handle::{Handle, TypeId, VariableId};
+use crate::reflect::TypeInner;
 use crate::sealed::Sealed;
 use crate::string::CompilerStr;
 use crate::{error, Compiler, PhantomCompiler, ToStatic};
@@ -17,6 +18,24 @@ use crate::iter::impl_iterator;
 /// The type of resource to query.
 pub use spirv_cross_sys::ResourceType;
 
+/// Resource types returned by [`ShaderResources::all_resources`], in order.
+const RESOURCE_TYPES: [ResourceType; 14] = [
+    ResourceType::UniformBuffer,
+    ResourceType::StorageBuffer,
+    ResourceType::StageInput,
+    ResourceType::StageOutput,
+    ResourceType::SubpassInput,
+    ResourceType::StorageImage,
+    ResourceType::SampledImage,
+    ResourceType::AtomicCounter,
+    ResourceType::AccelerationStructure,
+    ResourceType::GlPlainUniform,
+    ResourceType::PushConstant,
+    ResourceType::ShaderRecordBuffer,
+    ResourceType::SeparateImage,
+    ResourceType::SeparateSamplers,
+];
+
 /// A handle to shader resources.
 pub struct ShaderResources(NonNull<spvc_resources_s>, PhantomCompiler);
 
@@ -141,11 +160,94 @@ impl<T> Compiler<T> {
             )));
         }
         unsafe {
-            sys::spvc_compiler_set_enabled_interface_variables(self.ptr.as_ptr(), set.0)
-                .ok(&*self)?;
+            sys::spvc_compiler_set_enabled_interface_variables(self.ptr_mut(), set.0).ok(&*self)?;
             Ok(())
         }
     }
+
+    /// Get the storage images which may be both read from and written to.
+    ///
+    /// A storage image is considered read-write unless it is decorated with
+    /// `NonReadable` or `NonWritable`. If this is empty, the MSL `readwrite_texture_fences`
+    /// option has no effect and can be disabled.
+    pub fn readwrite_images(&self) -> error::Result<Vec<Handle<VariableId>>> {
+        let resources = self.shader_resources()?;
+        let mut images = Vec::new();
+        for image in resources.resources_for_type(ResourceType::StorageImage)? {
+            if self
+                .decoration(image.id, spirv::Decoration::NonReadable)?
+                .is_some()
+                || self
+                    .decoration(image.id, spirv::Decoration::NonWritable)?
+                    .is_some()
+            {
+                continue;
+            }
+
+            images.push(image.id);
+        }
+
+        Ok(images)
+    }
+
+    /// Get the buffers that contain buffer device addresses, i.e. pointers in the
+    /// `PhysicalStorageBuffer` storage class.
+    ///
+    /// This covers uniform buffers, storage buffers and push constant buffers.
+    /// SPIRV-Cross does not support controlling the MSL address space of individual buffers,
+    /// only of whole argument buffers with `Compiler<Msl>::set_argument_buffer_device_address_space`.
+    pub fn buffer_device_address_variables(&self) -> error::Result<Vec<Handle<VariableId>>> {
+        let resources = self.shader_resources()?;
+        let mut variables = Vec::new();
+        for resource_type in [
+            ResourceType::UniformBuffer,
+            ResourceType::StorageBuffer,
+            ResourceType::PushConstant,
+        ] {
+            for resource in resources.resources_for_type(resource_type)? {
+                if self.type_contains_device_address(resource.base_type_id, &mut Vec::new())? {
+                    variables.push(resource.id);
+                }
+            }
+        }
+
+        Ok(variables)
+    }
+
+    /// Check if the type contains a `PhysicalStorageBuffer` pointer,
+    /// without following pointers.
+    fn type_contains_device_address(
+        &self,
+        type_id: Handle<TypeId>,
+        visited: &mut Vec<Handle<TypeId>>,
+    ) -> error::Result<bool> {
+        if visited.contains(&type_id) {
+            return Ok(false);
+        }
+        visited.push(type_id);
+
+        match self.type_description(type_id)?.inner {
+            TypeInner::Pointer { storage, .. } => {
+                Ok(storage == spirv::StorageClass::PhysicalStorageBuffer)
+            }
+            // Arrays of pointers take the storage class of the pointer.
+            TypeInner::Array { storage, base, .. } => {
+                if storage == spirv::StorageClass::PhysicalStorageBuffer {
+                    return Ok(true);
+                }
+                self.type_contains_device_address(base, visited)
+            }
+            TypeInner::Struct(ty) => {
+                for member in ty.members {
+                    if self.type_contains_device_address(member.id, visited)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            _ => Ok(false),
+        }
+    }
 }
 
 /// Iterator over reflected resources, created by [`ShaderResources::resources_for_type`].
@@ -167,6 +269,7 @@ impl_iterator!(BuiltinResourceIter<'a>: BuiltinResource<'a> as and_then |s, o: &
 
 /// Description of a shader resource.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Resource<'a> {
     /// A handle to the variable this resource points to.
     pub id: Handle<VariableId>,
@@ -231,6 +334,7 @@ impl Clone for Resource<'_> {
 
 /// Description of a built-in shader resource.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BuiltinResource<'a> {
     /// The SPIR-V built-in for this resource.
     pub builtin: spirv::BuiltIn,
@@ -297,6 +401,7 @@ impl Clone for BuiltinResource<'_> {
 
 /// All SPIR-V resources declared in the module.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AllResources<'a> {
     /// Uniform buffer (UBOs) resources.
     pub uniform_buffers: Vec<Resource<'a>>,
@@ -482,6 +587,21 @@ impl ShaderResources {
         Ok(BuiltinResourceIter(self.1.clone(), slice.iter()))
     }
 
+    /// Get an iterator over all resources declared in the shader, along with their type.
+    ///
+    /// Unlike [`ShaderResources::all_resources`], this does not allocate. Resources are yielded
+    /// grouped by type, in the same order as the fields of [`AllResources`]. Built-in resources
+    /// are not included.
+    pub fn iter_all(&self) -> impl Iterator<Item = (ResourceType, Resource<'static>)> + '_ {
+        RESOURCE_TYPES.into_iter().flat_map(move |ty| {
+            // Querying the list only fails for unknown resource types.
+            self.resources_for_type(ty)
+                .into_iter()
+                .flatten()
+                .map(move |resource| (ty, resource))
+        })
+    }
+
     /// Get all resources declared in the shader.
     ///
     /// This will allocate a `Vec` for every resource type.
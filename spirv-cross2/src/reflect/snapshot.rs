@@ -0,0 +1,368 @@
+use crate::error;
+use crate::reflect::{ResourceType, ScalarValue, TypeInner};
+use crate::Compiler;
+use std::fmt::{Display, Formatter};
+
+/// `ResourceType` is defined in `spirv_cross_sys`, which does not depend on serde.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(remote = "ResourceType")]
+#[allow(dead_code)]
+enum ResourceTypeDef {
+    Unknown,
+    UniformBuffer,
+    StorageBuffer,
+    StageInput,
+    StageOutput,
+    SubpassInput,
+    StorageImage,
+    SampledImage,
+    AtomicCounter,
+    PushConstant,
+    SeparateImage,
+    SeparateSamplers,
+    AccelerationStructure,
+    RayQuery,
+    ShaderRecordBuffer,
+    GlPlainUniform,
+}
+
+/// An entry point in a [`ReflectionSnapshot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SnapshotEntryPoint {
+    /// The name of the entry point.
+    pub name: String,
+    /// The execution model of the entry point.
+    pub execution_model: spirv::ExecutionModel,
+}
+
+/// A resource that consumes a descriptor binding in a [`ReflectionSnapshot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SnapshotResource {
+    /// The name of the resource.
+    pub name: String,
+    /// The descriptor set of the resource.
+    pub set: u32,
+    /// The binding of the resource.
+    pub binding: u32,
+    /// The type of the resource.
+    #[cfg_attr(feature = "serde", serde(with = "ResourceTypeDef"))]
+    pub resource_type: ResourceType,
+    /// The number of descriptors consumed by the resource.
+    pub descriptor_count: u32,
+}
+
+/// A member of a push constant block in a [`ReflectionSnapshot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SnapshotPushConstantMember {
+    /// The name of the member, if any.
+    pub name: Option<String>,
+    /// The offset in bytes from the beginning of the push constant block.
+    pub offset: u32,
+    /// The declared size of the member.
+    pub size: usize,
+}
+
+/// A specialization constant in a [`ReflectionSnapshot`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SnapshotSpecializationConstant {
+    /// The declared `constant_id` of the constant.
+    pub constant_id: u32,
+    /// The default value of the constant, if it is a scalar.
+    pub default: Option<ScalarValue>,
+}
+
+/// The reflected interface of the current entry point of a shader,
+/// created by [`Compiler::reflection_snapshot`].
+///
+/// Unlike other reflection types, a snapshot does not hold any handles, so it can be
+/// kept after the compiler is dropped and compared with a snapshot of another module
+/// with [`ReflectionSnapshot::diff`].
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReflectionSnapshot {
+    /// Every entry point in the module.
+    pub entry_points: Vec<SnapshotEntryPoint>,
+    /// The resources returned by [`Compiler::binding_table`].
+    pub resources: Vec<SnapshotResource>,
+    /// The members of every push constant block.
+    pub push_constant_members: Vec<SnapshotPushConstantMember>,
+    /// Every specialization constant in the module.
+    pub specialization_constants: Vec<SnapshotSpecializationConstant>,
+}
+
+/// The items added, removed and changed between two snapshots.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SnapshotChanges<T> {
+    /// Items only in the new snapshot.
+    pub added: Vec<T>,
+    /// Items only in the old snapshot.
+    pub removed: Vec<T>,
+    /// Items in both snapshots that are not equal, as `(old, new)`.
+    pub changed: Vec<(T, T)>,
+}
+
+impl<T> Default for SnapshotChanges<T> {
+    fn default() -> Self {
+        SnapshotChanges {
+            added: Vec::new(),
+            removed: Vec::new(),
+            changed: Vec::new(),
+        }
+    }
+}
+
+impl<T> SnapshotChanges<T> {
+    /// Returns whether there are no changes.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// The differences between two reflection snapshots, created by [`ReflectionSnapshot::diff`].
+///
+/// Items are matched between snapshots as follows.
+/// * Entry points by name and execution model, so they are only ever added or removed.
+/// * Resources by descriptor set, binding and resource type.
+/// * Push constant members by offset.
+/// * Specialization constants by `constant_id`.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReflectionDiff {
+    /// Changes to the entry points.
+    pub entry_points: SnapshotChanges<SnapshotEntryPoint>,
+    /// Changes to the resources.
+    pub resources: SnapshotChanges<SnapshotResource>,
+    /// Changes to the push constant members.
+    pub push_constant_members: SnapshotChanges<SnapshotPushConstantMember>,
+    /// Changes to the specialization constants.
+    pub specialization_constants: SnapshotChanges<SnapshotSpecializationConstant>,
+}
+
+impl ReflectionDiff {
+    /// Returns whether the snapshots are equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.entry_points.is_empty()
+            && self.resources.is_empty()
+            && self.push_constant_members.is_empty()
+            && self.specialization_constants.is_empty()
+    }
+}
+
+/// Match items of two snapshots by key.
+fn diff_by<T: Clone + PartialEq, K: PartialEq>(
+    old: &[T],
+    new: &[T],
+    key: impl Fn(&T) -> K,
+) -> SnapshotChanges<T> {
+    let mut changes = SnapshotChanges::default();
+    for old_item in old {
+        match new.iter().find(|new_item| key(new_item) == key(old_item)) {
+            Some(new_item) if new_item != old_item => {
+                changes.changed.push((old_item.clone(), new_item.clone()))
+            }
+            Some(_) => {}
+            None => changes.removed.push(old_item.clone()),
+        }
+    }
+
+    for new_item in new {
+        if !old.iter().any(|old_item| key(old_item) == key(new_item)) {
+            changes.added.push(new_item.clone());
+        }
+    }
+
+    changes
+}
+
+impl ReflectionSnapshot {
+    /// Get the differences from this snapshot to `other`.
+    ///
+    /// Items only in `other` are reported as added, and items only in `self` as removed.
+    pub fn diff(&self, other: &ReflectionSnapshot) -> ReflectionDiff {
+        ReflectionDiff {
+            entry_points: diff_by(&self.entry_points, &other.entry_points, |entry_point| {
+                (entry_point.name.clone(), entry_point.execution_model)
+            }),
+            resources: diff_by(&self.resources, &other.resources, |resource| {
+                (resource.set, resource.binding, resource.resource_type)
+            }),
+            push_constant_members: diff_by(
+                &self.push_constant_members,
+                &other.push_constant_members,
+                |member| member.offset,
+            ),
+            specialization_constants: diff_by(
+                &self.specialization_constants,
+                &other.specialization_constants,
+                |constant| constant.constant_id,
+            ),
+        }
+    }
+}
+
+impl Display for SnapshotEntryPoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "entry point {} ({:?})", self.name, self.execution_model)
+    }
+}
+
+impl Display for SnapshotResource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "resource {} (set = {}, binding = {}, {:?} x {})",
+            self.name, self.set, self.binding, self.resource_type, self.descriptor_count
+        )
+    }
+}
+
+impl Display for SnapshotPushConstantMember {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "push constant member {} (offset = {}, size = {})",
+            self.name.as_deref().unwrap_or("<unnamed>"),
+            self.offset,
+            self.size
+        )
+    }
+}
+
+impl Display for SnapshotSpecializationConstant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "specialization constant {}", self.constant_id)?;
+        match self.default {
+            Some(default) => write!(f, " (default = {default:?})"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: Display> Display for SnapshotChanges<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for item in &self.added {
+            writeln!(f, "+ {item}")?;
+        }
+        for item in &self.removed {
+            writeln!(f, "- {item}")?;
+        }
+        for (old, new) in &self.changed {
+            writeln!(f, "~ {old} -> {new}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for ReflectionDiff {
+    /// Print one line per change, prefixed with `+` if added, `-` if removed,
+    /// and `~` if changed.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.entry_points)?;
+        write!(f, "{}", self.resources)?;
+        write!(f, "{}", self.push_constant_members)?;
+        write!(f, "{}", self.specialization_constants)
+    }
+}
+
+/// Reflection snapshots.
+impl<T> Compiler<T> {
+    /// Take a snapshot of the reflected interface of the current entry point.
+    ///
+    /// Resources are those returned by [`Compiler::binding_table`].
+    pub fn reflection_snapshot(&self) -> error::Result<ReflectionSnapshot> {
+        let mut snapshot = ReflectionSnapshot::default();
+        for entry_point in self.entry_points()? {
+            snapshot.entry_points.push(SnapshotEntryPoint {
+                name: entry_point.name.to_string(),
+                execution_model: entry_point.execution_model,
+            });
+        }
+
+        for binding in self.binding_table()? {
+            snapshot.resources.push(SnapshotResource {
+                name: binding.name.to_string(),
+                set: binding.set,
+                binding: binding.binding,
+                resource_type: binding.resource_type,
+                descriptor_count: binding.descriptor_count,
+            });
+        }
+
+        let resources = self.shader_resources()?;
+        for push_constant in resources.resources_for_type(ResourceType::PushConstant)? {
+            let TypeInner::Struct(block) = self.type_description(push_constant.base_type_id)?.inner
+            else {
+                continue;
+            };
+
+            for member in block.members {
+                snapshot
+                    .push_constant_members
+                    .push(SnapshotPushConstantMember {
+                        name: member.name.map(|name| name.to_string()),
+                        offset: member.offset,
+                        size: member.size,
+                    });
+            }
+        }
+
+        for constant in self.specialization_constants()? {
+            snapshot
+                .specialization_constants
+                .push(SnapshotSpecializationConstant {
+                    constant_id: constant.constant_id,
+                    default: self.specialization_constant_scalar_value(constant.id)?,
+                });
+        }
+
+        Ok(snapshot)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::SpirvCrossError;
+    use crate::Compiler;
+    use crate::{targets, Module};
+
+    static BASIC_SPV: &[u8] = include_bytes!("../../basic.spv");
+
+    #[test]
+    pub fn reflection_snapshot_diff() -> Result<(), SpirvCrossError> {
+        let vec = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&vec));
+
+        let mut compiler: Compiler<targets::None> = Compiler::new(words)?;
+        let old = compiler.reflection_snapshot()?;
+        assert!(old.diff(&old).is_empty());
+
+        // Moving the uniform buffer removes it from its binding, and adds it at a new binding.
+        let bindings = compiler.binding_table()?;
+        let (ubo, tex) = (bindings[0].id, bindings[1].id);
+        compiler.set_decoration(ubo, spirv::Decoration::Binding, Some(5u32))?;
+        compiler.set_name(tex, "renamed")?;
+
+        let new = compiler.reflection_snapshot()?;
+        let diff = old.diff(&new);
+
+        assert_eq!(1, diff.resources.added.len());
+        assert_eq!(5, diff.resources.added[0].binding);
+        assert_eq!(1, diff.resources.removed.len());
+        assert_eq!(0, diff.resources.removed[0].binding);
+
+        assert_eq!(1, diff.resources.changed.len());
+        let (old_tex, new_tex) = &diff.resources.changed[0];
+        assert_eq!(("tex", "renamed"), (&*old_tex.name, &*new_tex.name));
+
+        assert!(diff.entry_points.is_empty());
+        assert_eq!(3, diff.to_string().lines().count());
+
+        Ok(())
+    }
+}
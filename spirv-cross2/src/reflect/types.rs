@@ -6,12 +6,17 @@ use spirv_cross_sys::{BaseType, SpvId, VariableId};
 use crate::error::{SpirvCrossError, ToContextError};
 use crate::handle::Handle;
 use crate::handle::{ConstantId, TypeId};
+use crate::reflect::Instructions;
 use crate::sealed::Sealed;
 use crate::string::CompilerStr;
 use spirv_cross_sys as sys;
+use std::collections::HashMap;
+
+mod naga;
 
 /// The kind of scalar
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum ScalarKind {
     /// Signed integer.
@@ -26,6 +31,7 @@ pub enum ScalarKind {
 
 /// The bit width of a scalar.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum BitWidth {
     /// 1 bit, padded to 1 byte.
@@ -57,6 +63,7 @@ impl BitWidth {
 
 /// A scalar type.
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Scalar {
     /// How the value’s bits are to be interpreted.
     pub kind: ScalarKind,
@@ -129,6 +136,7 @@ impl TryFrom<BaseType> for Scalar {
 
 /// A type definition.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Type<'a> {
     /// The SPIR-V ID of the type.
     pub id: Handle<TypeId>,
@@ -143,6 +151,7 @@ pub struct Type<'a> {
 
 /// Type definition for a struct member.
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StructMember<'a> {
     /// The type ID of the struct member.
     pub id: Handle<TypeId>,
@@ -168,6 +177,7 @@ pub struct StructMember<'a> {
 
 /// Type definition for a struct.
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StructType<'a> {
     /// The type ID of the struct.
     pub id: Handle<TypeId>,
@@ -183,6 +193,7 @@ pub struct StructType<'a> {
 /// If an array dimension is specified as a specialization constant,
 /// then the dimension will be [`ArrayDimension::Constant`].
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ArrayDimension {
     /// A literal array dimension, i.e. `array[4]`.
     Literal(u32),
@@ -199,6 +210,7 @@ pub enum ArrayDimension {
 
 /// Class of image or texture handle.
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ImageClass {
     /// Combined image samplers.
     Sampled {
@@ -220,11 +232,45 @@ pub enum ImageClass {
     Storage {
         /// The image format of the storage image.
         format: spirv::ImageFormat,
+        /// The access qualifier of the storage image.
+        ///
+        /// SPIR-V declares access with the `NonReadable` and `NonWritable` decorations
+        /// on the image variable rather than on the type, so this is only known if the
+        /// image type was obtained from a variable with [`Compiler::variable_image_type`],
+        /// and is `None` when the image type is described by [`Compiler::type_description`].
+        access: Option<ImageAccess>,
     },
 }
 
+/// The access qualifier of a storage image.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ImageAccess {
+    /// The image is decorated `NonWritable`, i.e. `readonly` in GLSL.
+    ///
+    /// This is also reported if the image is decorated both `NonWritable` and `NonReadable`.
+    ReadOnly,
+    /// The image is decorated `NonReadable`, i.e. `writeonly` in GLSL.
+    WriteOnly,
+    /// The image may be both read from and written to.
+    ReadWrite,
+}
+
+/// How an image is used, as declared by the `Sampled` operand of `OpTypeImage`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ImageSampledUsage {
+    /// The usage is only known at run time (`Sampled = 0`).
+    Unknown,
+    /// The image is used with a sampler (`Sampled = 1`).
+    Sampling,
+    /// The image is used without a sampler, i.e. as a storage image (`Sampled = 2`).
+    Storage,
+}
+
 /// Type definition for an image or texture handle.
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ImageType {
     /// The id of the type.
     pub id: Handle<TypeId>,
@@ -234,6 +280,19 @@ pub struct ImageType {
     pub dimension: spirv::Dim,
     /// The class of the image.
     pub class: ImageClass,
+    /// The declared usage of the image.
+    ///
+    /// Unlike [`ImageType::class`], this distinguishes images whose usage
+    /// is only known at run time.
+    pub sampled: ImageSampledUsage,
+}
+
+impl ImageType {
+    /// Returns whether the image is an input attachment, i.e. has the dimension
+    /// [`Dim::DimSubpassData`](spirv::Dim::DimSubpassData).
+    pub fn is_subpass_data(&self) -> bool {
+        self.dimension == spirv::Dim::DimSubpassData
+    }
 }
 
 /// Enum with additional type information, depending on the kind of type.
@@ -241,6 +300,7 @@ pub struct ImageType {
 /// The design of this API is inspired heavily by [`naga::TypeInner`](https://docs.rs/naga/latest/naga/enum.TypeInner.html),
 /// with some changes to fit SPIR-V.
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TypeInner<'a> {
     /// Unknown type.
     Unknown,
@@ -316,6 +376,23 @@ pub enum TypeInner<'a> {
     Sampler,
 }
 
+impl TypeInner<'_> {
+    /// Returns whether the type is an opaque handle that occupies a descriptor slot,
+    /// rather than plain data that occupies bytes in a buffer.
+    ///
+    /// This is true for images, samplers, acceleration structures,
+    /// and pointers into [`StorageClass::UniformConstant`].
+    ///
+    /// Arrays are not resolved, check the base type of the array instead.
+    pub fn is_opaque(&self) -> bool {
+        match self {
+            TypeInner::Image(_) | TypeInner::Sampler | TypeInner::AccelerationStructure => true,
+            TypeInner::Pointer { storage, .. } => *storage == StorageClass::UniformConstant,
+            _ => false,
+        }
+    }
+}
+
 /// A size hole requiring the stride of a matrix,
 /// and whether the matrix is column or row major.
 ///
@@ -324,6 +401,7 @@ pub enum TypeInner<'a> {
 /// matrix is row major. By default, the matrix is
 /// considered column major.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MatrixStrideHole {
     columns: usize,
     rows: usize,
@@ -352,6 +430,7 @@ impl ResolveSize for MatrixStrideHole {
 ///
 /// This hole must be resolved with the size of the array.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ArraySizeHole {
     stride: usize,
     declared: usize,
@@ -365,6 +444,7 @@ pub struct ArraySizeHole {
 /// The declared size of this hole is the number of elements
 /// times the declared size of the base type.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UnknownStrideHole {
     hint: Box<TypeSizeHint>,
     count: usize,
@@ -418,6 +498,7 @@ impl Sealed for usize {}
 /// If an array stride is found, it will calculate a statically known size with
 /// the array stride.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TypeSizeHint {
     /// A statically known type size hint.
     Static(usize),
@@ -464,7 +545,7 @@ impl<T> Compiler<T> {
     // None of the names here belong to the context, they belong to the compiler.
     // so 'ctx is unsound to return.
 
-    fn process_struct(&self, struct_ty_id: TypeId) -> error::Result<StructType> {
+    fn process_struct(&self, struct_ty_id: TypeId) -> error::Result<StructType<'static>> {
         unsafe {
             let ty = sys::spvc_compiler_get_type_handle(self.ptr.as_ptr(), struct_ty_id);
             let base_ty = sys::spvc_type_get_basetype(ty);
@@ -544,7 +625,7 @@ impl<T> Compiler<T> {
         }
     }
 
-    fn process_vector(&self, id: TypeId, vec_width: u32) -> error::Result<TypeInner> {
+    fn process_vector(&self, id: TypeId, vec_width: u32) -> error::Result<TypeInner<'static>> {
         unsafe {
             let ty = sys::spvc_compiler_get_type_handle(self.ptr.as_ptr(), id);
             let base_ty = sys::spvc_type_get_basetype(ty);
@@ -555,7 +636,12 @@ impl<T> Compiler<T> {
         }
     }
 
-    fn process_matrix(&self, id: TypeId, rows: u32, columns: u32) -> error::Result<TypeInner> {
+    fn process_matrix(
+        &self,
+        id: TypeId,
+        rows: u32,
+        columns: u32,
+    ) -> error::Result<TypeInner<'static>> {
         unsafe {
             let ty = sys::spvc_compiler_get_type_handle(self.ptr.as_ptr(), id);
             let base_ty = sys::spvc_type_get_basetype(ty);
@@ -632,6 +718,40 @@ impl<T> Compiler<T> {
         }
     }
 
+    /// Find the `Sampled` operand of the `OpTypeImage` declaring the given type,
+    /// following `OpTypeSampledImage` to its image type.
+    ///
+    /// The operands of every image type are found in a single scan of the module,
+    /// the first time this is called.
+    fn image_sampled_operand(&self, id: TypeId) -> error::Result<Option<u32>> {
+        if self.image_sampled_operands.borrow().is_none() {
+            let mut sampled_operands = HashMap::new();
+            for instruction in Instructions::new(self.original_words()) {
+                let (op, operands) = instruction?;
+                match (op, operands) {
+                    (Some(spirv::Op::TypeImage), [image, _, _, _, _, _, sampled, ..]) => {
+                        sampled_operands.insert(*image, *sampled);
+                    }
+                    // OpTypeSampledImage always comes after its image type.
+                    (Some(spirv::Op::TypeSampledImage), [sampled_image, image, ..]) => {
+                        if let Some(&sampled) = sampled_operands.get(image) {
+                            sampled_operands.insert(*sampled_image, sampled);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            *self.image_sampled_operands.borrow_mut() = Some(sampled_operands);
+        }
+
+        Ok(self
+            .image_sampled_operands
+            .borrow()
+            .as_ref()
+            .and_then(|sampled_operands| sampled_operands.get(&id.0 .0).copied()))
+    }
+
     fn process_image(&self, id: TypeId) -> error::Result<ImageType> {
         unsafe {
             let ty = sys::spvc_compiler_get_type_handle(self.ptr.as_ptr(), id);
@@ -659,7 +779,10 @@ impl<T> Compiler<T> {
             };
 
             let class = if storage {
-                ImageClass::Storage { format }
+                ImageClass::Storage {
+                    format,
+                    access: None,
+                }
             } else if base_ty == BaseType::SampledImage {
                 ImageClass::Sampled {
                     depth,
@@ -673,11 +796,26 @@ impl<T> Compiler<T> {
                 }
             };
 
+            let sampled = match self.image_sampled_operand(id)? {
+                Some(0) => ImageSampledUsage::Unknown,
+                Some(1) => ImageSampledUsage::Sampling,
+                Some(2) => ImageSampledUsage::Storage,
+                Some(sampled) => {
+                    return Err(SpirvCrossError::InvalidSpirv(format!(
+                        "Unknown image sampled operand found: {sampled}"
+                    )))
+                }
+                // The type was not declared in the original module.
+                None if storage => ImageSampledUsage::Storage,
+                None => ImageSampledUsage::Sampling,
+            };
+
             Ok(ImageType {
                 id: self.create_handle(id),
                 sampled_type: self.create_handle(sampled_id),
                 dimension,
                 class,
+                sampled,
             })
         }
     }
@@ -691,7 +829,25 @@ impl<T> Compiler<T> {
     /// usually with a scalar base type.
     pub fn type_description(&self, id: Handle<TypeId>) -> error::Result<Type> {
         let id = self.yield_id(id)?;
+        if let Some(ty) = self.type_cache.borrow().get(&id) {
+            return Ok(ty.clone());
+        }
+
+        // Nested types are cached as they are resolved, so the cache must not be borrowed here.
+        //
+        // Names are borrowed from the compiler rather than copied, so cache hits do not allocate
+        // strings. They are only valid until the compiler is mutated, which always goes through
+        // `Compiler::ptr_mut` and clears the cache, and entries are only handed out for the
+        // lifetime of the borrow of the compiler.
+        let ty = self.type_description_uncached(id)?;
+        self.type_cache.borrow_mut().insert(id, ty.clone());
+        Ok(ty)
+    }
 
+    /// Describe a type, with names borrowed from the compiler.
+    ///
+    /// The names are only valid until they are changed, see [`Compiler::type_description`].
+    fn type_description_uncached(&self, id: TypeId) -> error::Result<Type<'static>> {
         unsafe {
             let ty = sys::spvc_compiler_get_type_handle(self.ptr.as_ptr(), id);
             let base_type_id = sys::spvc_type_get_base_type_id(ty);
@@ -826,6 +982,8 @@ impl<T> Compiler<T> {
     /// This will resolve array sizes involving specialization constants.
     fn type_size_hint(&self, ty: &TypeInner) -> error::Result<TypeSizeHint> {
         Ok(match ty {
+            // The base type of a pointer is never followed, so self-referential
+            // types through forward declared pointers always terminate.
             TypeInner::Pointer { .. } => TypeSizeHint::Static(BitWidth::Word.byte_size()),
             TypeInner::Struct(s) => {
                 if let Some(stride) = self.struct_has_runtime_array(s)? {
@@ -918,6 +1076,24 @@ impl<T> Compiler<T> {
         Ok(None)
     }
 
+    /// Get the number of locations consumed by an interface variable of the given type.
+    pub(crate) fn io_location_count(&self, type_id: Handle<TypeId>) -> error::Result<u32> {
+        let mut ty = self.type_description(type_id)?.inner;
+        let mut count = 1;
+        if let TypeInner::Array { base, .. } = ty {
+            count = self.descriptor_count(type_id)?;
+            ty = self.type_description(base)?.inner;
+        }
+
+        let element = match ty {
+            TypeInner::Matrix { columns, .. } => columns,
+            TypeInner::Vector { width, scalar } if width > 2 && scalar.size.byte_size() == 8 => 2,
+            _ => 1,
+        };
+
+        Ok(count.saturating_mul(element))
+    }
+
     /// Get the underlying type of the variable.
     pub fn variable_type(
         &self,
@@ -934,16 +1110,200 @@ impl<T> Compiler<T> {
             Ok(self.create_handle(type_id))
         }
     }
+
+    /// Get the storage class of the variable.
+    ///
+    /// The storage class is read from the pointer type of the variable, as returned by
+    /// [`Compiler::variable_type`].
+    pub fn variable_storage_class(
+        &self,
+        variable: impl Into<Handle<VariableId>>,
+    ) -> error::Result<spirv::StorageClass> {
+        let type_id = self.variable_type(variable)?;
+        let type_id = self.yield_id(type_id)?;
+
+        unsafe {
+            let ty = sys::spvc_compiler_get_type_handle(self.ptr.as_ptr(), type_id);
+            let storage_class = sys::spvc_type_get_storage_class(ty);
+
+            spirv::StorageClass::from_u32(storage_class.0 as u32).ok_or_else(|| {
+                SpirvCrossError::InvalidSpirv(format!(
+                    "Unknown StorageClass found: {}",
+                    storage_class.0
+                ))
+            })
+        }
+    }
+
+    /// Get the image type of an image variable, or array of images.
+    ///
+    /// Unlike [`Compiler::type_description`], the [`ImageAccess`] of a storage image is
+    /// known, and is derived from the `NonReadable` and `NonWritable` decorations on the variable.
+    ///
+    /// If the variable is not an image, sampled image, or array of either,
+    /// returns [`SpirvCrossError::InvalidArgument`].
+    pub fn variable_image_type(
+        &self,
+        image_variable: impl Into<Handle<VariableId>>,
+    ) -> error::Result<ImageType> {
+        let image_variable = image_variable.into();
+        let type_id = self.variable_type(image_variable)?;
+        let type_id = self.yield_id(type_id)?;
+
+        let mut image = unsafe {
+            let ty = sys::spvc_compiler_get_type_handle(self.ptr.as_ptr(), type_id);
+            // Arrays refer to the image type as their base type.
+            let base_type_id = sys::spvc_type_get_base_type_id(ty);
+            let base_ty = sys::spvc_compiler_get_type_handle(self.ptr.as_ptr(), base_type_id);
+
+            match sys::spvc_type_get_basetype(base_ty) {
+                BaseType::Image | BaseType::SampledImage => self.process_image(base_type_id)?,
+                _ => {
+                    return Err(SpirvCrossError::InvalidArgument(String::from(
+                        "The variable is not an image or sampled image",
+                    )))
+                }
+            }
+        };
+
+        if let ImageClass::Storage { access, .. } = &mut image.class {
+            let non_readable = self
+                .decoration(image_variable, spirv::Decoration::NonReadable)?
+                .is_some();
+            let non_writable = self
+                .decoration(image_variable, spirv::Decoration::NonWritable)?
+                .is_some();
+
+            *access = Some(match (non_readable, non_writable) {
+                (_, true) => ImageAccess::ReadOnly,
+                (true, false) => ImageAccess::WriteOnly,
+                (false, false) => ImageAccess::ReadWrite,
+            });
+        }
+
+        Ok(image)
+    }
+
+    /// Get the scalar type returned when sampling or reading from an image variable.
+    ///
+    /// This is the component type of the image, for example a `float` for `sampler2D`, or
+    /// a `uint` for `usampler2D`. Arrays of images return the component type of the element.
+    ///
+    /// If the variable is not an image, sampled image, or array of either,
+    /// returns [`SpirvCrossError::InvalidArgument`].
+    pub fn sampled_component_type(
+        &self,
+        image_variable: impl Into<Handle<VariableId>>,
+    ) -> error::Result<Scalar> {
+        let image = self.variable_image_type(image_variable)?;
+
+        match self.type_description(image.sampled_type)?.inner {
+            TypeInner::Scalar(scalar) => Ok(scalar),
+            _ => Err(SpirvCrossError::InvalidSpirv(String::from(
+                "The sampled type of the image is not a scalar",
+            ))),
+        }
+    }
+
+    /// Returns whether the interface variable must be declared with `flat` interpolation
+    /// in GLSL, i.e. it contains integer or 64-bit scalars.
+    ///
+    /// This ignores any [`Flat`](spirv::Decoration::Flat) decoration on the variable, so it
+    /// can be used to find integer varyings that are missing one.
+    pub fn requires_flat_interpolation(
+        &self,
+        variable: impl Into<Handle<VariableId>>,
+    ) -> error::Result<bool> {
+        let type_id = self.variable_type(variable)?;
+        self.type_requires_flat_interpolation(type_id)
+    }
+
+    fn type_requires_flat_interpolation(&self, type_id: Handle<TypeId>) -> error::Result<bool> {
+        let scalar = match self.type_description(type_id)?.inner {
+            TypeInner::Scalar(scalar)
+            | TypeInner::Vector { scalar, .. }
+            | TypeInner::Matrix { scalar, .. } => scalar,
+            // Buffer device addresses are 64-bit.
+            TypeInner::Pointer {
+                storage: StorageClass::PhysicalStorageBuffer,
+                ..
+            } => return Ok(true),
+            TypeInner::Pointer { base, .. } | TypeInner::Array { base, .. } => {
+                return self.type_requires_flat_interpolation(base)
+            }
+            TypeInner::Struct(ty) => {
+                for member in ty.members {
+                    if self.type_requires_flat_interpolation(member.id)? {
+                        return Ok(true);
+                    }
+                }
+                return Ok(false);
+            }
+            _ => return Ok(false),
+        };
+
+        Ok(matches!(scalar.kind, ScalarKind::Int | ScalarKind::Uint)
+            || scalar.size.byte_size() == 8)
+    }
 }
 
 #[cfg(test)]
 mod test {
     use crate::error::SpirvCrossError;
+    use crate::reflect::{BitWidth, Scalar, ScalarKind, TypeInner};
     use crate::Compiler;
     use crate::{targets, Module};
 
     static BASIC_SPV: &[u8] = include_bytes!("../../basic.spv");
 
+    #[test]
+    pub fn type_is_opaque() -> Result<(), SpirvCrossError> {
+        let vec = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&vec));
+
+        let compiler: Compiler<targets::None> = Compiler::new(words)?;
+        let resources = compiler.shader_resources()?.all_resources()?;
+
+        let ubo = &resources.uniform_buffers[0];
+        assert!(!compiler
+            .type_description(ubo.base_type_id)?
+            .inner
+            .is_opaque());
+
+        let tex = &resources.sampled_images[0];
+        assert!(compiler
+            .type_description(tex.base_type_id)?
+            .inner
+            .is_opaque());
+
+        let tex_pointer = compiler.type_description(compiler.variable_type(tex.id)?)?;
+        assert!(matches!(tex_pointer.inner, TypeInner::Pointer { .. }));
+        assert!(tex_pointer.inner.is_opaque());
+
+        let ubo_pointer = compiler.type_description(compiler.variable_type(ubo.id)?)?;
+        assert!(!ubo_pointer.inner.is_opaque());
+
+        assert!(TypeInner::Sampler.is_opaque());
+        assert!(TypeInner::AccelerationStructure.is_opaque());
+        assert!(!TypeInner::Void.is_opaque());
+        assert!(!TypeInner::Unknown.is_opaque());
+        assert!(!TypeInner::Scalar(Scalar {
+            kind: ScalarKind::Float,
+            size: BitWidth::Word,
+        })
+        .is_opaque());
+        assert!(!TypeInner::Vector {
+            width: 4,
+            scalar: Scalar {
+                kind: ScalarKind::Float,
+                size: BitWidth::Word,
+            },
+        }
+        .is_opaque());
+
+        Ok(())
+    }
+
     #[test]
     pub fn get_stage_outputs() -> Result<(), SpirvCrossError> {
         let vec = Vec::from(BASIC_SPV);
@@ -998,6 +1358,64 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    pub fn type_description_cache_invalidated() -> Result<(), SpirvCrossError> {
+        let vec = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&vec));
+
+        let mut compiler: Compiler<targets::None> = Compiler::new(words)?;
+        let resources = compiler.shader_resources()?.all_resources()?;
+        let id = resources.uniform_buffers[0].base_type_id;
+
+        let TypeInner::Struct(ty) = compiler.type_description(id)?.inner else {
+            panic!("expected a struct type");
+        };
+        assert_eq!(Some("MVP"), ty.members[0].name.as_deref());
+        assert_eq!(0, ty.members[0].offset);
+
+        compiler.set_member_name(id, 0, "NotMVP")?;
+        compiler.set_member_decoration_by_handle(id, 0, spirv::Decoration::Offset, Some(16))?;
+
+        let TypeInner::Struct(ty) = compiler.type_description(id)?.inner else {
+            panic!("expected a struct type");
+        };
+        assert_eq!(Some("NotMVP"), ty.members[0].name.as_deref());
+        assert_eq!(16, ty.members[0].offset);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn type_description_cache_observes_renames() -> Result<(), SpirvCrossError> {
+        let vec = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&vec));
+
+        let mut compiler: Compiler<targets::None> = Compiler::new(words)?;
+        let resources = compiler.shader_resources()?.all_resources()?;
+        let variable = resources.uniform_buffers[0].id;
+        let id = resources.uniform_buffers[0].base_type_id;
+
+        // Cache the struct through the pointer type of the variable, then rename it.
+        let pointer = compiler.variable_type(variable)?;
+        compiler.type_description(pointer)?;
+        let TypeInner::Struct(ty) = compiler.type_description(id)?.inner else {
+            panic!("expected a struct type");
+        };
+        assert_eq!(Some("MVP"), ty.members[0].name.as_deref());
+
+        compiler.set_member_name(id, 0, "Renamed")?;
+        compiler.set_name(id, "RenamedBlock")?;
+
+        let ty = compiler.type_description(id)?;
+        assert_eq!(Some("RenamedBlock"), ty.name.as_deref());
+        let TypeInner::Struct(ty) = ty.inner else {
+            panic!("expected a struct type");
+        };
+        assert_eq!(Some("Renamed"), ty.members[0].name.as_deref());
+
+        Ok(())
+    }
+
     #[test]
     pub fn get_variable_type_test() -> Result<(), SpirvCrossError> {
         let vec = Vec::from(BASIC_SPV);
@@ -1015,4 +1433,52 @@ mod test {
         eprintln!("{:?}", resources);
         Ok(())
     }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "serde-json"))]
+    pub fn serialize_type() -> Result<(), SpirvCrossError> {
+        use crate::reflect::Type;
+
+        let vec = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&vec));
+
+        let compiler: Compiler<targets::None> = Compiler::new(words)?;
+        let resources = compiler.shader_resources()?.all_resources()?;
+        let ty = compiler.type_description(resources.uniform_buffers[0].base_type_id)?;
+
+        let json = serde_json::to_string(&ty).unwrap();
+        let deserialized: Type = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(ty.id.id(), deserialized.id.id());
+        assert_eq!(ty.name, deserialized.name);
+        assert_eq!(ty.inner, deserialized.inner);
+
+        // Deserialized handles do not belong to the compiler.
+        assert!(matches!(
+            compiler.type_description(deserialized.id),
+            Err(SpirvCrossError::HandleFromWrongCompiler { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn variable_storage_class() -> Result<(), SpirvCrossError> {
+        let vec = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&vec));
+
+        let compiler: Compiler<targets::None> = Compiler::new(words)?;
+        let resources = compiler.shader_resources()?.all_resources()?;
+
+        assert_eq!(
+            spirv::StorageClass::Uniform,
+            compiler.variable_storage_class(resources.uniform_buffers[0].id)?
+        );
+        assert_eq!(
+            spirv::StorageClass::UniformConstant,
+            compiler.variable_storage_class(resources.sampled_images[0].id)?
+        );
+
+        Ok(())
+    }
 }
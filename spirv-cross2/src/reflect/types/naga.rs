@@ -0,0 +1,350 @@
+#![cfg(feature = "naga")]
+#![cfg_attr(docsrs, doc(cfg(feature = "naga")))]
+use crate::error::SpirvCrossError;
+use crate::handle::{Handle, TypeId};
+use crate::reflect::{
+    ArrayDimension, ImageAccess, ImageClass, ImageType, Scalar, ScalarKind, StructType, TypeInner,
+};
+use crate::{error, Compiler};
+use naga::UniqueArena;
+use spirv::{Dim, ImageFormat, StorageClass};
+use spirv_cross_sys as sys;
+
+impl From<ScalarKind> for naga::ScalarKind {
+    fn from(value: ScalarKind) -> Self {
+        match value {
+            ScalarKind::Int => naga::ScalarKind::Sint,
+            ScalarKind::Uint => naga::ScalarKind::Uint,
+            ScalarKind::Float => naga::ScalarKind::Float,
+            ScalarKind::Bool => naga::ScalarKind::Bool,
+        }
+    }
+}
+
+impl From<Scalar> for naga::Scalar {
+    fn from(value: Scalar) -> Self {
+        naga::Scalar {
+            kind: value.kind.into(),
+            width: value.size.byte_size() as naga::Bytes,
+        }
+    }
+}
+
+fn unsupported(what: String) -> SpirvCrossError {
+    SpirvCrossError::InvalidArgument(format!("{what} can not be represented in naga"))
+}
+
+fn vector_size(size: u32) -> error::Result<naga::VectorSize> {
+    match size {
+        2 => Ok(naga::VectorSize::Bi),
+        3 => Ok(naga::VectorSize::Tri),
+        4 => Ok(naga::VectorSize::Quad),
+        _ => Err(unsupported(format!("A vector of size {size}"))),
+    }
+}
+
+fn address_space(storage: StorageClass) -> error::Result<naga::AddressSpace> {
+    Ok(match storage {
+        StorageClass::Function => naga::AddressSpace::Function,
+        StorageClass::Private => naga::AddressSpace::Private,
+        StorageClass::Workgroup => naga::AddressSpace::WorkGroup,
+        StorageClass::Uniform => naga::AddressSpace::Uniform,
+        StorageClass::StorageBuffer => naga::AddressSpace::Storage {
+            access: naga::StorageAccess::LOAD | naga::StorageAccess::STORE,
+        },
+        StorageClass::UniformConstant => naga::AddressSpace::Handle,
+        StorageClass::PushConstant => naga::AddressSpace::PushConstant,
+        _ => return Err(unsupported(format!("The storage class {storage:?}"))),
+    })
+}
+
+fn storage_format(format: ImageFormat) -> error::Result<naga::StorageFormat> {
+    Ok(match format {
+        ImageFormat::R8 => naga::StorageFormat::R8Unorm,
+        ImageFormat::R8Snorm => naga::StorageFormat::R8Snorm,
+        ImageFormat::R8ui => naga::StorageFormat::R8Uint,
+        ImageFormat::R8i => naga::StorageFormat::R8Sint,
+        ImageFormat::R16 => naga::StorageFormat::R16Unorm,
+        ImageFormat::R16Snorm => naga::StorageFormat::R16Snorm,
+        ImageFormat::R16ui => naga::StorageFormat::R16Uint,
+        ImageFormat::R16i => naga::StorageFormat::R16Sint,
+        ImageFormat::R16f => naga::StorageFormat::R16Float,
+        ImageFormat::Rg8 => naga::StorageFormat::Rg8Unorm,
+        ImageFormat::Rg8Snorm => naga::StorageFormat::Rg8Snorm,
+        ImageFormat::Rg8ui => naga::StorageFormat::Rg8Uint,
+        ImageFormat::Rg8i => naga::StorageFormat::Rg8Sint,
+        ImageFormat::R32ui => naga::StorageFormat::R32Uint,
+        ImageFormat::R32i => naga::StorageFormat::R32Sint,
+        ImageFormat::R32f => naga::StorageFormat::R32Float,
+        ImageFormat::Rg16 => naga::StorageFormat::Rg16Unorm,
+        ImageFormat::Rg16Snorm => naga::StorageFormat::Rg16Snorm,
+        ImageFormat::Rg16ui => naga::StorageFormat::Rg16Uint,
+        ImageFormat::Rg16i => naga::StorageFormat::Rg16Sint,
+        ImageFormat::Rg16f => naga::StorageFormat::Rg16Float,
+        ImageFormat::Rgba8 => naga::StorageFormat::Rgba8Unorm,
+        ImageFormat::Rgba8Snorm => naga::StorageFormat::Rgba8Snorm,
+        ImageFormat::Rgba8ui => naga::StorageFormat::Rgba8Uint,
+        ImageFormat::Rgba8i => naga::StorageFormat::Rgba8Sint,
+        ImageFormat::Rgb10A2 => naga::StorageFormat::Rgb10a2Unorm,
+        ImageFormat::Rgb10a2ui => naga::StorageFormat::Rgb10a2Uint,
+        ImageFormat::Rg32ui => naga::StorageFormat::Rg32Uint,
+        ImageFormat::Rg32i => naga::StorageFormat::Rg32Sint,
+        ImageFormat::Rg32f => naga::StorageFormat::Rg32Float,
+        ImageFormat::Rgba16 => naga::StorageFormat::Rgba16Unorm,
+        ImageFormat::Rgba16Snorm => naga::StorageFormat::Rgba16Snorm,
+        ImageFormat::Rgba16ui => naga::StorageFormat::Rgba16Uint,
+        ImageFormat::Rgba16i => naga::StorageFormat::Rgba16Sint,
+        ImageFormat::Rgba16f => naga::StorageFormat::Rgba16Float,
+        ImageFormat::Rgba32ui => naga::StorageFormat::Rgba32Uint,
+        ImageFormat::Rgba32i => naga::StorageFormat::Rgba32Sint,
+        ImageFormat::Rgba32f => naga::StorageFormat::Rgba32Float,
+        _ => return Err(unsupported(format!("The image format {format:?}"))),
+    })
+}
+
+impl<T> Compiler<T> {
+    /// Convert the type with the given ID into a [`naga::Type`], inserting it
+    /// and every type it refers to into `types`.
+    ///
+    /// Array dimensions given by specialization constants are resolved to the
+    /// current value of the constant. Combined image samplers are converted to
+    /// their image type, since naga has no combined image sampler type.
+    ///
+    /// The access of a storage image is a property of its variable rather than its type,
+    /// so storage images are always converted with both load and store access.
+    ///
+    /// If the type has no equivalent in naga, for example a pointer into the `Input` storage
+    /// class or an image with an unknown format, returns [`SpirvCrossError::InvalidArgument`].
+    pub fn naga_type(
+        &self,
+        id: Handle<TypeId>,
+        types: &mut UniqueArena<naga::Type>,
+    ) -> error::Result<naga::Handle<naga::Type>> {
+        let ty = self.type_description(id)?;
+        let name = ty.name.map(|name| name.to_string());
+
+        let inner = match ty.inner {
+            TypeInner::Scalar(scalar) => naga::TypeInner::Scalar(scalar.into()),
+            TypeInner::Vector { width, scalar } => naga::TypeInner::Vector {
+                size: vector_size(width)?,
+                scalar: scalar.into(),
+            },
+            TypeInner::Matrix {
+                columns,
+                rows,
+                scalar,
+            } => naga::TypeInner::Matrix {
+                columns: vector_size(columns)?,
+                rows: vector_size(rows)?,
+                scalar: scalar.into(),
+            },
+            TypeInner::Pointer {
+                base,
+                storage,
+                forward: false,
+            } => naga::TypeInner::Pointer {
+                base: self.naga_type(base, types)?,
+                space: address_space(storage)?,
+            },
+            TypeInner::Array {
+                base,
+                dimensions,
+                stride,
+                ..
+            } => return self.naga_array_type(name, base, &dimensions, stride, types),
+            TypeInner::Struct(ty) => self.naga_struct_type(ty, types)?,
+            TypeInner::Image(image) => self.naga_image_type(image)?,
+            TypeInner::Sampler => naga::TypeInner::Sampler { comparison: false },
+            TypeInner::AccelerationStructure => naga::TypeInner::AccelerationStructure,
+            inner => return Err(unsupported(format!("The type {inner:?}"))),
+        };
+
+        Ok(types.insert(naga::Type { name, inner }, naga::Span::UNDEFINED))
+    }
+
+    fn naga_array_type(
+        &self,
+        mut name: Option<String>,
+        base: Handle<TypeId>,
+        dimensions: &[ArrayDimension],
+        stride: Option<u32>,
+        types: &mut UniqueArena<naga::Type>,
+    ) -> error::Result<naga::Handle<naga::Type>> {
+        let mut lengths = Vec::with_capacity(dimensions.len());
+        for dimension in dimensions {
+            lengths.push(match dimension {
+                ArrayDimension::Literal(length) => *length,
+                ArrayDimension::Constant(constant) => {
+                    self.specialization_constant_value::<u32>(*constant)?
+                }
+            });
+        }
+
+        // Dimensions are innermost first, but only the stride of the outermost array is known.
+        let mut strides = vec![0; lengths.len()];
+        if let Some(stride) = stride {
+            // The element of each dimension is the array of the next-inner dimension,
+            // so the next-inner stride is this stride divided by the next-inner length.
+            let mut stride = stride;
+            for index in (0..lengths.len()).rev() {
+                strides[index] = stride;
+                if let Some(inner) = index.checked_sub(1) {
+                    stride /= lengths[inner].max(1);
+                }
+            }
+        } else {
+            let mut stride = self.type_description(base)?.size_hint.declared() as u32;
+            for (index, length) in lengths.iter().enumerate() {
+                strides[index] = stride;
+                stride *= *length;
+            }
+        }
+
+        let mut handle = self.naga_type(base, types)?;
+        let outermost = lengths.len().saturating_sub(1);
+        for (index, (length, stride)) in lengths.into_iter().zip(strides).enumerate() {
+            let size = match std::num::NonZeroU32::new(length) {
+                Some(length) => naga::ArraySize::Constant(length),
+                None => naga::ArraySize::Dynamic,
+            };
+
+            let inner = naga::TypeInner::Array {
+                base: handle,
+                size,
+                stride,
+            };
+
+            let name = if index == outermost {
+                name.take()
+            } else {
+                None
+            };
+            handle = types.insert(naga::Type { name, inner }, naga::Span::UNDEFINED);
+        }
+
+        Ok(handle)
+    }
+
+    fn naga_struct_type(
+        &self,
+        ty: StructType,
+        types: &mut UniqueArena<naga::Type>,
+    ) -> error::Result<naga::TypeInner> {
+        let mut members = Vec::with_capacity(ty.members.len());
+        for member in ty.members {
+            members.push(naga::StructMember {
+                name: member.name.map(|name| name.to_string()),
+                ty: self.naga_type(member.id, types)?,
+                binding: None,
+                offset: member.offset,
+            });
+        }
+
+        Ok(naga::TypeInner::Struct {
+            members,
+            span: ty.size as u32,
+        })
+    }
+
+    fn naga_image_type(&self, image: ImageType) -> error::Result<naga::TypeInner> {
+        let dim = match image.dimension {
+            Dim::Dim1D => naga::ImageDimension::D1,
+            Dim::Dim2D => naga::ImageDimension::D2,
+            Dim::Dim3D => naga::ImageDimension::D3,
+            Dim::DimCube => naga::ImageDimension::Cube,
+            dim => return Err(unsupported(format!("The image dimension {dim:?}"))),
+        };
+
+        // Storage images do not carry these in the ImageClass.
+        let (arrayed, depth) = unsafe {
+            let id = self.yield_id(image.id)?;
+            let ty = sys::spvc_compiler_get_type_handle(self.ptr.as_ptr(), id);
+            (
+                sys::spvc_type_get_image_arrayed(ty),
+                sys::spvc_type_get_image_is_depth(ty),
+            )
+        };
+
+        let class = match image.class {
+            ImageClass::Sampled { multisampled, .. } | ImageClass::Texture { multisampled, .. }
+                if depth =>
+            {
+                naga::ImageClass::Depth {
+                    multi: multisampled,
+                }
+            }
+            ImageClass::Sampled { multisampled, .. } | ImageClass::Texture { multisampled, .. } => {
+                let TypeInner::Scalar(scalar) = self.type_description(image.sampled_type)?.inner
+                else {
+                    return Err(SpirvCrossError::InvalidSpirv(String::from(
+                        "The sampled type of the image is not a scalar",
+                    )));
+                };
+
+                naga::ImageClass::Sampled {
+                    kind: scalar.kind.into(),
+                    multi: multisampled,
+                }
+            }
+            ImageClass::Storage { format, access } => naga::ImageClass::Storage {
+                format: storage_format(format)?,
+                access: match access {
+                    Some(ImageAccess::ReadOnly) => naga::StorageAccess::LOAD,
+                    Some(ImageAccess::WriteOnly) => naga::StorageAccess::STORE,
+                    // The access of an image type is unknown without its variable.
+                    Some(ImageAccess::ReadWrite) | None => {
+                        naga::StorageAccess::LOAD | naga::StorageAccess::STORE
+                    }
+                },
+            },
+        };
+
+        Ok(naga::TypeInner::Image {
+            dim,
+            arrayed,
+            class,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::SpirvCrossError;
+    use crate::reflect::{BitWidth, Scalar, ScalarKind};
+    use crate::Compiler;
+    use crate::{targets, Module};
+
+    static BASIC_SPV: &[u8] = include_bytes!("../../../basic.spv");
+
+    #[test]
+    pub fn scalar() {
+        let scalar = Scalar {
+            kind: ScalarKind::Uint,
+            size: BitWidth::HalfWord,
+        };
+
+        assert_eq!(
+            naga::Scalar {
+                kind: naga::ScalarKind::Uint,
+                width: 2,
+            },
+            scalar.into()
+        );
+    }
+
+    #[test]
+    pub fn uniform_buffer() -> Result<(), SpirvCrossError> {
+        let vec = Vec::from(BASIC_SPV);
+        let words = Module::from_words(bytemuck::cast_slice(&vec));
+
+        let compiler: Compiler<targets::None> = Compiler::new(words)?;
+        let resources = compiler.shader_resources()?.all_resources()?;
+
+        let mut types = naga::UniqueArena::new();
+        let ty = compiler.naga_type(resources.uniform_buffers[0].base_type_id, &mut types)?;
+
+        assert!(matches!(types[ty].inner, naga::TypeInner::Struct { .. }));
+
+        Ok(())
+    }
+}
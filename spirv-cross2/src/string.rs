@@ -116,6 +116,20 @@ impl MaybeOwnedCString<'_> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for CompilerStr<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_ref())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CompilerStr<'_> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <String as serde::Deserialize>::deserialize(deserializer).map(CompilerStr::from)
+    }
+}
+
 impl AsRef<str> for CompilerStr<'_> {
     fn as_ref(&self) -> &str {
         self.cow.as_ref()
@@ -284,6 +298,19 @@ impl<'a> CompilerStr<'a> {
         }
     }
 
+    /// Convert into an owned `String`, which only allocates if the string is borrowed.
+    pub(crate) fn into_string(self) -> String {
+        self.cow.into_owned()
+    }
+
+    /// Detach the string from the lifetime `'a`.
+    ///
+    /// Strings borrowed from a compiler instance or a Rust `&str` are copied into
+    /// an owned string, while strings that are already owned are moved without allocating.
+    pub fn into_owned(self) -> CompilerStr<'static> {
+        CompilerStr::from_string(self.into_string())
+    }
+
     /// Allocate if necessary, if not then return a pointer to the original cstring.
     ///
     /// The returned pointer will be valid for the lifetime `'a`.
@@ -404,4 +431,18 @@ mod test {
     //     assert_eq!(original_ptr, new_ptr as usize);
     //     // lt.borrow_mut().set(cstr)
     // }
+
+    #[test]
+    fn into_owned() {
+        let owned: CompilerStr<'static> = {
+            let string = String::from("hello");
+            CompilerStr::from(string.as_str()).into_owned()
+        };
+        assert_eq!("hello", owned);
+
+        let string = String::from("hello");
+        let original_ptr = string.as_ptr();
+        let owned = CompilerStr::from(string).into_owned();
+        assert_eq!(original_ptr, owned.as_ref().as_ptr());
+    }
 }
@@ -1,10 +1,18 @@
 use crate::compile;
+use crate::compile::sealed::CompileHooks;
 use crate::compile::CompilableTarget;
 use crate::sealed::Sealed;
 use spirv_cross_sys::CompilerBackend;
 
 /// Reflection only backend, no compilation features
 /// enabled.
+///
+/// `Compiler<None>` is already the minimal reflection path. It creates the base
+/// SPIRV-Cross compiler without any target backend, and never allocates compiler
+/// options, as it can not be compiled. A separate reflection-only type would not
+/// avoid any further work.
+///
+/// The `reflection` benchmark compares the per-module overhead against a compilable target.
 pub struct None;
 
 /// Compile SPIR-V to GLSL.
@@ -26,13 +34,20 @@ pub struct Hlsl;
 ///
 /// This backend is deprecated but is included here for completion.
 /// See the [SPIRV-Cross docs](https://github.com/KhronosGroup/SPIRV-Cross?tab=readme-ov-file#using-shaders-generated-from-c-backend)
-/// for how to debug shaders generated from the C++ backend
+/// for how to debug shaders generated from the C++ backend.
+///
+/// The name of the exported interface can be changed with `Compiler<Cpp>::set_interface_name`.
 #[deprecated = "This backend is deprecated in SPIRV-Cross."]
 #[cfg(feature = "cpp")]
 #[cfg_attr(docsrs, doc(cfg(feature = "cpp")))]
 pub struct Cpp;
 
 /// Compile SPIR-V to a JSON reflection format
+///
+/// The output is SPIRV-Cross's own reflection format. It is valid JSON as long as the
+/// identifiers in the module do not contain characters that need escaping, which SPIRV-Cross
+/// does not escape. With the `serde-json` feature, `CompiledArtifact<Json>::parse` parses
+/// the output and reports invalid JSON as an error.
 #[cfg(feature = "json")]
 #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
 pub struct Json;
@@ -40,18 +55,31 @@ pub struct Json;
 impl Sealed for None {}
 impl Target for None {
     const BACKEND: CompilerBackend = CompilerBackend::None;
+    type State = ();
 }
 
 #[cfg(feature = "glsl")]
 #[cfg_attr(docsrs, doc(cfg(feature = "glsl")))]
 mod glsl {
     use super::*;
+    use crate::handle::{Handle, VariableId};
+
+    /// GLSL specific state of a compiler instance, kept outside of SPIRV-Cross.
+    #[derive(Default)]
+    pub struct GlslState {
+        /// Buffer blocks flattened with `Compiler<Glsl>::flatten_buffer_block`, applied when compiling.
+        pub(crate) flattened_blocks: Vec<Handle<VariableId>>,
+        /// Integer variables set to highp with `Compiler<Glsl>::set_precision`.
+        pub(crate) highp_ints: Vec<Handle<VariableId>>,
+    }
+
     impl CompilableTarget for Glsl {
         type Options = compile::glsl::CompilerOptions;
     }
     impl Sealed for Glsl {}
     impl Target for Glsl {
         const BACKEND: CompilerBackend = CompilerBackend::Glsl;
+        type State = GlslState;
     }
 }
 
@@ -59,12 +87,24 @@ mod glsl {
 #[cfg_attr(docsrs, doc(cfg(feature = "hlsl")))]
 mod hlsl {
     use super::*;
+    use crate::compile::hlsl::ResourceBinding;
+
+    /// HLSL specific state of a compiler instance, kept outside of SPIRV-Cross.
+    #[derive(Default)]
+    pub struct HlslState {
+        /// Resource bindings added with `Compiler<Hlsl>::add_resource_binding`.
+        pub(crate) resource_bindings: Vec<(spirv::ExecutionModel, ResourceBinding)>,
+        /// Whether `Compiler<Hlsl>::emit_vk_binding_annotations` is enabled.
+        pub(crate) vk_binding_annotations: bool,
+    }
+
     impl CompilableTarget for Hlsl {
         type Options = compile::hlsl::CompilerOptions;
     }
     impl Sealed for Hlsl {}
     impl Target for Hlsl {
         const BACKEND: CompilerBackend = CompilerBackend::Hlsl;
+        type State = HlslState;
     }
 }
 
@@ -72,12 +112,37 @@ mod hlsl {
 #[cfg_attr(docsrs, doc(cfg(feature = "msl")))]
 mod msl {
     use super::*;
+    use crate::compile::msl::{
+        AuxBufferIndices, BindTarget, ResourceBinding, ShaderVariableFormat,
+    };
+    use crate::handle::{Handle, VariableId};
+
+    /// MSL specific state of a compiler instance, kept outside of SPIRV-Cross.
+    #[derive(Default)]
+    pub struct MslState {
+        /// Shader input formats added with `Compiler<Msl>::add_shader_input`, by location.
+        pub(crate) shader_inputs: Vec<(u32, ShaderVariableFormat)>,
+        /// Resource bindings added with `Compiler<Msl>::add_resource_binding`, applied when compiling.
+        pub(crate) resource_bindings: Vec<(spirv::ExecutionModel, ResourceBinding, BindTarget)>,
+        /// Resources that are runtime arrays, found when first adding a resource binding without a count.
+        pub(crate) runtime_arrays: Option<Vec<Handle<VariableId>>>,
+        /// Descriptor sets added with `Compiler<Msl>::add_discrete_descriptor_set`.
+        pub(crate) discrete_descriptor_sets: Vec<u32>,
+        /// Whether the instance was compiled with `CompilerOptions::vertex_for_tessellation`.
+        pub(crate) vertex_for_tessellation: bool,
+        /// Whether the instance was compiled with `CompilerOptions::argument_buffers`.
+        pub(crate) argument_buffers: bool,
+        /// The auxiliary buffer indices the instance was compiled with.
+        pub(crate) aux_buffer_indices: AuxBufferIndices,
+    }
+
     impl CompilableTarget for Msl {
         type Options = compile::msl::CompilerOptions;
     }
     impl Sealed for Msl {}
     impl Target for Msl {
         const BACKEND: CompilerBackend = CompilerBackend::Msl;
+        type State = MslState;
     }
 }
 
@@ -88,9 +153,11 @@ mod json {
     impl CompilableTarget for Json {
         type Options = compile::NoOptions;
     }
+    impl CompileHooks for Json {}
     impl Sealed for Json {}
     impl Target for Json {
         const BACKEND: CompilerBackend = CompilerBackend::Json;
+        type State = ();
     }
 }
 
@@ -103,12 +170,16 @@ mod cpp {
         type Options = compile::NoOptions;
     }
 
+    #[allow(deprecated)]
+    impl CompileHooks for Cpp {}
+
     #[allow(deprecated)]
     impl Sealed for Cpp {}
 
     #[allow(deprecated)]
     impl Target for Cpp {
         const BACKEND: CompilerBackend = CompilerBackend::Cpp;
+        type State = ();
     }
 }
 
@@ -116,4 +187,9 @@ mod cpp {
 pub trait Target: Sealed {
     #[doc(hidden)]
     const BACKEND: CompilerBackend;
+
+    /// Target specific state of a compiler instance that SPIRV-Cross does not keep,
+    /// or that is applied when compiling.
+    #[doc(hidden)]
+    type State: Default + Send + 'static;
 }
@@ -2,7 +2,17 @@ use glslang::SpirvVersion::{SPIRV1_0, SPIRV1_1, SPIRV1_6};
 use glslang::{
     CompilerOptions, OpenGlVersion, ShaderInput, ShaderSource, ShaderStage, Target, VulkanVersion,
 };
-use spirv_cross2::reflect::TypeInner;
+use spirv_cross2::compile::hlsl::HlslShaderModel;
+use spirv_cross2::compile::msl::{
+    MslVersion, MtlVertexFormat, ShaderInterfaceVariable, ShaderVariableFormat, ShaderVariableRate,
+    StageInEntry,
+};
+use spirv_cross2::compile::CompilableTarget;
+use spirv_cross2::reflect::{
+    BitWidth, BufferClass, DecorationValue, ImageSampledUsage, InputTopology, LayoutWarningKind,
+    PaddingRegion, ResourceType, Scalar, ScalarKind, ScalarValue, StructMember, TypeInner,
+};
+use spirv_cross2::targets::{Glsl, Hlsl, Msl};
 use spirv_cross2::SpirvCrossError;
 use spirv_cross2::{Compiler, Module};
 
@@ -358,3 +368,2736 @@ void main() {
 
     Ok(())
 }
+
+#[test]
+pub fn hlsl_map_sets_to_spaces() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(set = 0, binding = 0) uniform UBO
+{
+    float value;
+};
+
+layout(location = 0) out vec4 color;
+layout(set = 2, binding = 1) uniform sampler2D tex;
+
+void main() {
+    color = texture(tex, vec2(value));
+}"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_0,
+        spirv_version: SPIRV1_0,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Fragment, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let mut compiler = Compiler::<Hlsl>::new(Module::from_words(&spv))?;
+
+    let resources = compiler.shader_resources()?;
+    compiler.map_sets_to_spaces(&resources)?;
+
+    let mut options = Hlsl::options();
+    options.shader_model = HlslShaderModel::ShaderModel5_1;
+
+    let artifact = compiler.compile(&options)?;
+    let source = artifact.to_string();
+
+    assert!(source.contains("register(b0, space0)"));
+    assert!(source.contains("register(t1, space2)"));
+    assert!(source.contains("register(s1, space2)"));
+
+    Ok(())
+}
+
+#[test]
+pub fn uses_buffer_device_address() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+#extension GL_EXT_buffer_reference : require
+
+layout(buffer_reference, std430) buffer Data
+{
+    float value;
+};
+
+layout(push_constant) uniform Push
+{
+    Data data;
+};
+
+layout(location = 0) out vec4 color;
+
+void main() {
+    color = vec4(data.value);
+}"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Fragment, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+
+    assert!(compiler.uses_buffer_device_address()?);
+
+    Ok(())
+}
+
+#[test]
+pub fn glsl_predict_required_extensions() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout (local_size_x = 64, local_size_y = 1, local_size_z = 1) in;
+
+layout(set = 0, binding = 0) buffer Data {
+    uint values[];
+} data;
+
+void main()
+{
+    data.values[gl_GlobalInvocationID.x] *= 2u;
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Compute, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::Glsl>::new(Module::from_words(&spv))?;
+
+    let mut options = spirv_cross2::targets::Glsl::options();
+    options.version = spirv_cross2::compile::glsl::GlslVersion::Glsl330;
+    options.vulkan_semantics = false;
+
+    let extensions = compiler.predict_required_extensions(&options)?;
+    assert!(extensions.iter().any(|e| e == "GL_ARB_compute_shader"));
+
+    Ok(())
+}
+
+#[test]
+pub fn glsl_predict_required_extensions_entry_point() -> Result<(), SpirvCrossError> {
+    use spirv::{ExecutionModel, Op};
+
+    fn instruction(words: &mut Vec<u32>, op: Op, operands: &[u32]) {
+        words.push((operands.len() as u32 + 1) << 16 | op as u32);
+        words.extend_from_slice(operands);
+    }
+
+    // An empty vertex shader "vs" and an empty compute shader "cs" in one module.
+    let (void, function, vs, cs) = (1, 2, 3, 5);
+    let mut spv = vec![spirv::MAGIC_NUMBER, 0x0001_0000, 0, 7, 0];
+    instruction(
+        &mut spv,
+        Op::Capability,
+        &[spirv::Capability::Shader as u32],
+    );
+    instruction(&mut spv, Op::MemoryModel, &[0, 1]);
+    instruction(
+        &mut spv,
+        Op::EntryPoint,
+        &[
+            ExecutionModel::Vertex as u32,
+            vs,
+            u32::from_le_bytes(*b"vs\0\0"),
+        ],
+    );
+    instruction(
+        &mut spv,
+        Op::EntryPoint,
+        &[
+            ExecutionModel::GLCompute as u32,
+            cs,
+            u32::from_le_bytes(*b"cs\0\0"),
+        ],
+    );
+    instruction(
+        &mut spv,
+        Op::ExecutionMode,
+        &[cs, spirv::ExecutionMode::LocalSize as u32, 1, 1, 1],
+    );
+    instruction(&mut spv, Op::TypeVoid, &[void]);
+    instruction(&mut spv, Op::TypeFunction, &[function, void]);
+    for entry_point in [vs, cs] {
+        instruction(&mut spv, Op::Function, &[void, entry_point, 0, function]);
+        instruction(&mut spv, Op::Label, &[entry_point + 1]);
+        instruction(&mut spv, Op::Return, &[]);
+        instruction(&mut spv, Op::FunctionEnd, &[]);
+    }
+
+    let mut options = Glsl::options();
+    options.version = spirv_cross2::compile::glsl::GlslVersion::Glsl330;
+    options.vulkan_semantics = false;
+
+    let compiler = Compiler::<Glsl>::new(Module::from_words(&spv))?;
+    let extensions = compiler.predict_required_extensions(&options)?;
+    assert!(!extensions.iter().any(|e| e == "GL_ARB_compute_shader"));
+
+    let mut compiler = Compiler::<Glsl>::new(Module::from_words(&spv))?;
+    compiler.set_entry_point("cs", ExecutionModel::GLCompute)?;
+    let extensions = compiler.predict_required_extensions(&options)?;
+    assert!(extensions.iter().any(|e| e == "GL_ARB_compute_shader"));
+
+    compiler.rename_entry_point("cs", "compute_main", ExecutionModel::GLCompute)?;
+    let extensions = compiler.predict_required_extensions(&options)?;
+    assert!(extensions.iter().any(|e| e == "GL_ARB_compute_shader"));
+
+    Ok(())
+}
+
+#[test]
+pub fn spec_id_decoration() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout (constant_id = 7) const uint SCALE = 2;
+
+layout(location = 0) out vec4 color;
+
+void main()
+{
+    color = vec4(float(SCALE));
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Fragment, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+    let constant = compiler.specialization_constants()?.next().unwrap();
+
+    assert_eq!(
+        Some(DecorationValue::Literal(7)),
+        compiler.decoration(constant.id, spirv::Decoration::SpecId)?
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn active_push_constant_ranges() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(push_constant) uniform Constants {
+    mat4 transform;
+    vec4 tint;
+} constants;
+
+layout(location = 0) out vec4 color;
+
+void main()
+{
+    color = constants.tint;
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Fragment, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+    let ranges = compiler.active_push_constant_ranges()?;
+
+    assert_eq!(&[(64, 16)], ranges.as_slice());
+
+    Ok(())
+}
+
+#[test]
+pub fn glsl_suggested_extensions() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+#extension GL_ARB_gpu_shader_int64 : require
+
+layout(push_constant) uniform Constants {
+    uint64_t value;
+} constants;
+
+layout(location = 0) out vec4 color;
+
+void main()
+{
+    color = vec4(float(constants.value));
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Fragment, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::Glsl>::new(Module::from_words(&spv))?;
+    let extensions = compiler.suggested_extensions()?;
+
+    assert!(extensions.iter().any(|e| e == "GL_ARB_gpu_shader_int64"));
+
+    Ok(())
+}
+
+#[test]
+pub fn msl_is_tessellation_kernel() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(location = 0) in vec4 position;
+
+void main()
+{
+    gl_Position = position;
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Vertex, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<Msl>::new(Module::from_words(&spv))?;
+    let artifact = compiler.compile(&Msl::options())?;
+    assert!(!artifact.is_tessellation_kernel()?);
+
+    let compiler = Compiler::<Msl>::new(Module::from_words(&spv))?;
+    let mut options = Msl::options();
+    options.vertex_for_tessellation = true;
+
+    let artifact = compiler.compile(&options)?;
+    assert!(artifact.is_tessellation_kernel()?);
+
+    Ok(())
+}
+
+#[test]
+pub fn hlsl_needs_num_workgroups_cbuffer() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout (local_size_x = 64, local_size_y = 1, local_size_z = 1) in;
+
+layout(set = 0, binding = 0) buffer Data {
+    uint values[];
+} data;
+
+void main()
+{
+    data.values[gl_GlobalInvocationID.x] = gl_NumWorkGroups.x;
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Compute, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let mut compiler = Compiler::<Hlsl>::new(Module::from_words(&spv))?;
+    assert!(compiler.needs_num_workgroups_cbuffer()?);
+    assert!(compiler.remap_num_workgroups_builtin().is_some());
+
+    Ok(())
+}
+
+#[test]
+pub fn sampled_component_type() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(set = 0, binding = 0) uniform usampler2D ids;
+
+layout(location = 0) out uvec4 color;
+
+void main()
+{
+    color = texture(ids, vec2(0.5));
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Fragment, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+    let resources = compiler.shader_resources()?.all_resources()?;
+
+    let scalar = compiler.sampled_component_type(resources.sampled_images[0].id)?;
+    assert_eq!(
+        Scalar {
+            kind: ScalarKind::Uint,
+            size: BitWidth::Word,
+        },
+        scalar
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn validate_std430_layout() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout (local_size_x = 64, local_size_y = 1, local_size_z = 1) in;
+
+layout(set = 0, binding = 0, std140) buffer Padded {
+    float weights[4];
+    uint count;
+} padded;
+
+layout(set = 0, binding = 1, std430) buffer Packed {
+    float weights[4];
+    uint count;
+} packed;
+
+void main()
+{
+    packed.weights[gl_GlobalInvocationID.x] = padded.weights[gl_GlobalInvocationID.x];
+    packed.count = padded.count;
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Compute, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+    let resources = compiler.shader_resources()?.all_resources()?;
+
+    let padded = resources
+        .storage_buffers
+        .iter()
+        .find(|r| r.name == "padded")
+        .unwrap();
+    let warnings = compiler.validate_std430_layout(padded.id)?;
+
+    assert_eq!(1, warnings.len());
+    assert_eq!(LayoutWarningKind::ArrayStride, warnings[0].kind);
+    assert_eq!(Some("weights"), warnings[0].name.as_deref());
+    assert_eq!((4, 16), (warnings[0].expected, warnings[0].declared));
+
+    let packed = resources
+        .storage_buffers
+        .iter()
+        .find(|r| r.name == "packed")
+        .unwrap();
+    assert!(compiler.validate_std430_layout(packed.id)?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+pub fn glsl_sanitize_names() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(set = 0, binding = 0) uniform Transform {
+    mat4 mvp;
+} transform;
+
+layout(location = 0) in vec4 position;
+
+void main()
+{
+    gl_Position = transform.mvp * position;
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Vertex, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let mut compiler = Compiler::<spirv_cross2::targets::Glsl>::new(Module::from_words(&spv))?;
+    let resources = compiler.shader_resources()?.all_resources()?;
+
+    let transform = resources.uniform_buffers[0].id;
+    compiler.set_name(transform, "gl_myVar")?;
+
+    assert_eq!(1, compiler.sanitize_glsl_names()?);
+    assert_eq!(Some("_gl_myVar"), compiler.name(transform)?.as_deref());
+
+    let per_vertex = resources.builtin_outputs[0].resource.base_type_id;
+    let TypeInner::Struct(per_vertex) = compiler.type_description(per_vertex)?.inner else {
+        panic!("expected gl_PerVertex block")
+    };
+    assert_eq!(Some("gl_Position"), per_vertex.members[0].name.as_deref());
+
+    Ok(())
+}
+
+#[test]
+pub fn interface_variable_component() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(location = 0, component = 0) in vec2 uv;
+layout(location = 0, component = 2) in vec2 lightmap_uv;
+
+void main()
+{
+    gl_Position = vec4(uv, lightmap_uv);
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Vertex, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+    let resources = compiler.shader_resources()?.all_resources()?;
+
+    let uv = resources
+        .stage_inputs
+        .iter()
+        .find(|r| r.name == "uv")
+        .unwrap();
+    let lightmap_uv = resources
+        .stage_inputs
+        .iter()
+        .find(|r| r.name == "lightmap_uv")
+        .unwrap();
+
+    assert_eq!(Some(0), compiler.interface_variable_component(uv.id)?);
+    assert_eq!(
+        Some(2),
+        compiler.interface_variable_component(lightmap_uv.id)?
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn hlsl_minimum_shader_model() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+#extension GL_KHR_shader_subgroup_basic : require
+
+layout (local_size_x = 64, local_size_y = 1, local_size_z = 1) in;
+
+layout(set = 0, binding = 0) buffer Data {
+    uint values[];
+} data;
+
+void main()
+{
+    if (subgroupElect()) {
+        data.values[gl_GlobalInvocationID.x] = 1;
+    }
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Compute, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<Hlsl>::new(Module::from_words(&spv))?;
+    let model = compiler.minimum_shader_model()?;
+    assert!(u32::from(model) >= u32::from(HlslShaderModel::ShaderModel6_0));
+
+    Ok(())
+}
+
+#[test]
+pub fn specialization_defaults() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(constant_id = 3) const float SCALE = 1.5;
+layout(constant_id = 1) const int OFFSET = -4;
+layout(constant_id = 2) const uint COUNT = 8;
+layout(constant_id = 0) const bool ENABLED = true;
+
+layout(location = 0) out vec4 color;
+
+void main()
+{
+    color = ENABLED ? vec4(SCALE * float(OFFSET + int(COUNT))) : vec4(0.0);
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Fragment, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+    let defaults = compiler.specialization_defaults()?;
+
+    assert_eq!(
+        vec![
+            (0, ScalarValue::Bool(true)),
+            (1, ScalarValue::Int(-4)),
+            (2, ScalarValue::Uint(8)),
+            (3, ScalarValue::Float(1.5)),
+        ],
+        defaults.into_iter().collect::<Vec<_>>()
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn readwrite_images() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout (local_size_x = 8, local_size_y = 8, local_size_z = 1) in;
+
+layout(set = 0, binding = 0, rgba8) uniform image2D accumulate;
+layout(set = 0, binding = 1, rgba8) uniform readonly image2D source;
+layout(set = 0, binding = 2, rgba8) uniform writeonly image2D destination;
+
+void main()
+{
+    ivec2 coord = ivec2(gl_GlobalInvocationID.xy);
+    vec4 value = imageLoad(accumulate, coord) + imageLoad(source, coord);
+    imageStore(accumulate, coord, value);
+    imageStore(destination, coord, value);
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Compute, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<Msl>::new(Module::from_words(&spv))?;
+    let images = compiler.readwrite_images()?;
+
+    assert_eq!(1, images.len());
+    assert_eq!(Some("accumulate"), compiler.name(images[0])?.as_deref());
+
+    Ok(())
+}
+
+#[test]
+pub fn acceleration_structures() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 460
+#extension GL_EXT_ray_tracing : require
+
+layout(set = 1, binding = 3) uniform accelerationStructureEXT tlas;
+layout(set = 0, binding = 0, rgba8) uniform writeonly image2D image;
+
+layout(location = 0) rayPayloadEXT vec4 payload;
+
+void main()
+{
+    traceRayEXT(tlas, gl_RayFlagsOpaqueEXT, 0xff, 0, 0, 0, vec3(0.0), 0.001, vec3(0.0, 0.0, 1.0), 1000.0, 0);
+    imageStore(image, ivec2(gl_LaunchIDEXT.xy), payload);
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::RayGeneration, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+    let structures = compiler.acceleration_structures()?;
+
+    assert_eq!(1, structures.len());
+    assert_eq!("tlas", structures[0].name);
+    assert_eq!((1, 3), (structures[0].set, structures[0].binding));
+
+    Ok(())
+}
+
+#[test]
+pub fn self_referential_buffer_reference() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+#extension GL_EXT_buffer_reference : require
+
+layout (local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+layout(buffer_reference) buffer Node;
+layout(buffer_reference, std430) buffer Node {
+    Node next;
+    Node children[2];
+    uint value;
+};
+
+layout(push_constant) uniform Push {
+    Node head;
+} push;
+
+void main()
+{
+    push.head.next.value = push.head.children[1].value;
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Compute, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+    let resources = compiler.shader_resources()?;
+    let push = resources
+        .resources_for_type(ResourceType::PushConstant)?
+        .next()
+        .unwrap();
+
+    // Follow pointers through the linked list a few times over to make sure
+    // reflecting each level terminates.
+    let mut pending = vec![push.base_type_id];
+    let mut visited = 0;
+    while let Some(id) = pending.pop() {
+        visited += 1;
+        if visited > 16 {
+            break;
+        }
+
+        let ty = compiler.type_description(id)?;
+        assert!(ty.size_hint.is_static() || matches!(ty.inner, TypeInner::Array { .. }));
+        match ty.inner {
+            TypeInner::Struct(s) => pending.extend(s.members.iter().map(|m| m.id)),
+            TypeInner::Pointer { base, storage, .. } => {
+                assert_eq!(spirv::StorageClass::PhysicalStorageBuffer, storage);
+                pending.push(base);
+            }
+            TypeInner::Array { base, .. } => pending.push(base),
+            _ => {}
+        }
+    }
+
+    assert!(visited > 16);
+
+    Ok(())
+}
+
+#[test]
+pub fn glsl_assign_missing_io_locations() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(location = 0) in vec4 position;
+layout(location = 1) in vec2 uv;
+
+layout(location = 0) out vec2 out_uv;
+layout(location = 3) out vec4 out_color;
+
+void main()
+{
+    gl_Position = position;
+    out_uv = uv;
+    out_color = position;
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Vertex, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let mut compiler = Compiler::<Glsl>::new(Module::from_words(&spv))?;
+    let resources = compiler.shader_resources()?;
+    let out_uv = resources
+        .resources_for_type(ResourceType::StageOutput)?
+        .find(|r| r.name == "out_uv")
+        .unwrap();
+
+    // SPIR-V for Vulkan requires locations, so remove it to simulate an unlocated output.
+    compiler.set_decoration(out_uv.id, spirv::Decoration::Location, None::<u32>)?;
+
+    assert_eq!(1, compiler.assign_missing_io_locations()?);
+    assert_eq!(
+        Some(4),
+        compiler
+            .decoration(out_uv.id, spirv::Decoration::Location)?
+            .and_then(|l| l.as_literal())
+    );
+    assert_eq!(0, compiler.assign_missing_io_locations()?);
+
+    let artifact = compiler.compile(&Glsl::options())?;
+    assert!(artifact
+        .as_ref()
+        .contains("layout(location = 4) out vec2 out_uv;"));
+
+    Ok(())
+}
+
+#[test]
+pub fn uses_derivatives() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(location = 0) in vec2 uv;
+layout(location = 0) out vec4 color;
+
+void main()
+{
+    color = vec4(dFdx(uv), fwidth(uv));
+}
+"##;
+
+    const NO_DERIVATIVES: &str = r##"#version 450
+
+layout(location = 0) in vec2 uv;
+layout(location = 0) out vec4 color;
+
+void main()
+{
+    color = vec4(uv, uv);
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    for (shader, expected) in [(SHADER, true), (NO_DERIVATIVES, false)] {
+        let src = ShaderSource::from(shader);
+        let shader = ShaderInput::new(&src, ShaderStage::Fragment, &opts, None, None).unwrap();
+        let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+        let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+        assert_eq!(expected, compiler.uses_derivatives()?);
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn msl_stage_in_layout() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(location = 0) in vec4 position;
+layout(location = 1) in uvec4 joints;
+
+void main()
+{
+    gl_Position = position + vec4(joints);
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Vertex, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let mut compiler = Compiler::<Msl>::new(Module::from_words(&spv))?;
+    compiler.add_shader_input(
+        1,
+        &ShaderInterfaceVariable {
+            builtin: None,
+            vecsize: None,
+            format: ShaderVariableFormat::Uint8,
+            rate: ShaderVariableRate::PerVertex,
+        },
+    )?;
+
+    let artifact = compiler.compile(&Msl::options())?;
+    assert_eq!(
+        vec![
+            StageInEntry {
+                location: 0,
+                attribute_index: 0,
+                format: ShaderVariableFormat::Other,
+            },
+            StageInEntry {
+                location: 1,
+                attribute_index: 1,
+                format: ShaderVariableFormat::Uint8,
+            },
+        ],
+        artifact.stage_in_layout()?
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn transform_feedback_layout() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(location = 0) in vec4 position;
+
+layout(location = 0, xfb_buffer = 1, xfb_stride = 32, xfb_offset = 16) out vec4 captured;
+layout(location = 1) out vec4 ignored;
+
+void main()
+{
+    gl_Position = position;
+    captured = position;
+    ignored = position;
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Vertex, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+    let varyings = compiler.transform_feedback_layout()?;
+
+    assert_eq!(1, varyings.len());
+    let captured = &varyings[0];
+    assert_eq!("captured", captured.name);
+    assert_eq!(
+        (1, 32, 16, 0),
+        (
+            captured.buffer,
+            captured.stride,
+            captured.offset,
+            captured.stream
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn execution_modes_with_arguments() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout (local_size_x = 8, local_size_y = 4, local_size_z = 2) in;
+
+layout(set = 0, binding = 0) buffer Data {
+    uint values[];
+} data;
+
+void main()
+{
+    data.values[gl_LocalInvocationIndex] = 1;
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Compute, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+    assert_eq!(
+        vec![(spirv::ExecutionMode::LocalSize, vec![8, 4, 2])],
+        compiler.execution_modes_with_arguments()?
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn subpass_data_image() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(input_attachment_index = 0, set = 0, binding = 0) uniform subpassInput previous;
+
+layout(location = 0) out vec4 color;
+
+void main()
+{
+    color = subpassLoad(previous);
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Fragment, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+    let resources = compiler.shader_resources()?;
+    let previous = resources
+        .resources_for_type(ResourceType::SubpassInput)?
+        .next()
+        .unwrap();
+
+    let TypeInner::Image(image) = compiler.type_description(previous.base_type_id)?.inner else {
+        panic!("subpass input is not an image");
+    };
+
+    assert_eq!(spirv::Dim::DimSubpassData, image.dimension);
+    assert!(image.is_subpass_data());
+
+    Ok(())
+}
+
+#[test]
+pub fn buffer_device_address_variables() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+#extension GL_EXT_buffer_reference : require
+
+layout (local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+layout(buffer_reference, std430) buffer Values {
+    uint values[];
+};
+
+layout(set = 0, binding = 0) buffer Pointers {
+    Values source;
+} pointers;
+
+layout(set = 0, binding = 1) buffer Plain {
+    uint value;
+} plain;
+
+void main()
+{
+    plain.value = pointers.source.values[0];
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Compute, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<Msl>::new(Module::from_words(&spv))?;
+    let variables = compiler.buffer_device_address_variables()?;
+
+    assert_eq!(1, variables.len());
+    assert_eq!(Some("pointers"), compiler.name(variables[0])?.as_deref());
+
+    Ok(())
+}
+
+#[test]
+pub fn uses_draw_parameters() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 460
+
+layout(location = 0) in vec4 position;
+layout(location = 0) flat out int instance;
+
+void main()
+{
+    gl_Position = position;
+    instance = gl_BaseInstance;
+}
+"##;
+
+    const NO_DRAW_PARAMETERS: &str = r##"#version 460
+
+layout(location = 0) in vec4 position;
+layout(location = 0) flat out int instance;
+
+void main()
+{
+    gl_Position = position;
+    instance = gl_InstanceIndex;
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    for (shader, expected) in [(SHADER, true), (NO_DRAW_PARAMETERS, false)] {
+        let src = ShaderSource::from(shader);
+        let shader = ShaderInput::new(&src, ShaderStage::Vertex, &opts, None, None).unwrap();
+        let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+        let mut compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+        assert_eq!(expected, compiler.uses_draw_parameters()?);
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn geometry_input_topology() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(triangles) in;
+layout(triangle_strip, max_vertices = 3) out;
+
+void main()
+{
+    for (int i = 0; i < 3; i++) {
+        gl_Position = gl_in[i].gl_Position;
+        EmitVertex();
+    }
+    EndPrimitive();
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let src = ShaderSource::from(SHADER);
+    let shader = ShaderInput::new(&src, ShaderStage::Geometry, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+    assert_eq!(Some(InputTopology::Triangles), compiler.input_topology()?);
+
+    Ok(())
+}
+
+#[test]
+pub fn image_sampled_usage() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(set = 0, binding = 0) uniform sampler2D tex;
+layout(set = 0, binding = 1, rgba8) uniform writeonly image2D img;
+
+layout(local_size_x = 1) in;
+
+void main()
+{
+    imageStore(img, ivec2(0), textureLod(tex, vec2(0.0), 0.0));
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let src = ShaderSource::from(SHADER);
+    let shader = ShaderInput::new(&src, ShaderStage::Compute, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+    let resources = compiler.shader_resources()?;
+
+    for (ty, expected) in [
+        (ResourceType::SampledImage, ImageSampledUsage::Sampling),
+        (ResourceType::StorageImage, ImageSampledUsage::Storage),
+    ] {
+        let resource = resources.resources_for_type(ty)?.next().unwrap();
+        let TypeInner::Image(image) = compiler.type_description(resource.base_type_id)?.inner
+        else {
+            panic!("expected image type");
+        };
+        assert_eq!(expected, image.sampled);
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn requires_flat_interpolation() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(location = 0) flat out int instance;
+layout(location = 1) out vec4 color;
+
+void main()
+{
+    gl_Position = vec4(0.0);
+    instance = gl_InstanceIndex;
+    color = vec4(1.0);
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let src = ShaderSource::from(SHADER);
+    let shader = ShaderInput::new(&src, ShaderStage::Vertex, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+    let resources = compiler.shader_resources()?;
+
+    for output in resources.resources_for_type(ResourceType::StageOutput)? {
+        let expected = output.name == "instance";
+        assert_eq!(expected, compiler.requires_flat_interpolation(output.id)?);
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn minimum_vulkan_version() -> Result<(), SpirvCrossError> {
+    // A SPIR-V 1.0 compute shader declaring GroupNonUniform, which requires Vulkan 1.1.
+    // glslang refuses to emit subgroup operations for SPIR-V 1.0, so the module is assembled by hand.
+    #[rustfmt::skip]
+    let mut words: [u32; 37] = [
+        0x07230203, 0x00010000, 0, 5, 0,
+        // OpCapability Shader
+        0x00020011, 1,
+        // OpCapability GroupNonUniform
+        0x00020011, 61,
+        // OpMemoryModel Logical GLSL450
+        0x0003000E, 0, 1,
+        // OpEntryPoint GLCompute %3 "main"
+        0x0005000F, 5, 3, 0x6E69616D, 0,
+        // OpExecutionMode %3 LocalSize 1 1 1
+        0x00060010, 3, 17, 1, 1, 1,
+        // %1 = OpTypeVoid
+        0x00020013, 1,
+        // %2 = OpTypeFunction %1
+        0x00030021, 2, 1,
+        // %3 = OpFunction %1 None %2
+        0x00050036, 1, 3, 0, 2,
+        // %4 = OpLabel
+        0x000200F8, 4,
+        // OpReturn
+        0x000100FD,
+        // OpFunctionEnd
+        0x00010038,
+    ];
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&words))?;
+    assert_eq!((1, 0), compiler.spirv_version());
+    assert_eq!((1, 1), compiler.minimum_vulkan_version()?);
+
+    // Without the subgroup capability, SPIR-V 1.0 only needs Vulkan 1.0.
+    words[8] = spirv::Capability::Shader as u32;
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&words))?;
+    assert_eq!((1, 0), compiler.minimum_vulkan_version()?);
+
+    Ok(())
+}
+
+#[test]
+pub fn flattened_uniform_names() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+struct Inner {
+    vec4 value;
+    float scale[2];
+};
+
+layout(std140, set = 0, binding = 0) uniform Block {
+    mat4 transform;
+    Inner inner;
+} block;
+
+layout(location = 0) out vec4 color;
+
+void main()
+{
+    color = block.transform * block.inner.value * block.inner.scale[1];
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let src = ShaderSource::from(SHADER);
+    let shader = ShaderInput::new(&src, ShaderStage::Fragment, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+    let resources = compiler.shader_resources()?;
+    let block = resources
+        .resources_for_type(ResourceType::UniformBuffer)?
+        .next()
+        .unwrap();
+
+    let names: Vec<_> = compiler
+        .flattened_uniform_names(block.id)?
+        .into_iter()
+        .map(|(name, _, offset)| (name, offset))
+        .collect();
+
+    assert_eq!(
+        vec![
+            (String::from("Block.transform"), 0),
+            (String::from("Block.inner.value"), 64),
+            (String::from("Block.inner.scale[0]"), 80),
+            (String::from("Block.inner.scale[1]"), 96),
+        ],
+        names
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn msl_features_requiring_higher_version() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+#extension GL_EXT_nonuniform_qualifier : require
+
+layout(set = 0, binding = 0) uniform texture2D textures[];
+layout(set = 0, binding = 1) uniform sampler samp;
+
+layout(location = 0) flat in int index;
+layout(location = 0) out vec4 color;
+
+void main()
+{
+    color = texture(sampler2D(textures[nonuniformEXT(index)], samp), vec2(0.0));
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let src = ShaderSource::from(SHADER);
+    let shader = ShaderInput::new(&src, ShaderStage::Fragment, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<Msl>::new(Module::from_words(&spv))?;
+
+    let features = compiler.features_requiring_higher_version(MslVersion::new(1, 2, 0))?;
+    assert_eq!(
+        vec![("argument buffers", MslVersion::new(2, 0, 0))],
+        features
+    );
+    assert!(compiler
+        .features_requiring_higher_version(MslVersion::new(2, 0, 0))?
+        .is_empty());
+
+    Ok(())
+}
+
+#[test]
+pub fn binding_aliases() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(set = 0, binding = 0) uniform texture2D image;
+layout(set = 0, binding = 0) uniform sampler samp;
+layout(set = 0, binding = 1) uniform sampler other;
+
+layout(location = 0) out vec4 color;
+
+void main()
+{
+    color = texture(sampler2D(image, samp), vec2(0.0)) + texture(sampler2D(image, other), vec2(1.0));
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let src = ShaderSource::from(SHADER);
+    let shader = ShaderInput::new(&src, ShaderStage::Fragment, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+    let resources = compiler.shader_resources()?;
+    let image = resources
+        .resources_for_type(ResourceType::SeparateImage)?
+        .next()
+        .unwrap();
+    let samp = resources
+        .resources_for_type(ResourceType::SeparateSamplers)?
+        .find(|sampler| sampler.name == "samp")
+        .unwrap();
+
+    assert_eq!(vec![vec![image.id, samp.id]], compiler.binding_aliases()?);
+
+    Ok(())
+}
+
+#[test]
+pub fn dual_source_outputs() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(location = 0, index = 1) out vec4 blend;
+layout(location = 0, index = 0) out vec4 color;
+
+void main()
+{
+    color = vec4(1.0);
+    blend = vec4(0.5);
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let src = ShaderSource::from(SHADER);
+    let shader = ShaderInput::new(&src, ShaderStage::Fragment, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+    let resources = compiler.shader_resources()?;
+    let output = |name: &str| {
+        resources
+            .resources_for_type(ResourceType::StageOutput)
+            .unwrap()
+            .find(|output| output.name == name)
+            .unwrap()
+            .id
+    };
+
+    assert_eq!(
+        vec![(output("color"), 0, 0), (output("blend"), 0, 1)],
+        compiler.dual_source_outputs()?
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn set_array_stride() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(std430, set = 0, binding = 0) buffer Vertices {
+    vec3 positions[4];
+};
+
+layout(location = 0) out vec3 position;
+
+void main()
+{
+    position = positions[gl_VertexIndex];
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let src = ShaderSource::from(SHADER);
+    let shader = ShaderInput::new(&src, ShaderStage::Vertex, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let mut compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+    let resources = compiler.shader_resources()?;
+    let buffer = resources
+        .resources_for_type(ResourceType::StorageBuffer)?
+        .next()
+        .unwrap();
+
+    let TypeInner::Struct(block) = compiler.type_description(buffer.base_type_id)?.inner else {
+        panic!("storage buffer is not a struct");
+    };
+    let member = &block.members[0];
+    assert_eq!(Some(16), member.array_stride);
+
+    // Drop the name, so the member no longer borrows the compiler.
+    let member = StructMember {
+        id: member.id,
+        struct_type: member.struct_type,
+        name: None,
+        index: member.index,
+        offset: member.offset,
+        size: member.size,
+        matrix_stride: member.matrix_stride,
+        array_stride: member.array_stride,
+    };
+
+    assert!(matches!(
+        compiler.set_array_stride(&member, 8),
+        Err(SpirvCrossError::InvalidArgument(_))
+    ));
+
+    compiler.set_array_stride(&member, 12)?;
+    let TypeInner::Struct(block) = compiler.type_description(buffer.base_type_id)?.inner else {
+        panic!("storage buffer is not a struct");
+    };
+    assert_eq!(Some(12), block.members[0].array_stride);
+
+    Ok(())
+}
+
+#[test]
+pub fn source_extensions() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+#extension GL_GOOGLE_include_directive : require
+
+layout(location = 0) out vec4 color;
+
+void main()
+{
+    color = vec4(1.0);
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let src = ShaderSource::from(SHADER);
+    let shader = ShaderInput::new(&src, ShaderStage::Fragment, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+    let extensions = compiler.source_extensions()?;
+    assert!(extensions
+        .iter()
+        .any(|extension| extension == "GL_GOOGLE_include_directive"));
+
+    Ok(())
+}
+
+#[test]
+pub fn msl_vertex_formats() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(location = 0) in vec3 position;
+layout(location = 1) in uvec2 ids;
+
+layout(location = 0) flat out uvec2 out_ids;
+
+void main()
+{
+    gl_Position = vec4(position, 1.0);
+    out_ids = ids;
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let src = ShaderSource::from(SHADER);
+    let shader = ShaderInput::new(&src, ShaderStage::Vertex, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<Msl>::new(Module::from_words(&spv))?;
+    let artifact = compiler.compile(&Msl::options())?;
+
+    assert_eq!(
+        vec![(0, MtlVertexFormat::Float3), (1, MtlVertexFormat::UInt2)],
+        artifact.vertex_formats()?
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn classify_buffer() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(push_constant) uniform Push {
+    vec4 tint;
+};
+
+layout(set = 0, binding = 0) uniform Ubo {
+    vec4 color;
+};
+
+layout(set = 0, binding = 1) buffer Ssbo {
+    vec4 values[];
+};
+
+layout(location = 0) out vec4 out_color;
+
+void main()
+{
+    out_color = tint * color * values[0];
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let src = ShaderSource::from(SHADER);
+    let shader = ShaderInput::new(&src, ShaderStage::Fragment, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+    let resources = compiler.shader_resources()?;
+
+    for (ty, expected) in [
+        (ResourceType::PushConstant, BufferClass::PushConstant),
+        (ResourceType::UniformBuffer, BufferClass::Uniform),
+        (ResourceType::StorageBuffer, BufferClass::Storage),
+    ] {
+        let resource = resources.resources_for_type(ty)?.next().unwrap();
+        assert_eq!(expected, compiler.classify_buffer(resource.id)?);
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn struct_padding() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(std140, set = 0, binding = 0) uniform Light {
+    vec3 position;
+    float radius;
+    float intensity;
+    vec3 color;
+};
+
+layout(location = 0) out vec4 frag_color;
+
+void main()
+{
+    frag_color = vec4(position * radius + color * intensity, 1.0);
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let src = ShaderSource::from(SHADER);
+    let shader = ShaderInput::new(&src, ShaderStage::Fragment, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+    let resources = compiler.shader_resources()?;
+    let buffer = resources
+        .resources_for_type(ResourceType::UniformBuffer)?
+        .next()
+        .unwrap();
+
+    // The scalar packs into the vec3, but the following vec3 is aligned to 16 bytes.
+    assert_eq!(
+        vec![PaddingRegion {
+            after_member_index: 2,
+            bytes: 12,
+        }],
+        compiler.struct_padding(buffer.base_type_id)?
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn coherent_uav_resources() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout (local_size_x = 64, local_size_y = 1, local_size_z = 1) in;
+
+layout(set = 0, binding = 0, std430) coherent buffer Shared {
+    uint counter;
+} shared_data;
+
+layout(set = 0, binding = 1, std430) buffer Private {
+    uint counter;
+} private_data;
+
+void main()
+{
+    private_data.counter = atomicAdd(shared_data.counter, 1);
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let src = ShaderSource::from(SHADER);
+    let shader = ShaderInput::new(&src, ShaderStage::Compute, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<Hlsl>::new(Module::from_words(&spv))?;
+    let resources = compiler.shader_resources()?;
+    let shared = resources
+        .resources_for_type(ResourceType::StorageBuffer)?
+        .find(|r| r.name == "shared_data")
+        .unwrap();
+
+    assert_eq!(vec![shared.id], compiler.coherent_uav_resources()?);
+
+    // SPIRV-Cross already declares coherent UAVs as globallycoherent.
+    let mut options = Hlsl::options();
+    options.shader_model = HlslShaderModel::ShaderModel5_1;
+    let source = compiler.compile(&options)?.to_string();
+
+    assert_eq!(1, source.matches("globallycoherent").count());
+
+    Ok(())
+}
+
+#[test]
+pub fn module_from_bytes() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(location = 0) out vec4 color;
+
+void main()
+{
+    color = vec4(1.0);
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let src = ShaderSource::from(SHADER);
+    let shader = ShaderInput::new(&src, ShaderStage::Fragment, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    // Offset the bytes by one so the module is not aligned to a word.
+    let mut native = vec![0u8];
+    native.extend(spv.iter().flat_map(|word| word.to_ne_bytes()));
+    let swapped: Vec<u8> = spv
+        .iter()
+        .flat_map(|word| word.swap_bytes().to_ne_bytes())
+        .collect();
+
+    for bytes in [&native[1..], &swapped[..]] {
+        let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_bytes(bytes)?)?;
+        assert_eq!(spv.as_slice(), compiler.original_words());
+        assert_eq!(
+            "main",
+            compiler.entry_points()?.next().unwrap().name.as_ref()
+        );
+    }
+
+    assert!(matches!(
+        Module::from_bytes(&native[1..native.len() - 1]),
+        Err(SpirvCrossError::InvalidSpirv(_))
+    ));
+    assert!(matches!(
+        Module::from_bytes(&[0u8; 20]),
+        Err(SpirvCrossError::InvalidSpirv(_))
+    ));
+
+    Ok(())
+}
+
+#[test]
+pub fn active_buffer_ranges_unused() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(set = 0, binding = 0) uniform Used {
+    vec4 unused_color;
+    vec4 color;
+};
+
+layout(set = 0, binding = 1) uniform Unused {
+    vec4 tint;
+};
+
+layout(location = 0) out vec4 frag_color;
+
+void main()
+{
+    frag_color = color;
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let src = ShaderSource::from(SHADER);
+    let shader = ShaderInput::new(&src, ShaderStage::Fragment, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+    let resources = compiler.shader_resources()?;
+
+    for buffer in resources.resources_for_type(ResourceType::UniformBuffer)? {
+        let ranges = compiler.active_buffer_ranges(buffer.id)?;
+        match compiler.decoration(buffer.id, spirv::Decoration::Binding)? {
+            Some(DecorationValue::Literal(0)) => {
+                assert_eq!(1, ranges.len());
+                assert_eq!(
+                    (1, 16, 16),
+                    (ranges[0].index, ranges[0].offset, ranges[0].range)
+                );
+            }
+            _ => assert!(ranges.is_empty()),
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn hlsl_remap_vertex_attributes() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(location = 0) in vec3 in_position;
+layout(location = 1) in vec3 in_normal;
+layout(location = 2) in vec2 in_uv;
+
+layout(location = 0) out vec3 normal;
+layout(location = 1) out vec2 uv;
+
+void main()
+{
+    gl_Position = vec4(in_position, 1.0);
+    normal = in_normal;
+    uv = in_uv;
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let src = ShaderSource::from(SHADER);
+    let shader = ShaderInput::new(&src, ShaderStage::Vertex, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let mut compiler = Compiler::<Hlsl>::new(Module::from_words(&spv))?;
+    let normal = String::from("NORMAL");
+    compiler.remap_vertex_attributes([(0, "POSITION"), (1, normal.as_str())])?;
+
+    let mut options = Hlsl::options();
+    options.shader_model = HlslShaderModel::ShaderModel5_0;
+    let source = compiler.compile(&options)?.to_string();
+
+    assert!(source.contains(": POSITION"));
+    assert!(source.contains(": NORMAL"));
+    assert!(source.contains(": TEXCOORD2"));
+
+    Ok(())
+}
+
+#[test]
+pub fn workgroup_size_resolved() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(local_size_x = 8, local_size_y = 4, local_size_y_id = 0) in;
+
+layout(std430, binding = 0) buffer Data {
+    uint values[];
+};
+
+void main()
+{
+    values[gl_GlobalInvocationID.x] = gl_WorkGroupSize.y;
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let src = ShaderSource::from(SHADER);
+    let shader = ShaderInput::new(&src, ShaderStage::Compute, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let mut compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+    assert_eq!([8, 4, 1], compiler.workgroup_size()?);
+
+    let y = compiler
+        .work_group_size_specialization_constants()
+        .y
+        .unwrap();
+    compiler.set_specialization_constant_value(y.id, 16u32)?;
+    assert_eq!([8, 16, 1], compiler.workgroup_size()?);
+
+    Ok(())
+}
+
+#[test]
+pub fn glsl_set_precision() -> Result<(), SpirvCrossError> {
+    use spirv_cross2::compile::glsl::{GlslPrecision, GlslVersion};
+
+    const SHADER: &str = r##"#version 310 es
+precision highp float;
+
+layout(location = 0) flat in mediump int in_value;
+layout(location = 0) out highp int out_value;
+
+void main()
+{
+    out_value = in_value;
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let src = ShaderSource::from(SHADER);
+    let shader = ShaderInput::new(&src, ShaderStage::Fragment, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let new_compiler = || -> Result<_, SpirvCrossError> {
+        let mut compiler = Compiler::<Glsl>::new(Module::from_words(&spv))?;
+        let resources = compiler.shader_resources()?;
+        let input = resources
+            .resources_for_type(ResourceType::StageInput)?
+            .next()
+            .unwrap()
+            .id;
+        let output = resources
+            .resources_for_type(ResourceType::StageOutput)?
+            .next()
+            .unwrap()
+            .id;
+
+        compiler.set_precision(input, GlslPrecision::High)?;
+        compiler.set_precision(output, GlslPrecision::Medium)?;
+        Ok(compiler)
+    };
+
+    let mut options = Glsl::options();
+    options.version = GlslVersion::Glsl310Es;
+    let source = new_compiler()?.compile(&options)?.to_string();
+
+    assert!(!source.contains("mediump int in_value"));
+    assert!(source.contains("mediump int out_value"));
+
+    options.version = GlslVersion::Glsl100Es;
+    assert!(matches!(
+        new_compiler()?.compile(&options),
+        Err(SpirvCrossError::InvalidOperation(_))
+    ));
+
+    Ok(())
+}
+
+#[test]
+pub fn module_len() {
+    let header = [spirv::MAGIC_NUMBER, 0x0001_0000, 0, 1, 0];
+
+    let module = Module::from_words(&header);
+    assert_eq!(5, module.len());
+    assert!(!module.is_empty());
+    assert!(module.magic_is_valid());
+
+    assert!(!Module::from_words(&header[..4]).magic_is_valid());
+    assert!(!Module::from_words(&[0; 5]).magic_is_valid());
+    assert!(Module::from_words(&[]).is_empty());
+}
+
+#[test]
+pub fn try_clone() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(location = 0) in vec4 in_position;
+
+void main()
+{
+    gl_Position = in_position;
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let src = ShaderSource::from(SHADER);
+    let shader = ShaderInput::new(&src, ShaderStage::Vertex, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let mut compiler = Compiler::<Hlsl>::new(Module::from_words(&spv))?;
+    let input = compiler
+        .shader_resources()?
+        .resources_for_type(ResourceType::StageInput)?
+        .next()
+        .unwrap()
+        .id;
+    compiler.set_name(input, "renamed_position")?;
+
+    let clone = compiler.try_clone()?;
+    assert_eq!(compiler.original_words(), clone.original_words());
+
+    let mut options = Hlsl::options();
+    options.shader_model = HlslShaderModel::ShaderModel6_0;
+    let source = clone.compile(&options)?.to_string();
+    assert!(!source.contains("renamed_position"));
+
+    options.shader_model = HlslShaderModel::ShaderModel5_0;
+    let source = compiler.compile(&options)?.to_string();
+    assert!(source.contains("renamed_position"));
+
+    Ok(())
+}
+
+#[test]
+pub fn shader_resources_iter_all() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(set = 0, binding = 0) uniform Globals {
+    vec4 tint;
+};
+layout(set = 0, binding = 1) uniform sampler2D tex;
+
+layout(location = 0) in vec2 uv;
+layout(location = 0) out vec4 color;
+
+void main()
+{
+    color = texture(tex, uv) * tint;
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let src = ShaderSource::from(SHADER);
+    let shader = ShaderInput::new(&src, ShaderStage::Fragment, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+    let resources = compiler.shader_resources()?;
+
+    let all: Vec<_> = resources
+        .iter_all()
+        .map(|(ty, resource)| (ty, resource.name.to_string()))
+        .collect();
+
+    assert_eq!(
+        vec![
+            (ResourceType::UniformBuffer, String::from("Globals")),
+            (ResourceType::StageInput, String::from("uv")),
+            (ResourceType::StageOutput, String::from("color")),
+            (ResourceType::SampledImage, String::from("tex")),
+        ],
+        all
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn flip_vertex_y_vertex_like_only() -> Result<(), SpirvCrossError> {
+    use spirv_cross2::compile::FlipY;
+
+    const VERTEX: &str = r##"#version 450
+
+void main()
+{
+    gl_Position = vec4(1.0);
+}
+"##;
+
+    const FRAGMENT: &str = r##"#version 450
+
+layout(location = 0) out vec4 color;
+
+void main()
+{
+    color = gl_FragCoord;
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let compile = |source: &str, stage, flip_vertex_y| -> Result<String, SpirvCrossError> {
+        let src = ShaderSource::from(source);
+        let shader = ShaderInput::new(&src, stage, &opts, None, None).unwrap();
+        let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+        let mut options = Glsl::options();
+        options.common.flip_vertex_y = flip_vertex_y;
+
+        let compiler = Compiler::<Glsl>::new(Module::from_words(&spv))?;
+        Ok(compiler.compile(&options)?.to_string())
+    };
+
+    assert!(compile(VERTEX, ShaderStage::Vertex, FlipY::Always)?
+        .contains("gl_Position.y = -gl_Position.y"));
+    assert!(compile(VERTEX, ShaderStage::Vertex, FlipY::VertexLike)?
+        .contains("gl_Position.y = -gl_Position.y"));
+    assert!(!compile(VERTEX, ShaderStage::Vertex, FlipY::Never)?
+        .contains("gl_Position.y = -gl_Position.y"));
+
+    let fragment = compile(FRAGMENT, ShaderStage::Fragment, FlipY::Never)?;
+    assert_eq!(
+        fragment,
+        compile(FRAGMENT, ShaderStage::Fragment, FlipY::VertexLike)?
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn variable_image_type_access() -> Result<(), SpirvCrossError> {
+    use spirv_cross2::reflect::{ImageAccess, ImageClass};
+
+    const SHADER: &str = r##"#version 450
+
+layout(local_size_x = 1) in;
+
+layout(rgba8, binding = 0) uniform readonly image2D src;
+layout(rgba8, binding = 1) uniform writeonly image2D dst;
+layout(r32ui, binding = 2) uniform uimage2D counter;
+
+void main()
+{
+    ivec2 pos = ivec2(gl_GlobalInvocationID.xy);
+    imageStore(dst, pos, imageLoad(src, pos));
+    imageAtomicAdd(counter, pos, 1u);
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let src = ShaderSource::from(SHADER);
+    let shader = ShaderInput::new(&src, ShaderStage::Compute, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+    let resources = compiler.shader_resources()?;
+
+    let mut access = Vec::new();
+    for image in resources.resources_for_type(ResourceType::StorageImage)? {
+        let ImageClass::Storage {
+            access: variable, ..
+        } = compiler.variable_image_type(image.id)?.class
+        else {
+            panic!("expected a storage image");
+        };
+
+        let TypeInner::Image(ty) = compiler.type_description(image.base_type_id)?.inner else {
+            panic!("expected an image type");
+        };
+        assert!(matches!(ty.class, ImageClass::Storage { access: None, .. }));
+
+        access.push((image.name.to_string(), variable));
+    }
+
+    access.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(
+        vec![
+            (String::from("counter"), Some(ImageAccess::ReadWrite)),
+            (String::from("dst"), Some(ImageAccess::WriteOnly)),
+            (String::from("src"), Some(ImageAccess::ReadOnly)),
+        ],
+        access
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn execution_mode_argument() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout (local_size_x = 64, local_size_y = 2, local_size_z = 1) in;
+
+layout(set = 0, binding = 0) buffer Data {
+    uint values[];
+} data;
+
+void main()
+{
+    data.values[gl_GlobalInvocationID.x] *= 2u;
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Compute, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+
+    let mode = spirv::ExecutionMode::LocalSize;
+    assert_eq!(Some(64), compiler.execution_mode_argument(mode, 0)?);
+    assert_eq!(Some(2), compiler.execution_mode_argument(mode, 1)?);
+    assert_eq!(Some(1), compiler.execution_mode_argument(mode, 2)?);
+    assert_eq!(None, compiler.execution_mode_argument(mode, 3)?);
+
+    assert_eq!(
+        None,
+        compiler.execution_mode_argument(spirv::ExecutionMode::OutputVertices, 0)?
+    );
+
+    Ok(())
+}
+
+#[test]
+pub fn set_sub_constant_value() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout (local_size_x_id = 0, local_size_y_id = 1, local_size_z_id = 2) in;
+
+layout(set = 0, binding = 0) buffer Data {
+    uvec3 size;
+} data;
+
+void main()
+{
+    data.size = gl_WorkGroupSize;
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_0,
+        spirv_version: SPIRV1_0,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Compute, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let mut compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+
+    let workgroup = compiler.work_group_size_specialization_constants();
+    let composite = workgroup.builtin_workgroup_size_handle.unwrap();
+    let y = workgroup.y.unwrap().id;
+
+    assert_eq!(3, compiler.specialization_sub_constants(composite)?.len());
+
+    compiler.set_sub_constant_value(composite, 1, 8u32)?;
+    assert_eq!(8, compiler.specialization_constant_value::<u32>(y)?);
+
+    assert!(matches!(
+        compiler.set_sub_constant_value(composite, 3, 8u32),
+        Err(SpirvCrossError::IndexOutOfBounds { row: 3, column: 0 })
+    ));
+
+    Ok(())
+}
+
+#[test]
+pub fn msl_runtime_array_binding_requires_count() -> Result<(), SpirvCrossError> {
+    use spirv_cross2::compile::msl::{BindTarget, ResourceBinding};
+    use std::num::NonZeroU32;
+
+    const SHADER: &str = r##"#version 450
+#extension GL_EXT_nonuniform_qualifier : require
+
+layout(set = 0, binding = 0) uniform texture2D textures[];
+layout(set = 0, binding = 1) uniform sampler samp;
+
+layout(location = 0) flat in uint index;
+layout(location = 0) out vec4 color;
+
+void main()
+{
+    color = texture(sampler2D(textures[nonuniformEXT(index)], samp), vec2(0.5));
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Fragment, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let mut compiler = Compiler::<Msl>::new(Module::from_words(&spv))?;
+    let stage = compiler.execution_model()?;
+
+    let textures = ResourceBinding::from_qualified(0, 0);
+    let mut target = BindTarget {
+        buffer: 0,
+        texture: 0,
+        sampler: 0,
+        count: None,
+    };
+
+    assert!(matches!(
+        compiler.add_resource_binding(stage, textures, &target),
+        Err(SpirvCrossError::InvalidArgument(_))
+    ));
+
+    let samp = ResourceBinding::from_qualified(0, 1);
+    compiler.add_resource_binding(stage, samp, &target)?;
+
+    target.count = NonZeroU32::new(16);
+    compiler.add_resource_binding(stage, textures, &target)?;
+
+    Ok(())
+}
+
+#[test]
+pub fn msl_auto_plan_bindings_shared_binding() -> Result<(), SpirvCrossError> {
+    use spirv_cross2::compile::msl::{MslIndexBases, ResourceBinding};
+    use std::num::NonZeroU32;
+
+    const SHADER: &str = r##"#version 450
+#extension GL_EXT_nonuniform_qualifier : require
+
+layout(set = 0, binding = 0) uniform texture2D textures[];
+layout(set = 0, binding = 1) uniform sampler samp;
+
+layout(location = 0) flat in uint index;
+layout(location = 0) out vec4 color;
+
+void main()
+{
+    color = texture(sampler2D(textures[nonuniformEXT(index)], samp), vec2(0.5));
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_3,
+        spirv_version: SPIRV1_6,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Fragment, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let mut compiler = Compiler::<Msl>::new(Module::from_words(&spv))?;
+    let stage = compiler.execution_model()?;
+    let resources = compiler.shader_resources()?;
+
+    // Move the sampler onto the binding of the images, as HLSL-sourced SPIR-V often does.
+    let samp = resources
+        .resources_for_type(ResourceType::SeparateSamplers)?
+        .next()
+        .unwrap()
+        .id;
+    compiler.set_decoration(samp, spirv::Decoration::Binding, Some(0u32))?;
+
+    let plan = compiler.auto_plan_bindings(&resources, MslIndexBases::default())?;
+    assert_eq!(1, plan.len());
+
+    let (binding, target) = &plan[0];
+    assert_eq!(&ResourceBinding::from_qualified(0, 0), binding);
+    assert_eq!((0, 0), (target.texture, target.sampler));
+    assert_eq!(NonZeroU32::new(1), target.count);
+
+    for (binding, target) in &plan {
+        compiler.add_resource_binding(stage, *binding, target)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn constant_numeric() -> Result<(), SpirvCrossError> {
+    use spirv_cross2::reflect::ConstantNumeric;
+
+    const SHADER: &str = r##"#version 450
+
+layout (local_size_x_id = 0, local_size_y_id = 1, local_size_z_id = 2) in;
+
+layout (constant_id = 3) const int OFFSET = -4;
+layout (constant_id = 4) const float SCALE = 1.5;
+layout (constant_id = 5) const bool ENABLED = true;
+
+layout(set = 0, binding = 0) buffer Data {
+    uvec3 size;
+    int offset;
+    float scale;
+} data;
+
+void main()
+{
+    data.size = gl_WorkGroupSize;
+    data.offset = OFFSET;
+    data.scale = ENABLED ? SCALE : 0.0;
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_0,
+        spirv_version: SPIRV1_0,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Compute, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+
+    let mut values = Vec::new();
+    for constant in compiler.specialization_constants()? {
+        if constant.constant_id >= 3 {
+            values.push((
+                constant.constant_id,
+                compiler.constant_numeric(constant.id)?,
+            ));
+        }
+    }
+    values.sort_by_key(|(constant_id, _)| *constant_id);
+
+    assert_eq!(
+        vec![
+            (3, vec![ConstantNumeric::I32(-4)]),
+            (4, vec![ConstantNumeric::F32(1.5)]),
+            (5, vec![ConstantNumeric::Bool(true)]),
+        ],
+        values
+    );
+    assert_eq!(-4.0, values[0].1[0].as_f64());
+
+    let workgroup = compiler.work_group_size_specialization_constants();
+    let composite = workgroup.builtin_workgroup_size_handle.unwrap();
+    assert_eq!(
+        vec![ConstantNumeric::U32(1); 3],
+        compiler.constant_numeric(composite)?
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "glam-types")]
+pub fn glam_specialization_constant_value() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout (local_size_x_id = 0, local_size_y_id = 1, local_size_z_id = 2) in;
+
+layout(set = 0, binding = 0) buffer Data {
+    uvec3 size;
+} data;
+
+void main()
+{
+    data.size = gl_WorkGroupSize;
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_0,
+        spirv_version: SPIRV1_0,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Compute, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let mut compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+
+    let workgroup = compiler.work_group_size_specialization_constants();
+    let composite = workgroup.builtin_workgroup_size_handle.unwrap();
+
+    compiler.set_specialization_constant_value(composite, glam::UVec3::new(8, 4, 2))?;
+    assert_eq!(
+        glam::UVec3::new(8, 4, 2),
+        compiler.specialization_constant_value::<glam::UVec3>(composite)?
+    );
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "naga")]
+pub fn naga_array_strides() -> Result<(), SpirvCrossError> {
+    const SHADER: &str = r##"#version 450
+
+layout(set = 0, binding = 0, std140) uniform Block {
+    float a[2][3];
+    float b[4];
+};
+
+layout(location = 0) out float color;
+
+void main()
+{
+    color = a[1][2] + b[3];
+}
+"##;
+
+    let glslang = glslang::Compiler::acquire().unwrap();
+
+    let src = ShaderSource::from(SHADER);
+    let mut opts = CompilerOptions::default();
+
+    opts.target = Target::Vulkan {
+        version: VulkanVersion::Vulkan1_0,
+        spirv_version: SPIRV1_0,
+    };
+
+    let shader = ShaderInput::new(&src, ShaderStage::Fragment, &opts, None, None).unwrap();
+    let spv = glslang.create_shader(shader).unwrap().compile().unwrap();
+
+    let compiler = Compiler::<spirv_cross2::targets::None>::new(Module::from_words(&spv))?;
+    let resources = compiler.shader_resources()?.all_resources()?;
+
+    let mut types = naga::UniqueArena::new();
+    let block = compiler.naga_type(resources.uniform_buffers[0].base_type_id, &mut types)?;
+    let naga::TypeInner::Struct { ref members, .. } = types[block].inner else {
+        panic!("expected a struct");
+    };
+
+    let array = |ty: naga::Handle<naga::Type>| match types[ty].inner {
+        naga::TypeInner::Array {
+            base,
+            size: naga::ArraySize::Constant(size),
+            stride,
+        } => (base, size.get(), stride),
+        ref inner => panic!("expected a sized array, got {inner:?}"),
+    };
+
+    // float a[2][3]: each element of the outer array is a float[3] with a stride of 16.
+    let (inner, size, stride) = array(members[0].ty);
+    assert_eq!((2, 48), (size, stride));
+    let (_, size, stride) = array(inner);
+    assert_eq!((3, 16), (size, stride));
+
+    let (_, size, stride) = array(members[1].ty);
+    assert_eq!((4, 16), (size, stride));
+
+    Ok(())
+}